@@ -0,0 +1,103 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Each layer of parsing the bundled certdata.txt, and some queries on
+// the result.  Run with `cargo bench`; add `--features rayon` for the
+// parallel parser.
+//
+// The octal values are most of the bytes, so `syntax::multiline_octal`
+// is what matters most; `slow_multiline_octal` is the same thing with
+// the parser combinators alone, as it was before the fast path.  On a
+// single core of a Xeon server (rustc 1.95, criterion defaults but for
+// `--warm-up-time 2 --measurement-time 5`), the 1.1 MiB file gave:
+//
+//                     slow_multiline_octal   multiline_octal
+//   octal values           69 MiB/s             549 MiB/s
+//   ObjectIter             68 MiB/s, 16.3 ms    197 MiB/s, 5.6 ms
+//   CertData               70 MiB/s, 15.7 ms    171 MiB/s, 6.5 ms
+//
+// where the slow ObjectIter and CertData figures are from swapping
+// `slow_multiline_octal` into `type_and_value`.  Target: `ObjectIter` at
+// 136 MiB/s or better on such a machine, twice the combinators alone.
+
+#[macro_use]
+extern crate criterion;
+extern crate nss_certdata_parser;
+
+use criterion::{black_box, Criterion, Throughput};
+
+use nss_certdata_parser::reader::{AttrIter, RawObjectIter};
+use nss_certdata_parser::{snapshot, syntax};
+use nss_certdata_parser::{CertData, Error, ObjectIter, Usage};
+
+const FIXTURE: &[u8] = include_bytes!("../tests/data/certdata.txt");
+
+fn parsing(c: &mut Criterion) {
+    let mut group = c.benchmark_group("parse");
+    group.throughput(Throughput::Bytes(FIXTURE.len() as u64));
+    group.bench_function("AttrIter", |b| b.iter(|| {
+        AttrIter::new(black_box(FIXTURE)).map(Result::unwrap).count()
+    }));
+    group.bench_function("RawObjectIter", |b| b.iter(|| {
+        RawObjectIter::new(black_box(FIXTURE)).map(Result::unwrap).count()
+    }));
+    group.bench_function("ObjectIter", |b| b.iter(|| {
+        ObjectIter::new(black_box(FIXTURE)).map(Result::unwrap).count()
+    }));
+    group.bench_function("CertData", |b| b.iter(|| {
+        CertData::from_iter(ObjectIter::new(black_box(FIXTURE))).unwrap()
+    }));
+    #[cfg(feature = "rayon")]
+    group.bench_function("parallel", |b| b.iter(|| {
+        nss_certdata_parser::parallel::parse_cert_data(black_box(FIXTURE)).unwrap()
+    }));
+    group.finish();
+}
+
+// Just the MULTILINE_OCTAL values, each decoded on its own.
+fn octal(c: &mut Criterion) {
+    let text = std::str::from_utf8(FIXTURE).unwrap();
+    let values: Vec<&[u8]> = text.split("MULTILINE_OCTAL\n").skip(1)
+        .map(|v| &v.as_bytes()[..v.find("\nEND\n").unwrap()])
+        .collect();
+    let mut group = c.benchmark_group("octal");
+    group.throughput(Throughput::Bytes(values.iter().map(|v| v.len() as u64).sum()));
+    group.bench_function("multiline_octal", |b| b.iter(|| {
+        values.iter().map(|&v| syntax::multiline_octal(black_box(v)).unwrap().1.len())
+            .sum::<usize>()
+    }));
+    group.bench_function("slow_multiline_octal", |b| b.iter(|| {
+        values.iter().map(|&v| syntax::slow_multiline_octal(black_box(v)).unwrap().1.len())
+            .sum::<usize>()
+    }));
+    group.finish();
+}
+
+fn collecting(c: &mut Criterion) {
+    let objs: Vec<_> = ObjectIter::new(FIXTURE).map(Result::unwrap).collect();
+    c.bench_function("CertData::from_iter (parsed objects)", |b| b.iter(|| {
+        CertData::from_iter(objs.iter().cloned().map(Ok::<_, Error>)).unwrap()
+    }));
+
+    let data = CertData::from_iter(ObjectIter::new(FIXTURE)).unwrap();
+    let mut buf = Vec::new();
+    snapshot::write_snapshot(&mut buf, &data).unwrap();
+    c.bench_function("load_snapshot", |b| b.iter(|| {
+        snapshot::load_snapshot(black_box(&buf)).unwrap()
+    }));
+}
+
+fn lookups(c: &mut Criterion) {
+    let data = CertData::from_iter(ObjectIter::new(FIXTURE)).unwrap();
+    c.bench_function("trust_for_cert (every cert)", |b| b.iter(|| {
+        data.certs().iter().filter(|cert| data.trust_for_cert(cert).is_some()).count()
+    }));
+    c.bench_function("trusted_certs", |b| b.iter(|| {
+        data.trusted_certs(black_box(Usage::TlsServer)).len()
+    }));
+    c.bench_function("distrusts", |b| b.iter(|| data.distrusts(black_box(Usage::Email)).len()));
+}
+
+criterion_group!(benches, parsing, octal, collecting, lookups);
+criterion_main!(benches);
@@ -6,19 +6,24 @@ extern crate nss_certdata_parser;
 
 use std::fs::File;
 use std::env::args;
-use std::io::BufReader;
+use std::io::{self, BufReader};
 
 use nss_certdata_parser::{ObjectIter, CertData, Usage};
+use nss_certdata_parser::codegen::{self, CodegenOptions, Format};
 
 fn main() {
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
     for path in args().skip(1) {
         let objs = ObjectIter::new(BufReader::new(File::open(path).unwrap()));
         let stuff = CertData::from_iter(objs).unwrap();
-        println!("pub const ALL_CERTS: &'static [Certificate<'static>] = &{:#?};",
-                 stuff.certs());
-        println!("pub const TLS_SERVER_TRUST_ROOTS: &'static [Certificate<'static>] = &{:#?};",
-                 stuff.trusted_certs(Usage::TlsServer));
-        println!("pub const TLS_SERVER_DISTRUSTS: &'static [Trust<'static>] = &{:#?};",
-                 stuff.distrusts(Usage::TlsServer));
+        let der = CodegenOptions { format: Format::Der, ..CodegenOptions::default() };
+        let all: Vec<_> = stuff.certs().iter().collect();
+        codegen::write_roots(&mut out, "ALL_CERTS", &all, &der).unwrap();
+        codegen::write_roots(&mut out, "TLS_SERVER_TRUST_ROOTS",
+                             &stuff.trusted_certs(Usage::TlsServer),
+                             &CodegenOptions::default()).unwrap();
+        codegen::write_distrusts(&mut out, "TLS_SERVER_DISTRUSTS",
+                                 &stuff.distrusts(Usage::TlsServer)).unwrap();
     }
 }
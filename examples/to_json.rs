@@ -3,32 +3,22 @@
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
 extern crate nss_certdata_parser;
+extern crate serde_json;
 
+use std::collections::BTreeMap;
 use std::fs::File;
 use std::env::args;
-use std::io::BufReader;
+use std::io::{self, BufReader};
 
 use nss_certdata_parser::reader::RawObjectIter;
-use nss_certdata_parser::syntax::Value;
 
 fn main() {
     for path in args().skip(1) {
-        println!("[");
-        for res_obj in RawObjectIter::new(BufReader::new(File::open(path).unwrap())) {
-            println!("   {{");
-            for (k, v) in res_obj.unwrap() {
-                let vj = match v {
-                    Value::Token(t, vv) => format!("{{ type: {:?}, value: {:?} }}", t, vv),
-                    Value::String(s) => format!("{:?}", s),
-                    // base64 would be a more usual (and compact) way
-                    // to embed an octet stream into JSON, but that
-                    // would need additional crate dependences.
-                    Value::Binary(b) => format!("{:?}", b).trim_matches('"').to_owned(),
-                };
-                println!("      {:?}: {},", k, vj);
-            }
-            println!("   }},");
-        }
-        println!("]");
+        // BTreeMap, so that the attributes come out in a stable order.
+        let objs: Vec<BTreeMap<_, _>> = RawObjectIter::new(BufReader::new(File::open(path).unwrap()))
+            .map(|res_obj| res_obj.unwrap().into_iter().collect())
+            .collect();
+        serde_json::to_writer_pretty(io::stdout(), &objs).unwrap();
+        println!();
     }
 }
@@ -0,0 +1,27 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// As for `attr_iter`, collected into a `CertData` and then queried.
+
+#![no_main]
+
+use std::io::BufReader;
+
+use libfuzzer_sys::fuzz_target;
+use nss_certdata_parser::{CertData, ObjectIter, Usage};
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((&size, input)) = data.split_first() {
+        let src = BufReader::with_capacity(size as usize + 1, input);
+        if let Ok(data) = CertData::from_iter(ObjectIter::new(src)) {
+            for cert in data.certs() {
+                let _ = data.trust_for_cert(cert);
+            }
+            for &usage in &[Usage::TlsServer, Usage::Email, Usage::CodeSigning] {
+                let _ = data.trusted_certs(usage);
+                let _ = data.distrusts(usage);
+            }
+        }
+    }
+});
@@ -0,0 +1,23 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// As for `attr_iter`, through the structured layer.
+
+#![no_main]
+
+use std::io::BufReader;
+
+use libfuzzer_sys::fuzz_target;
+use nss_certdata_parser::ObjectIter;
+
+fuzz_target!(|data: &[u8]| {
+    if let Some((&size, input)) = data.split_first() {
+        let src = BufReader::with_capacity(size as usize + 1, input);
+        for obj in ObjectIter::new(src) {
+            if obj.is_err() {
+                break;
+            }
+        }
+    }
+});
@@ -0,0 +1,676 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// `certdata`: looking at, checking and converting certdata.txt files
+// from the command line.  See `USAGE` for the commands.
+
+extern crate nss_certdata_parser;
+
+use std::collections::BTreeMap;
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::Path;
+use std::process;
+
+use nss_certdata_parser::export::{self, colon_hex, sort_certs, Column, CsvOptions, Order,
+                                  PemOptions};
+use nss_certdata_parser::x509::PublicKey;
+use nss_certdata_parser::format::{self, FormatOptions};
+use nss_certdata_parser::lint::{self, LintOptions, Rule, Severity, UnknownRule};
+use nss_certdata_parser::{der, diff, name, p11kit, pem, report, snapshot};
+use nss_certdata_parser::{CertData, Certificate, DerError, Error, ObjectIter, TrustLevel, Usage};
+
+const USAGE: &str = "\
+usage: certdata <command> [options] <file>...
+
+commands:
+  list [--usage U] [--trust T] FILE   list certificates, optionally only those
+                                      with trust level T (default trusted) for U
+  show LABEL|FINGERPRINT FILE         describe certificates by label, or by
+                                      SHA-256 or SHA-1 fingerprint (or a prefix
+                                      of at least 8 hex digits)
+  export --format F [options] FILE    convert to pem, der, json, csv, tsv, p11kit
+                                      or snapshot
+  diff OLD NEW                        list changes between two files
+  lint [options] FILE                 check a file for likely mistakes
+  fmt [--check] [--sort] FILE...      rewrite files in the canonical layout;
+                                      with --check, list those that aren't
+  stats FILE                          summarize a file
+
+FILE may be `-` for standard input, and except for lint and fmt may be a
+snapshot (from export --format snapshot) instead of certdata.txt.  Usages
+are tls-server, email and code-signing; trust levels are trusted,
+must-verify and distrusted.
+
+export options:
+  -o, --output PATH    write to PATH (a directory, for der) instead of stdout
+  --usage U            for pem and der, the usage to export roots for
+                       (default tls-server)
+  --columns A,B,...    for csv and tsv, the columns to write
+
+lint options:
+  --only R,...         check only these rules
+  --disable R,...      don't check these rules
+  The rules are bad-object, bad-certificate, duplicate-label, comment-label,
+  der-mismatch, missing-trust, missing-hash, weak-signature, weak-key,
+  expired-trusted and future-distrust-after.  Notes don't count as problems.
+
+fmt options:
+  --check              don't rewrite anything
+  --sort               put certificates in label order, each followed by
+                       its trust objects
+
+Exit status is 0 on success; 1 if diff finds differences, lint finds
+problems, show finds nothing or fmt --check finds files to reformat; 2 for
+usage errors; and 3 for files that can't be read, parsed or written.
+";
+
+const USAGES: &[Usage] = &[Usage::TlsServer, Usage::Email, Usage::CodeSigning];
+
+#[derive(Debug, PartialEq, Eq)]
+enum Failure {
+    Usage(String),
+    Error(String),
+}
+
+impl From<io::Error> for Failure {
+    fn from(err: io::Error) -> Self {
+        Failure::Error(format!("writing output: {}", err))
+    }
+}
+
+impl From<Error> for Failure {
+    fn from(err: Error) -> Self {
+        Failure::Error(err.to_string())
+    }
+}
+
+impl From<DerError> for Failure {
+    fn from(err: DerError) -> Self {
+        Error::from(err).into()
+    }
+}
+
+fn usage_error<T, S: Into<String>>(msg: S) -> Result<T, Failure> {
+    Err(Failure::Usage(msg.into()))
+}
+
+/// `Ok(false)` is a negative answer, not an error.
+type Outcome = Result<bool, Failure>;
+
+struct Args {
+    free: Vec<String>,
+    opts: Vec<(&'static str, String)>,
+}
+
+// An option's long name, its short form if any, and whether it takes a
+// value.
+type OptSpec = (&'static str, Option<char>, bool);
+
+// Options that take values can be given as `--name VALUE`, `--name=VALUE`
+// or, if there's a short form, `-n VALUE`; flags as `--name` or `-n`.
+fn parse_args(args: &[String], spec: &[OptSpec]) -> Result<Args, Failure> {
+    let mut parsed = Args { free: Vec::new(), opts: Vec::new() };
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        if arg == "--" {
+            parsed.free.extend(iter.cloned());
+            break;
+        }
+        let (found, inline) = if let Some(opt) = arg.strip_prefix("--") {
+            let (key, value) = match opt.find('=') {
+                Some(eq) => (&opt[..eq], Some(opt[eq + 1..].to_owned())),
+                None => (opt, None),
+            };
+            (spec.iter().find(|&&(long, _, _)| long == key), value)
+        } else if let Some(opt) = arg.strip_prefix('-').filter(|opt| !opt.is_empty()) {
+            let mut chars = opt.chars();
+            let short = chars.next();
+            let rest = chars.as_str();
+            (spec.iter().find(|&&(_, s, _)| s.is_some() && s == short),
+             if rest.is_empty() { None } else { Some(rest.to_owned()) })
+        } else {
+            parsed.free.push(arg.clone());
+            continue;
+        };
+        let long = match found {
+            Some(&(long, _, true)) => long,
+            Some(&(long, _, false)) => {
+                if inline.is_some() {
+                    return usage_error(format!("{} doesn't take a value", arg));
+                }
+                parsed.opts.push((long, String::new()));
+                continue;
+            }
+            None => return usage_error(format!("unknown option {}", arg)),
+        };
+        let value = match inline.or_else(|| iter.next().cloned()) {
+            Some(value) => value,
+            None => return usage_error(format!("{} needs a value", arg)),
+        };
+        parsed.opts.push((long, value));
+    }
+    Ok(parsed)
+}
+
+impl Args {
+    fn get(&self, long: &str) -> Option<&str> {
+        self.opts.iter().rev().find(|&&(l, _)| l == long).map(|(_, v)| &v[..])
+    }
+
+    fn has(&self, long: &str) -> bool {
+        self.opts.iter().any(|&(l, _)| l == long)
+    }
+
+    fn files(&self, names: &[&str]) -> Result<&[String], Failure> {
+        if self.free.len() != names.len() {
+            return usage_error(format!("expected {}", names.join(" ")));
+        }
+        Ok(&self.free)
+    }
+}
+
+fn parse_usage(s: &str) -> Result<Usage, Failure> {
+    match s {
+        "tls-server" | "tls" => Ok(Usage::TlsServer),
+        "email" => Ok(Usage::Email),
+        "code-signing" => Ok(Usage::CodeSigning),
+        _ => usage_error(format!("unknown usage {:?}", s)),
+    }
+}
+
+fn usage_name(usage: Usage) -> &'static str {
+    match usage {
+        Usage::TlsServer => "tls-server",
+        Usage::Email => "email",
+        Usage::CodeSigning => "code-signing",
+    }
+}
+
+fn parse_level(s: &str) -> Result<TrustLevel, Failure> {
+    match s {
+        "trusted" => Ok(TrustLevel::TrustedDelegator),
+        "must-verify" => Ok(TrustLevel::MustVerify),
+        "distrusted" => Ok(TrustLevel::Distrust),
+        _ => usage_error(format!("unknown trust level {:?}", s)),
+    }
+}
+
+fn level_name(level: TrustLevel) -> &'static str {
+    match level {
+        TrustLevel::TrustedDelegator => "trusted",
+        TrustLevel::MustVerify => "must-verify",
+        TrustLevel::Distrust => "distrusted",
+    }
+}
+
+fn trust_level(data: &CertData, cert: &Certificate, usage: Usage) -> TrustLevel {
+    data.trust_for_cert(cert).map_or(TrustLevel::MustVerify, |t| t.trust_level(usage))
+}
+
+fn read_input(path: &str) -> Result<Vec<u8>, Failure> {
+    let mut buf = Vec::new();
+    let result = if path == "-" {
+        io::stdin().read_to_end(&mut buf)
+    } else {
+        File::open(path).and_then(|mut f| f.read_to_end(&mut buf))
+    };
+    result.map_err(|err| Failure::Error(format!("{}: {}", path, err)))?;
+    Ok(buf)
+}
+
+// Says where in the file a syntax error is.
+fn file_error(path: &str, buf: &[u8], err: Error) -> Failure {
+    Failure::Error(match err {
+        Error::ParseError(ref e) => {
+            let end = (e.byte_offset as usize).min(buf.len());
+            let line = 1 + buf[..end].iter().filter(|&&b| b == b'\n').count();
+            format!("{}:{}: {}", path, line, err)
+        }
+        _ => format!("{}: {}", path, err),
+    })
+}
+
+fn load(path: &str) -> Result<CertData, Failure> {
+    let buf = read_input(path)?;
+    if snapshot::is_snapshot(&buf) {
+        return snapshot::load_snapshot(&buf)
+            .map(|data| data.to_cert_data())
+            .map_err(|err| file_error(path, &buf, err.into()));
+    }
+    CertData::from_iter(ObjectIter::new(&buf[..])).map_err(|err| file_error(path, &buf, err))
+}
+
+fn key_description(cert: &Certificate) -> Result<String, Error> {
+    let key = PublicKey::parse(cert.x509()?.spki)?;
+    Ok(match (key.curve_name(), key.bits()) {
+        (Some(curve), _) => format!("{} {}", key.algorithm(), curve),
+        (None, Some(bits)) => format!("{} {}", key.algorithm(), bits),
+        (None, None) => key.algorithm(),
+    })
+}
+
+fn list<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[("usage", None, true), ("trust", None, true)])?;
+    let file = &args.files(&["FILE"])?[0];
+    let usage = args.get("usage").map(parse_usage).transpose()?;
+    let level = args.get("trust").map(parse_level).transpose()?;
+    let data = load(file)?;
+
+    // With only a usage, list what's trusted for it; with only a level,
+    // what has it for any usage.
+    let usages = usage.map_or_else(|| USAGES.to_vec(), |u| vec![u]);
+    let wanted = level.or(usage.map(|_| TrustLevel::TrustedDelegator));
+    writeln!(out, "{:<16}  {:<11} {:<11} {:<12} LABEL", "SHA-256", "TLS-SERVER", "EMAIL",
+             "CODE-SIGNING")?;
+    for cert in sort_certs(data.certs().iter().collect(), Order::Label) {
+        if let Some(wanted) = wanted {
+            if !usages.iter().any(|&u| trust_level(&data, cert, u) == wanted) {
+                continue;
+            }
+        }
+        let levels: Vec<_> = USAGES.iter().map(|&u| level_name(trust_level(&data, cert, u)))
+            .collect();
+        writeln!(out, "{}  {:<11} {:<11} {:<12} {}", &report::hex(&cert.sha256_fingerprint())[..16],
+                 levels[0], levels[1], levels[2], cert.label)?;
+    }
+    Ok(true)
+}
+
+// A label, or a prefix of a SHA-256 or SHA-1 fingerprint.
+fn matches(cert: &Certificate, query: &str) -> bool {
+    if cert.label == query {
+        return true;
+    }
+    let digits = query.replace(':', "").to_uppercase();
+    digits.len() >= 8 && digits.chars().all(|c| c.is_ascii_hexdigit()) &&
+        (report::hex(&cert.sha256_fingerprint()).starts_with(&digits) ||
+         report::hex(&cert.sha1_fingerprint()).starts_with(&digits))
+}
+
+fn describe<W: Write>(out: &mut W, data: &CertData, cert: &Certificate) -> Result<(), Failure> {
+    let info = cert.x509()?;
+    let field = |out: &mut W, key: &str, value: &str| writeln!(out, "{:<26}{}", key, value);
+    field(out, "Label:", &cert.label)?;
+    field(out, "Subject:", &name::to_rfc4514(&cert.subject)?)?;
+    field(out, "Issuer:", &name::to_rfc4514(&cert.issuer)?)?;
+    field(out, "Serial:", &colon_hex(der::parse_one(info.serial, der::tag::INTEGER)?.value))?;
+    field(out, "SHA-256:", &colon_hex(&cert.sha256_fingerprint()))?;
+    field(out, "SHA-1:", &colon_hex(&cert.sha1_fingerprint()))?;
+    field(out, "Not before:", &info.not_before.to_string())?;
+    field(out, "Not after:", &info.not_after.to_string())?;
+    field(out, "Key:", &key_description(cert)?)?;
+    for &usage in USAGES {
+        field(out, &format!("Trust ({}):", usage_name(usage)),
+              level_name(trust_level(data, cert, usage)))?;
+    }
+    for &usage in USAGES {
+        if let Some(after) = cert.distrust_after(usage) {
+            field(out, &format!("Distrust after ({}):", usage_name(usage)), &after.to_string())?;
+        }
+    }
+    if let Some(policy) = cert.mozilla_ca_policy {
+        field(out, "Mozilla CA policy:", if policy { "yes" } else { "no" })?;
+    }
+    out.write_all(pem::encode("CERTIFICATE", &cert.cert).as_bytes())?;
+    Ok(())
+}
+
+fn show<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[])?;
+    let free = args.files(&["LABEL|FINGERPRINT", "FILE"])?;
+    let data = load(&free[1])?;
+    let found: Vec<_> = sort_certs(data.certs().iter().collect(), Order::Label).into_iter()
+        .filter(|cert| matches(cert, &free[0]))
+        .collect();
+    if found.is_empty() {
+        eprintln!("certdata: no certificate matches {:?}", free[0]);
+        return Ok(false);
+    }
+    for (i, cert) in found.into_iter().enumerate() {
+        if i > 0 {
+            writeln!(out)?;
+        }
+        describe(out, &data, cert)?;
+    }
+    Ok(true)
+}
+
+fn write_output<W: Write>(out: &mut W, path: Option<&str>, bytes: &[u8]) -> Result<(), Failure> {
+    match path {
+        Some(path) => {
+            fs::write(path, bytes).map_err(|err| Failure::Error(format!("{}: {}", path, err)))
+        }
+        None => Ok(out.write_all(bytes)?),
+    }
+}
+
+fn export<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[("format", Some('f'), true), ("output", Some('o'), true),
+                                  ("usage", None, true), ("columns", None, true)])?;
+    let file = &args.files(&["FILE"])?[0];
+    let format = match args.get("format") {
+        Some(format) => format,
+        None => return usage_error("export needs --format"),
+    };
+    let uses_usage = format == "pem" || format == "der";
+    let uses_columns = format == "csv" || format == "tsv";
+    if !uses_usage && args.get("usage").is_some() {
+        return usage_error(format!("--usage doesn't apply to {}", format));
+    }
+    if !uses_columns && args.get("columns").is_some() {
+        return usage_error(format!("--columns doesn't apply to {}", format));
+    }
+    let usage = args.get("usage").map_or(Ok(Usage::TlsServer), parse_usage)?;
+    let columns = match args.get("columns") {
+        Some(list) => list.split(',')
+            .map(|c| c.trim().parse::<Column>().map_err(|e| Failure::Usage(e.to_string())))
+            .collect::<Result<Vec<_>, _>>()?,
+        None => Column::all(),
+    };
+
+    let mut bytes = Vec::new();
+    match format {
+        "der" => {
+            let dir = match args.get("output") {
+                Some(dir) => Path::new(dir),
+                None => return usage_error("der export needs --output DIRECTORY"),
+            };
+            let data = load(file)?;
+            let io_error = |err: io::Error| Failure::Error(format!("{}: {}", dir.display(), err));
+            fs::create_dir_all(dir).map_err(io_error)?;
+            for cert in data.trusted_certs(usage) {
+                let hex = report::hex(&cert.sha256_fingerprint()).to_lowercase();
+                fs::write(dir.join(format!("{}.der", hex)), &cert.cert[..]).map_err(io_error)?;
+            }
+            return Ok(true);
+        }
+        "pem" => export::write_pem_bundle(&mut bytes, &load(file)?, usage, &PemOptions::default())?,
+        "json" => bytes = load(file)?.to_json_report()?.into_bytes(),
+        "csv" | "tsv" => {
+            let opts = CsvOptions {
+                columns,
+                separator: if format == "csv" { ',' } else { '\t' },
+                ..CsvOptions::default()
+            };
+            export::write_csv(&mut bytes, &load(file)?, &opts)?;
+        }
+        "p11kit" => p11kit::write_p11kit(&mut bytes, &load(file)?)?,
+        "snapshot" => snapshot::write_snapshot(&mut bytes, &load(file)?)?,
+        _ => return usage_error(format!("unknown format {:?}", format)),
+    }
+    write_output(out, args.get("output"), &bytes)?;
+    Ok(true)
+}
+
+fn diff<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[])?;
+    let free = args.files(&["OLD", "NEW"])?;
+    let changes = diff::diff(&load(&free[0])?, &load(&free[1])?);
+    for change in &changes {
+        writeln!(out, "{}", change)?;
+    }
+    Ok(changes.is_empty())
+}
+
+fn parse_rules(list: &str) -> Result<Vec<Rule>, Failure> {
+    list.split(',').map(|name| name.parse().map_err(|err: UnknownRule| {
+        Failure::Usage(err.to_string())
+    })).collect()
+}
+
+fn lint<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[("only", None, true), ("disable", None, true)])?;
+    let file = &args.files(&["FILE"])?[0];
+    let mut opts = LintOptions::default();
+    if let Some(only) = args.get("only") {
+        opts.rules = parse_rules(only)?;
+    }
+    if let Some(disable) = args.get("disable") {
+        let disabled = parse_rules(disable)?;
+        opts.rules.retain(|rule| !disabled.contains(rule));
+    }
+    let buf = read_input(file)?;
+    let diags = lint::lint(&buf, &opts).map_err(|err| file_error(file, &buf, err))?;
+    for diag in &diags {
+        writeln!(out, "{}:{}", file, diag)?;
+    }
+    Ok(diags.iter().all(|diag| diag.severity() == Severity::Note))
+}
+
+fn fmt<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[("check", None, false), ("sort", None, false)])?;
+    if args.free.is_empty() {
+        return usage_error("expected FILE...");
+    }
+    let opts = FormatOptions { sort: args.has("sort") };
+    let mut clean = true;
+    for file in &args.free {
+        let buf = read_input(file)?;
+        let formatted = format::format_certdata(&buf, &opts)
+            .map_err(|err| file_error(file, &buf, err))?;
+        let changed = formatted.as_bytes() != &buf[..];
+        if args.has("check") {
+            if changed {
+                writeln!(out, "{}", file)?;
+                clean = false;
+            }
+        } else if file == "-" {
+            // Standard input goes to standard output, changed or not.
+            out.write_all(formatted.as_bytes())?;
+        } else if changed {
+            write_output(out, Some(file), formatted.as_bytes())?;
+        }
+    }
+    Ok(clean)
+}
+
+fn stats<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let args = parse_args(args, &[])?;
+    let file = &args.files(&["FILE"])?[0];
+    let data = load(file)?;
+    let line = |out: &mut W, key: &str, n: usize| writeln!(out, "{:<34}{:>5}", key, n);
+
+    line(out, "certificates:", data.certs().len())?;
+    line(out, "trust objects:", data.trusts().len())?;
+    for &usage in USAGES {
+        line(out, &format!("trusted for {}:", usage_name(usage)),
+             data.trusted_certs(usage).len())?;
+    }
+    for &usage in USAGES {
+        line(out, &format!("distrusted for {}:", usage_name(usage)),
+             data.distrusts(usage).len())?;
+    }
+    for &usage in USAGES {
+        let n = data.certs().iter().filter(|c| c.distrust_after(usage).is_some()).count();
+        if n > 0 {
+            line(out, &format!("with {} distrust-after:", usage_name(usage)), n)?;
+        }
+    }
+    let mut keys = BTreeMap::new();
+    for cert in data.certs() {
+        *keys.entry(key_description(cert)?).or_insert(0) += 1;
+    }
+    writeln!(out, "keys:")?;
+    for (key, n) in keys {
+        line(out, &format!("  {}:", key), n)?;
+    }
+    Ok(true)
+}
+
+fn run<W: Write>(args: &[String], out: &mut W) -> Outcome {
+    let (command, rest) = match args.split_first() {
+        Some((command, rest)) => (&command[..], rest),
+        None => return usage_error("no command given"),
+    };
+    match command {
+        "list" => list(rest, out),
+        "show" => show(rest, out),
+        "export" => export(rest, out),
+        "diff" => diff(rest, out),
+        "lint" => lint(rest, out),
+        "fmt" => fmt(rest, out),
+        "stats" => stats(rest, out),
+        "help" | "-h" | "--help" => {
+            out.write_all(USAGE.as_bytes())?;
+            Ok(true)
+        }
+        _ => usage_error(format!("unknown command {:?}", command)),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let stdout = io::stdout();
+    let mut out = stdout.lock();
+    let result = run(&args, &mut out).and_then(|ok| {
+        out.flush()?;
+        Ok(ok)
+    });
+    process::exit(match result {
+        Ok(true) => 0,
+        Ok(false) => 1,
+        Err(Failure::Usage(msg)) => {
+            eprintln!("certdata: {}\nTry `certdata help` for more information.", msg);
+            2
+        }
+        Err(Failure::Error(msg)) => {
+            eprintln!("certdata: {}", msg);
+            3
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{parse_args, run, Failure, Outcome};
+
+    const FIXTURE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/certdata.txt");
+
+    fn certdata(args: &[&str]) -> (Outcome, String) {
+        let args: Vec<String> = args.iter().map(|&a| a.replace("FIXTURE", FIXTURE)).collect();
+        let mut out = Vec::new();
+        let result = run(&args, &mut out);
+        (result, String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_args() {
+        let args: Vec<String> = ["a", "--format=pem", "-o", "out", "-ofile", "--", "-b"]
+            .iter().map(|&a| a.to_owned()).collect();
+        let spec = [("format", None, true), ("output", Some('o'), true), ("check", None, false)];
+        let parsed = parse_args(&args, &spec).unwrap();
+        assert_eq!(parsed.free, vec!["a", "-b"]);
+        assert_eq!(parsed.get("format"), Some("pem"));
+        assert_eq!(parsed.get("output"), Some("file"));
+        assert!(parsed.files(&["FILE"]).is_err());
+        assert!(!parsed.has("check"));
+
+        let args = vec!["--frobnicate".to_owned()];
+        assert!(parse_args(&args, &[]).is_err());
+        let args = vec!["--format".to_owned()];
+        assert!(parse_args(&args, &spec).is_err());
+        let args = vec!["--check".to_owned(), "x".to_owned()];
+        let parsed = parse_args(&args, &spec).unwrap();
+        assert!(parsed.has("check"));
+        assert_eq!(parsed.free, vec!["x"]);
+        let args = vec!["--check=yes".to_owned()];
+        assert!(parse_args(&args, &spec).is_err());
+    }
+
+    #[test]
+    fn test_list() {
+        let (result, out) = certdata(&["list", "--usage", "email", "FIXTURE"]);
+        assert_eq!(result, Ok(true));
+        let lines: Vec<_> = out.lines().collect();
+        assert!(lines[0].starts_with("SHA-256 "));
+        assert!(lines.iter().skip(1).all(|l| l[18..].starts_with("trusted     trusted") ||
+                                              l[30..].starts_with("trusted")));
+        assert!(lines.iter().any(|l| l.ends_with(" Amazon Root CA 1")));
+        assert!(!lines.iter().any(|l| l.ends_with(" E-Tugra Certification Authority")));
+
+        let (_, out) = certdata(&["list", "--trust", "distrusted", "FIXTURE"]);
+        assert_eq!(out.lines().count(), 2);
+        assert!(out.ends_with(" Explicitly Distrust Example Compromised CA\n"));
+        assert!(certdata(&["list", "--usage", "ssh", "FIXTURE"]).0.is_err());
+    }
+
+    #[test]
+    fn test_show() {
+        let (result, out) = certdata(&["show", "ACCVRAIZ1", "FIXTURE"]);
+        assert_eq!(result, Ok(true));
+        assert!(out.contains("\nSerial:                   5E:C3:B7:A6:43:7F:A4:E0\n"));
+        assert!(out.contains("\nKey:                      RSA 4096\n"));
+        assert!(out.contains("\nTrust (code-signing):     must-verify\n"));
+        assert!(out.ends_with("-----END CERTIFICATE-----\n"));
+
+        let (_, by_hash) = certdata(&["show", "9a:6e:c0:12", "FIXTURE"]);
+        assert_eq!(by_hash, out);
+        assert_eq!(certdata(&["show", "nonesuch", "FIXTURE"]), (Ok(false), String::new()));
+    }
+
+    #[test]
+    fn test_export() {
+        let (result, out) = certdata(&["export", "--format", "csv", "--columns", "label,sha1",
+                                       "FIXTURE"]);
+        assert_eq!(result, Ok(true));
+        assert!(out.starts_with("label,sha1\r\n"));
+        let (_, out) = certdata(&["export", "-f", "pem", "--usage", "email", "FIXTURE"]);
+        assert!(out.starts_with("-----BEGIN CERTIFICATE-----\n"));
+
+        // A snapshot reads back as the same thing.
+        let snap = env::temp_dir().join(format!("certdata-test-{}.snap", process::id()));
+        let snap = snap.to_str().unwrap();
+        assert_eq!(certdata(&["export", "-f", "snapshot", "-o", snap, "FIXTURE"]).0, Ok(true));
+        assert_eq!(certdata(&["diff", "FIXTURE", snap]), (Ok(true), String::new()));
+        fs::remove_file(snap).unwrap();
+
+        match certdata(&["export", "--format", "xml", "FIXTURE"]).0 {
+            Err(Failure::Usage(_)) => (),
+            other => panic!("{:?}", other),
+        }
+        match certdata(&["export", "--format", "json", "--usage", "email", "FIXTURE"]).0 {
+            Err(Failure::Usage(_)) => (),
+            other => panic!("{:?}", other),
+        }
+        match certdata(&["export", "--format", "json", "/nonexistent/certdata.txt"]).0 {
+            Err(Failure::Error(msg)) => assert!(msg.starts_with("/nonexistent/certdata.txt: ")),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_other_commands() {
+        assert_eq!(certdata(&["diff", "FIXTURE", "FIXTURE"]), (Ok(true), String::new()));
+        let (result, out) = certdata(&["lint", "FIXTURE"]);
+        assert_eq!(result, Ok(false));
+        assert!(out.contains(": warning: ACCVRAIZ1: signed with SHA-1 [weak-signature]\n"));
+        let only = ["lint", "--only", "weak-key,der-mismatch", "FIXTURE"];
+        assert_eq!(certdata(&only), (Ok(true), String::new()));
+        let disable = ["lint", "--disable", "weak-signature,expired-trusted,missing-hash",
+                       "FIXTURE"];
+        let (result, out) = certdata(&disable);
+        assert_eq!(result, Ok(true));
+        assert!(out.lines().all(|l| l.contains(": note: ")));
+        match certdata(&["lint", "--only", "weak-keys", "FIXTURE"]).0 {
+            Err(Failure::Usage(msg)) => assert_eq!(msg, "unknown lint rule \"weak-keys\""),
+            other => panic!("{:?}", other),
+        }
+        let (result, out) = certdata(&["stats", "FIXTURE"]);
+        assert_eq!(result, Ok(true));
+        assert!(out.starts_with("certificates:                       143\n"));
+        assert!(out.contains("\n  EC P-384:"));
+        assert_eq!(certdata(&["fmt", "--check", "FIXTURE"]), (Ok(true), String::new()));
+        let (result, out) = certdata(&["fmt", "--check", "--sort", "FIXTURE"]);
+        assert_eq!(result, Ok(false));
+        assert_eq!(out, format!("{}\n", FIXTURE));
+        match certdata(&["frob"]).0 {
+            Err(Failure::Usage(_)) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+}
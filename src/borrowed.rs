@@ -0,0 +1,271 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Borrowed versions of `Certificate`, `Trust` and `CertData`, which
+// can be built in const contexts (see `codegen::write_cert_data_ref`),
+// so that compiled-in roots can be queried the same way as ones parsed
+// at run time.
+
+use std::borrow::Cow;
+
+use collect::CertData;
+use structured::{Certificate, Object, Trust, TrustLevel, Usage};
+use time::Time;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CertificateRef<'a> {
+    pub label: &'a str,
+    pub cert: &'a [u8],
+    pub issuer: &'a [u8],
+    pub serial: &'a [u8],
+    pub subject: &'a [u8],
+    pub server_distrust_after: Option<Time>,
+    pub email_distrust_after: Option<Time>,
+    pub mozilla_ca_policy: Option<bool>,
+}
+
+impl<'a> CertificateRef<'a> {
+    /// As `Certificate::distrust_after`.
+    pub fn distrust_after(&self, usage: Usage) -> Option<Time> {
+        match usage {
+            Usage::TlsServer => self.server_distrust_after,
+            Usage::Email => self.email_distrust_after,
+            Usage::CodeSigning => None,
+        }
+    }
+}
+
+impl<'a> From<&'a Certificate> for CertificateRef<'a> {
+    fn from(cert: &'a Certificate) -> Self {
+        CertificateRef {
+            label: &cert.label,
+            cert: &cert.cert,
+            issuer: &cert.issuer,
+            serial: &cert.serial,
+            subject: &cert.subject,
+            server_distrust_after: cert.server_distrust_after,
+            email_distrust_after: cert.email_distrust_after,
+            mozilla_ca_policy: cert.mozilla_ca_policy,
+        }
+    }
+}
+
+impl<'a> From<CertificateRef<'a>> for Certificate {
+    fn from(cert: CertificateRef<'a>) -> Self {
+        Certificate {
+            label: cert.label.to_owned(),
+            cert: cert.cert.to_vec().into(),
+            issuer: cert.issuer.to_vec().into(),
+            serial: cert.serial.to_vec().into(),
+            subject: cert.subject.to_vec().into(),
+            server_distrust_after: cert.server_distrust_after,
+            email_distrust_after: cert.email_distrust_after,
+            mozilla_ca_policy: cert.mozilla_ca_policy,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TrustRef<'a> {
+    pub label: &'a str,
+    pub issuer: &'a [u8],
+    pub serial: &'a [u8],
+    pub tls_server_trust: TrustLevel,
+    pub email_trust: TrustLevel,
+    pub code_signing_trust: TrustLevel,
+    pub md5: Option<&'a [u8]>,
+    pub sha1: Option<&'a [u8]>,
+}
+
+impl<'a> TrustRef<'a> {
+    /// As `Trust::trust_level`.
+    pub fn trust_level(&self, usage: Usage) -> TrustLevel {
+        match usage {
+            Usage::TlsServer => self.tls_server_trust,
+            Usage::Email => self.email_trust,
+            Usage::CodeSigning => self.code_signing_trust,
+        }
+    }
+}
+
+impl<'a> From<&'a Trust> for TrustRef<'a> {
+    fn from(trust: &'a Trust) -> Self {
+        TrustRef {
+            label: &trust.label,
+            issuer: &trust.issuer,
+            serial: &trust.serial,
+            tls_server_trust: trust.tls_server_trust,
+            email_trust: trust.email_trust,
+            code_signing_trust: trust.code_signing_trust,
+            md5: trust.md5.as_ref().map(|b| &b[..]),
+            sha1: trust.sha1.as_ref().map(|b| &b[..]),
+        }
+    }
+}
+
+impl<'a> From<TrustRef<'a>> for Trust {
+    fn from(trust: TrustRef<'a>) -> Self {
+        Trust {
+            label: trust.label.to_owned(),
+            issuer: trust.issuer.to_vec().into(),
+            serial: trust.serial.to_vec().into(),
+            tls_server_trust: trust.tls_server_trust,
+            email_trust: trust.email_trust,
+            code_signing_trust: trust.code_signing_trust,
+            md5: trust.md5.map(|b| b.to_vec().into()),
+            sha1: trust.sha1.map(|b| b.to_vec().into()),
+        }
+    }
+}
+
+/// The same queries as `CertData`, over borrowed records: either
+/// `static` slices, a `CertData` (see the `From` impl), or a snapshot
+/// (see `snapshot::load_snapshot`).  Unlike `CertData`, the order of
+/// static records isn't assumed, so lookups in those are linear;
+/// certdata.txt is small enough for that not to matter.
+#[derive(Debug, Clone)]
+pub struct CertDataRef<'a> {
+    certs: Cow<'a, [CertificateRef<'a>]>,
+    trusts: Cow<'a, [TrustRef<'a>]>,
+    // Whether `trusts` is sorted by issuer and serial, as in `CertData`.
+    sorted: bool,
+}
+
+impl<'a> CertDataRef<'a> {
+    pub const fn new(certs: &'a [CertificateRef<'a>], trusts: &'a [TrustRef<'a>]) -> Self {
+        CertDataRef {
+            certs: Cow::Borrowed(certs),
+            trusts: Cow::Borrowed(trusts),
+            sorted: false,
+        }
+    }
+
+    // The caller has checked the order of `trusts`.
+    pub(crate) fn from_sorted(certs: Vec<CertificateRef<'a>>, trusts: Vec<TrustRef<'a>>)
+                              -> Self {
+        CertDataRef {
+            certs: Cow::Owned(certs),
+            trusts: Cow::Owned(trusts),
+            sorted: true,
+        }
+    }
+
+    pub fn certs(&self) -> &[CertificateRef<'a>] {
+        &self.certs
+    }
+    pub fn trusts(&self) -> &[TrustRef<'a>] {
+        &self.trusts
+    }
+
+    pub fn trust_for(&self, issuer: &[u8], serial: &[u8]) -> Option<&TrustRef<'a>> {
+        if self.sorted {
+            self.trusts.binary_search_by(|t| (t.issuer, t.serial).cmp(&(issuer, serial)))
+                .ok().map(|i| &self.trusts[i])
+        } else {
+            self.trusts.iter().find(|t| t.issuer == issuer && t.serial == serial)
+        }
+    }
+
+    pub fn trust_for_cert(&self, cert: &CertificateRef) -> Option<&TrustRef<'a>> {
+        self.trust_for(cert.issuer, cert.serial)
+    }
+
+    pub fn trusted_certs(&self, usage: Usage) -> Vec<&CertificateRef<'a>> {
+        self.certs.iter()
+            .filter(|cert| {
+                self.trust_for_cert(cert)
+                    .map_or(TrustLevel::MustVerify, |trust| trust.trust_level(usage))
+                    == TrustLevel::TrustedDelegator
+            }).collect()
+    }
+    pub fn distrusts(&self, usage: Usage) -> Vec<&TrustRef<'a>> {
+        self.trusts.iter()
+            .filter(|trust| trust.trust_level(usage) == TrustLevel::Distrust)
+            .collect()
+    }
+
+    /// Copies everything into an owned `CertData`.
+    pub fn to_cert_data(&self) -> CertData {
+        let certs = self.certs.iter().map(|&c| Ok(Object::Certificate(c.into())));
+        let trusts = self.trusts.iter().map(|&t| Ok(Object::Trust(t.into())));
+        CertData::from_iter::<(), _>(certs.chain(trusts)).expect("no errors to return")
+    }
+}
+
+impl<'a> From<&'a CertData> for CertDataRef<'a> {
+    fn from(data: &'a CertData) -> Self {
+        CertDataRef::from_sorted(data.certs().iter().map(CertificateRef::from).collect(),
+                                 data.trusts().iter().map(TrustRef::from).collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{CertDataRef, CertificateRef, TrustRef};
+    use collect::CertData;
+    use reader::ObjectIter;
+    use structured::{Certificate, Trust, TrustLevel, Usage};
+    use time::Time;
+
+    static CERTS: &[CertificateRef<'static>] = &[
+        CertificateRef {
+            label: "Example",
+            cert: b"\x30\x00",
+            issuer: b"\x30\x02\x31\x00",
+            serial: b"\x02\x01\x01",
+            subject: b"\x30\x02\x31\x00",
+            server_distrust_after: Some(Time::from_unix(1700000000)),
+            email_distrust_after: None,
+            mozilla_ca_policy: Some(true),
+        },
+    ];
+    static TRUSTS: &[TrustRef<'static>] = &[
+        TrustRef {
+            label: "Example",
+            issuer: b"\x30\x02\x31\x00",
+            serial: b"\x02\x01\x01",
+            tls_server_trust: TrustLevel::TrustedDelegator,
+            email_trust: TrustLevel::MustVerify,
+            code_signing_trust: TrustLevel::Distrust,
+            md5: None,
+            sha1: None,
+        },
+    ];
+    static DATA: CertDataRef<'static> = CertDataRef::new(CERTS, TRUSTS);
+
+    #[test]
+    fn test_static() {
+        assert_eq!(DATA.trusted_certs(Usage::TlsServer), vec![&CERTS[0]]);
+        assert!(DATA.trusted_certs(Usage::Email).is_empty());
+        assert_eq!(DATA.distrusts(Usage::CodeSigning), vec![&TRUSTS[0]]);
+        assert_eq!(DATA.trusted_certs(Usage::TlsServer)[0].distrust_after(Usage::TlsServer),
+                   Some(Time::from_unix(1700000000)));
+        let owned = DATA.to_cert_data();
+        assert_eq!(owned.trusted_certs(Usage::TlsServer)[0].label, "Example");
+    }
+
+    #[test]
+    fn test_same_answers() {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let data_ref = CertDataRef::from(&data);
+        for &usage in &[Usage::TlsServer, Usage::Email, Usage::CodeSigning] {
+            let owned: Vec<_> = data.trusted_certs(usage).into_iter()
+                .map(CertificateRef::from).collect();
+            let borrowed: Vec<_> = data_ref.trusted_certs(usage).into_iter().cloned().collect();
+            assert_eq!(owned, borrowed);
+            let owned: Vec<_> = data.distrusts(usage).into_iter().map(TrustRef::from).collect();
+            let borrowed: Vec<_> = data_ref.distrusts(usage).into_iter().cloned().collect();
+            assert_eq!(owned, borrowed);
+        }
+
+        // And back again.
+        let cert = Certificate::from(data_ref.certs()[0]);
+        assert_eq!(cert.cert, data.certs()[0].cert);
+        assert_eq!(cert.server_distrust_after, data.certs()[0].server_distrust_after);
+        let trust = Trust::from(data_ref.trusts()[0]);
+        assert_eq!(trust.sha1, data.trusts()[0].sha1);
+        assert_eq!(data_ref.to_cert_data().certs().len(), data.certs().len());
+    }
+}
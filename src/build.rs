@@ -0,0 +1,155 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// For `build.rs` scripts that compile a vendored `certdata.txt` into
+// the crate, along the lines of:
+//
+//     let out = Path::new(&env::var("OUT_DIR").unwrap()).join("roots.rs");
+//     build::generate("certdata.txt", &out, &BuildOptions::default()).unwrap();
+//
+// and then `include!(concat!(env!("OUT_DIR"), "/roots.rs"));`.
+
+use std::fs::{self, File};
+use std::io::{BufReader, Write};
+use std::path::Path;
+
+use codegen::{self, byte_literal, CodegenOptions};
+use collect::CertData;
+use export::{self, Order};
+use reader::ObjectIter;
+use structured::Usage;
+use time::Time;
+use Error;
+
+#[derive(Debug, Clone)]
+pub struct BuildOptions {
+    /// Emit `<USAGE>_ROOTS` and `<USAGE>_DISTRUSTS` for each of these.
+    pub usages: Vec<Usage>,
+    /// Leave out roots whose distrust-after date for the usage is
+    /// before this time.
+    pub distrust_as_of: Option<Time>,
+    /// Also emit `<USAGE>_TRUST_ANCHORS`, in this form.
+    pub trust_anchors: Option<CodegenOptions>,
+    /// Print `cargo:rerun-if-changed` for the input file.
+    pub rerun_if_changed: bool,
+}
+
+impl Default for BuildOptions {
+    fn default() -> Self {
+        BuildOptions {
+            usages: vec![Usage::TlsServer],
+            distrust_as_of: None,
+            trust_anchors: None,
+            rerun_if_changed: true,
+        }
+    }
+}
+
+fn usage_prefix(usage: Usage) -> &'static str {
+    match usage {
+        Usage::TlsServer => "TLS_SERVER",
+        Usage::Email => "EMAIL",
+        Usage::CodeSigning => "CODE_SIGNING",
+    }
+}
+
+/// The generated source, without touching the filesystem.  `source`
+/// only goes in the header comment.
+pub fn generate_source(data: &CertData, source: &str, opts: &BuildOptions)
+                       -> Result<String, Error> {
+    let mut out = Vec::new();
+    writeln!(out, "// Generated by nss-certdata-parser from {:?}; do not edit.", source)?;
+    writeln!(out)?;
+    writeln!(out, "pub struct Root {{")?;
+    writeln!(out, "    pub label: &'static str,")?;
+    writeln!(out, "    pub der: &'static [u8],")?;
+    writeln!(out, "}}")?;
+    for &usage in &opts.usages {
+        let prefix = usage_prefix(usage);
+        let certs = export::select(data, usage, opts.distrust_as_of, Order::Label);
+        writeln!(out)?;
+        writeln!(out, "pub static {}_ROOTS: &[Root] = &[", prefix)?;
+        for cert in &certs {
+            writeln!(out, "    Root {{")?;
+            writeln!(out, "        label: {:?},", cert.label)?;
+            writeln!(out, "        der: {},", byte_literal(&cert.cert, 13))?;
+            writeln!(out, "    }},")?;
+        }
+        writeln!(out, "];")?;
+        if let Some(ref anchors) = opts.trust_anchors {
+            writeln!(out)?;
+            codegen::write_roots(&mut out, &format!("{}_TRUST_ANCHORS", prefix), &certs,
+                                 anchors)?;
+        }
+        writeln!(out)?;
+        codegen::write_distrusts(&mut out, &format!("{}_DISTRUSTS", prefix),
+                                 &data.distrusts(usage))?;
+    }
+    Ok(String::from_utf8(out).expect("labels are UTF-8"))
+}
+
+/// Parses `input` and writes the Rust source to `out_path`.  The file
+/// is left alone if it wouldn't change, so that its dependents aren't
+/// rebuilt for nothing.
+pub fn generate<P: AsRef<Path>, Q: AsRef<Path>>(input: P, out_path: Q, opts: &BuildOptions)
+                                                -> Result<(), Error> {
+    let input = input.as_ref();
+    let out_path = out_path.as_ref();
+    if opts.rerun_if_changed {
+        println!("cargo:rerun-if-changed={}", input.display());
+    }
+    let objs = ObjectIter::new(BufReader::new(File::open(input)?));
+    let data = CertData::from_iter(objs)?;
+    let source = input.file_name().map_or(input.as_os_str(), |name| name);
+    let text = generate_source(&data, &source.to_string_lossy(), opts)?;
+    if fs::read(out_path).ok().as_ref().map(|old| &old[..]) != Some(text.as_bytes()) {
+        fs::write(out_path, text)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generate, generate_source, BuildOptions};
+    use codegen::CodegenOptions;
+    use collect::CertData;
+    use reader::ObjectIter;
+    use std::env;
+    use std::fs;
+    use structured::Usage;
+
+    #[test]
+    fn test_source() {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let opts = BuildOptions {
+            usages: vec![Usage::TlsServer, Usage::Email],
+            trust_anchors: Some(CodegenOptions::default()),
+            ..BuildOptions::default()
+        };
+        let text = generate_source(&data, "certdata.txt", &opts).unwrap();
+        assert!(text.starts_with("// Generated by nss-certdata-parser from \"certdata.txt\""));
+        for name in &["TLS_SERVER_ROOTS: &[Root]", "TLS_SERVER_TRUST_ANCHORS",
+                      "TLS_SERVER_DISTRUSTS", "EMAIL_ROOTS: &[Root]", "EMAIL_TRUST_ANCHORS",
+                      "EMAIL_DISTRUSTS"] {
+            assert!(text.contains(&format!("pub static {}", name)), "no {}", name);
+        }
+        assert!(!text.contains("CODE_SIGNING"));
+        assert_eq!(text.matches("    Root {\n").count(),
+                   data.trusted_certs(Usage::TlsServer).len() +
+                   data.trusted_certs(Usage::Email).len());
+        assert!(text.contains("        label: \"ACCVRAIZ1\",\n        der: b\"\\x30\\x82"));
+    }
+
+    #[test]
+    fn test_generate() {
+        let out = env::temp_dir().join(format!("certdata-build-{}.rs", ::std::process::id()));
+        let opts = BuildOptions { rerun_if_changed: false, ..BuildOptions::default() };
+        generate("tests/data/certdata.txt", &out, &opts).unwrap();
+        let text = fs::read_to_string(&out).unwrap();
+        assert!(text.contains("pub static TLS_SERVER_ROOTS"));
+        fs::remove_file(&out).unwrap();
+        assert!(generate("tests/data/no-such-file", &out, &opts).is_err());
+    }
+}
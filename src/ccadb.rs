@@ -0,0 +1,374 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// The CSV reports Mozilla publishes from the CCADB, such as
+// `IncludedCACertificateReportPEMCSV`, and checking them against a
+// certdata.txt.  Only the columns needed for that are read:
+//
+//     Common Name or Certificate Name, SHA-256 Fingerprint, Trust Bits,
+//     Distrust for TLS After Date, Distrust for S/MIME After Date
+//
+// Trust bits are `;`-separated, with `Websites` meaning TLS server
+// authentication; dates are `YYYY.MM.DD`.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::io::Read;
+use std::mem;
+
+use collect::CertData;
+use export::{sort_certs, Order};
+use report::hex;
+use structured::{TrustLevel, Usage};
+use time::Time;
+use Error;
+
+const NAME: &str = "Common Name or Certificate Name";
+const SHA256: &str = "SHA-256 Fingerprint";
+const TRUST_BITS: &str = "Trust Bits";
+const TLS_DISTRUST: &str = "Distrust for TLS After Date";
+const EMAIL_DISTRUST: &str = "Distrust for S/MIME After Date";
+
+// The usages the CCADB has trust bits for.
+const USAGES: &[Usage] = &[Usage::TlsServer, Usage::Email];
+
+quick_error!{
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum CcadbError {
+        Syntax(line: usize, what: &'static str) {
+            description("CSV syntax error")
+            display("line {}: {}", line, what)
+        }
+        MissingColumn(name: &'static str) {
+            description("missing CCADB column")
+            display("no {:?} column", name)
+        }
+        BadField(line: usize, column: &'static str, value: String) {
+            description("bad CCADB field")
+            display("line {}: bad {}: {:?}", line, column, value)
+        }
+    }
+}
+
+/// One row of the report.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CcadbRoot {
+    /// Where the row starts in the file.
+    pub line: usize,
+    pub name: String,
+    pub sha256: Vec<u8>,
+    pub tls_server: bool,
+    pub email: bool,
+    pub server_distrust_after: Option<Time>,
+    pub email_distrust_after: Option<Time>,
+}
+
+impl CcadbRoot {
+    pub fn trusted_for(&self, usage: Usage) -> bool {
+        match usage {
+            Usage::TlsServer => self.tls_server,
+            Usage::Email => self.email,
+            Usage::CodeSigning => false,
+        }
+    }
+
+    pub fn distrust_after(&self, usage: Usage) -> Option<Time> {
+        match usage {
+            Usage::TlsServer => self.server_distrust_after,
+            Usage::Email => self.email_distrust_after,
+            Usage::CodeSigning => None,
+        }
+    }
+}
+
+// RFC 4180 records, each with the line it starts on.  Quoted fields may
+// span lines; blank lines are skipped.
+fn parse_records(text: &str) -> Result<Vec<(usize, Vec<String>)>, CcadbError> {
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let (mut line, mut start) = (1, 1);
+    let (mut in_quotes, mut was_quoted) = (false, false);
+    let mut chars = text.trim_start_matches('\u{feff}').chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_quotes {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => in_quotes = false,
+                c => field.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() && !was_quoted => {
+                in_quotes = true;
+                was_quoted = true;
+            }
+            ',' => {
+                record.push(mem::take(&mut field));
+                was_quoted = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' => {
+                if !record.is_empty() || !field.is_empty() || was_quoted {
+                    record.push(mem::take(&mut field));
+                    records.push((start, mem::take(&mut record)));
+                }
+                start = line;
+                was_quoted = false;
+            }
+            '"' => return Err(CcadbError::Syntax(line, "quote in unquoted field")),
+            _ if was_quoted => return Err(CcadbError::Syntax(line, "text after closing quote")),
+            c => field.push(c),
+        }
+    }
+    if in_quotes {
+        return Err(CcadbError::Syntax(start, "unterminated quoted field"));
+    }
+    if !record.is_empty() || !field.is_empty() || was_quoted {
+        record.push(field);
+        records.push((start, record));
+    }
+    Ok(records)
+}
+
+fn parse_hex(s: &str) -> Option<Vec<u8>> {
+    let digits: Vec<u8> = s.bytes().filter(|&b| b != b':').collect();
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+    digits.chunks(2)
+        .map(|pair| std::str::from_utf8(pair).ok().and_then(|p| u8::from_str_radix(p, 16).ok()))
+        .collect()
+}
+
+fn parse_date(s: &str) -> Option<Option<Time>> {
+    if s.is_empty() {
+        return Some(None);
+    }
+    s.replace('.', "-").parse().ok().map(Some)
+}
+
+/// Reads a CCADB CSV report.  Columns are found by their header, so
+/// extra or reordered columns don't matter.
+pub fn read_ccadb<R: Read>(mut src: R) -> Result<Vec<CcadbRoot>, Error> {
+    let mut text = String::new();
+    src.read_to_string(&mut text)?;
+    let mut records = parse_records(&text)?.into_iter();
+    let header = match records.next() {
+        Some((_, header)) => header,
+        None => return Ok(Vec::new()),
+    };
+    let column = |name: &'static str| {
+        header.iter().position(|h| h.trim() == name).ok_or(CcadbError::MissingColumn(name))
+    };
+    let (name_col, sha256_col, bits_col) = (column(NAME)?, column(SHA256)?, column(TRUST_BITS)?);
+    let (tls_col, email_col) = (column(TLS_DISTRUST)?, column(EMAIL_DISTRUST)?);
+
+    let mut roots = Vec::new();
+    for (line, record) in records {
+        let field = |col: usize| record.get(col).map_or("", |f| f.trim());
+        let bad = |column: &'static str, col: usize| {
+            CcadbError::BadField(line, column, field(col).to_owned())
+        };
+        let sha256 = parse_hex(field(sha256_col)).filter(|h| h.len() == 32)
+            .ok_or_else(|| bad(SHA256, sha256_col))?;
+        // Older reports also have `Code`, for code signing, which the
+        // CCADB no longer tracks.
+        let bits: Vec<&str> = field(bits_col).split(';').map(str::trim).collect();
+        roots.push(CcadbRoot {
+            line,
+            name: field(name_col).to_owned(),
+            sha256,
+            tls_server: bits.contains(&"Websites"),
+            email: bits.contains(&"Email"),
+            server_distrust_after: parse_date(field(tls_col))
+                .ok_or_else(|| bad(TLS_DISTRUST, tls_col))?,
+            email_distrust_after: parse_date(field(email_col))
+                .ok_or_else(|| bad(EMAIL_DISTRUST, email_col))?,
+        });
+    }
+    Ok(roots)
+}
+
+/// A way in which certdata.txt and the CCADB disagree.  "Included"
+/// means trusted for TLS or email, the usages the CCADB tracks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Discrepancy {
+    /// Included in certdata.txt, but not listed in the CCADB.
+    NotInCcadb { label: String, sha256: Vec<u8> },
+    /// Listed in the CCADB, but not in certdata.txt at all.
+    NotInCertData { name: String, sha256: Vec<u8> },
+    TrustBit { label: String, usage: Usage, certdata: bool, ccadb: bool },
+    /// Compared by date, since that's all the CCADB records.
+    DistrustAfter { label: String, usage: Usage, certdata: Option<Time>, ccadb: Option<Time> },
+}
+
+impl fmt::Display for Discrepancy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let set = |b: bool| if b { "set" } else { "not set" };
+        let date = |t: Option<Time>| t.map_or_else(|| "none".to_owned(), |t| t.date_string());
+        match *self {
+            Discrepancy::NotInCcadb { ref label, ref sha256 } =>
+                write!(f, "{} ({}): in certdata.txt but not in the CCADB", label, hex(sha256)),
+            Discrepancy::NotInCertData { ref name, ref sha256 } =>
+                write!(f, "{} ({}): in the CCADB but not in certdata.txt", name, hex(sha256)),
+            Discrepancy::TrustBit { ref label, usage, certdata, ccadb } =>
+                write!(f, "{}: {:?} trust bit {} in certdata.txt, {} in the CCADB",
+                       label, usage, set(certdata), set(ccadb)),
+            Discrepancy::DistrustAfter { ref label, usage, certdata, ccadb } =>
+                write!(f, "{}: {:?} distrust-after {} in certdata.txt, {} in the CCADB",
+                       label, usage, date(certdata), date(ccadb)),
+        }
+    }
+}
+
+/// Matches CCADB rows to certificates by SHA-256 fingerprint and lists
+/// the differences: certificates in label order, then CCADB rows that
+/// matched nothing, in file order.
+pub fn reconcile(data: &CertData, roots: &[CcadbRoot]) -> Vec<Discrepancy> {
+    let mut by_hash = HashMap::new();
+    for root in roots {
+        by_hash.entry(&root.sha256[..]).or_insert(root);
+    }
+    let mut found = Vec::new();
+    let mut out = Vec::new();
+    for cert in sort_certs(data.certs().iter().collect(), Order::Label) {
+        let sha256 = cert.sha256_fingerprint();
+        let trust = data.trust_for_cert(cert);
+        let trusted = |usage| {
+            trust.is_some_and(|t| t.trust_level(usage) == TrustLevel::TrustedDelegator)
+        };
+        let root = match by_hash.get(&sha256[..]) {
+            Some(&root) => root,
+            None => {
+                if USAGES.iter().any(|&usage| trusted(usage)) {
+                    out.push(Discrepancy::NotInCcadb {
+                        label: cert.label.clone(),
+                        sha256: sha256.to_vec(),
+                    });
+                }
+                continue;
+            }
+        };
+        found.push(&root.sha256[..]);
+        for &usage in USAGES {
+            if trusted(usage) != root.trusted_for(usage) {
+                out.push(Discrepancy::TrustBit {
+                    label: cert.label.clone(),
+                    usage,
+                    certdata: trusted(usage),
+                    ccadb: root.trusted_for(usage),
+                });
+            }
+        }
+        for &usage in USAGES {
+            let (ours, theirs) = (cert.distrust_after(usage), root.distrust_after(usage));
+            if ours.map(|t| t.date_string()) != theirs.map(|t| t.date_string()) {
+                out.push(Discrepancy::DistrustAfter {
+                    label: cert.label.clone(),
+                    usage,
+                    certdata: ours,
+                    ccadb: theirs,
+                });
+            }
+        }
+    }
+    for root in roots {
+        if !found.contains(&&root.sha256[..]) {
+            out.push(Discrepancy::NotInCertData {
+                name: root.name.clone(),
+                sha256: root.sha256.clone(),
+            });
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_records, read_ccadb, reconcile, CcadbError, Discrepancy};
+    use collect::CertData;
+    use reader::ObjectIter;
+    use structured::Usage;
+    use time::Time;
+    use Error;
+
+    fn record(line: usize, fields: &[&str]) -> (usize, Vec<String>) {
+        (line, fields.iter().map(|&f| f.to_owned()).collect())
+    }
+
+    #[test]
+    fn test_records() {
+        assert_eq!(parse_records("\u{feff}a,b\r\n\"c,\"\"d\"\"\",\r\n\n\"e\nf\",\"\"\ng").unwrap(),
+                   vec![record(1, &["a", "b"]),
+                        record(2, &["c,\"d\"", ""]),
+                        record(4, &["e\nf", ""]),
+                        record(6, &["g"])]);
+        assert_eq!(parse_records("a\n\"b").unwrap_err(),
+                   CcadbError::Syntax(2, "unterminated quoted field"));
+        assert_eq!(parse_records("a\"b").unwrap_err(),
+                   CcadbError::Syntax(1, "quote in unquoted field"));
+        assert_eq!(parse_records("\"a\"b").unwrap_err(),
+                   CcadbError::Syntax(1, "text after closing quote"));
+    }
+
+    #[test]
+    fn test_read() {
+        let roots = read_ccadb(&include_bytes!("../tests/data/ccadb.csv")[..]).unwrap();
+        assert_eq!(roots.len(), 142);
+        let certigna = roots.iter().find(|r| r.name == "Certigna").unwrap();
+        assert_eq!(certigna.line, 70);
+        assert!(certigna.tls_server && !certigna.email);
+        assert_eq!(certigna.email_distrust_after, Some(Time::from_ymd_hms(2035, 12, 31, 0, 0, 0)
+                                                       .unwrap()));
+        let example = roots.last().unwrap();
+        assert_eq!(example.name, "Example \"Test\" Root");
+        assert_eq!(example.sha256, vec![0; 32]);
+
+        let header = "Common Name or Certificate Name,SHA-256 Fingerprint,Trust Bits,\
+                      Distrust for TLS After Date,Distrust for S/MIME After Date\n";
+        match read_ccadb(format!("{}x,00,Websites,,", header).as_bytes()) {
+            Err(Error::CcadbError(CcadbError::BadField(2, "SHA-256 Fingerprint", _))) => (),
+            other => panic!("{:?}", other),
+        }
+        let row = "x,0000000000000000000000000000000000000000000000000000000000000000";
+        match read_ccadb(format!("{}{},Websites,2023.02.30,", header, row).as_bytes()) {
+            Err(Error::CcadbError(CcadbError::BadField(2, "Distrust for TLS After Date", _))) =>
+                (),
+            other => panic!("{:?}", other),
+        }
+        match read_ccadb(&b"SHA-256 Fingerprint\n"[..]) {
+            Err(Error::CcadbError(CcadbError::MissingColumn(_))) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_reconcile() {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let roots = read_ccadb(&include_bytes!("../tests/data/ccadb.csv")[..]).unwrap();
+        let found: Vec<_> = reconcile(&data, &roots).iter().map(Discrepancy::to_string).collect();
+        assert_eq!(found, vec![
+            "ACCVRAIZ1: Email trust bit set in certdata.txt, not set in the CCADB",
+            "Amazon Root CA 1 (8ECDE6884F3D87B1125BA31AC3FCB13D7016DE7F57CC904FE1CB97C6AE98196E): \
+             in certdata.txt but not in the CCADB",
+            "E-Tugra Global Root CA ECC v3: TlsServer distrust-after 2023-02-01 in certdata.txt, \
+             2023-03-01 in the CCADB",
+            "Example \"Test\" Root (0000000000000000000000000000000000000000000000000000000000000000): \
+             in the CCADB but not in certdata.txt",
+        ]);
+        match reconcile(&data, &roots)[0] {
+            Discrepancy::TrustBit { usage: Usage::Email, certdata: true, ccadb: false, .. } => (),
+            ref other => panic!("{:?}", other),
+        }
+    }
+}
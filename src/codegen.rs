@@ -0,0 +1,347 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Rust source for compiling roots into a program, e.g. from a
+// `build.rs`: `static` arrays of webpki-style trust anchors or plain
+// DER, and a table of explicit distrusts.  Everything is emitted as
+// byte string literals, so the output has no dependencies beyond
+// whatever trust anchor type the caller names.
+
+use std::io::Write;
+
+use constraints;
+use der::{self, tag};
+use export::colon_hex;
+use collect::CertData;
+use structured::{Certificate, Trust};
+use time::Time;
+use x509::{oid, CertInfo};
+use Error;
+
+/// The fields of a webpki `TrustAnchor`.  As there, each is the
+/// *contents* of the DER element, without its tag and length.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnchorParts<'a> {
+    pub subject: &'a [u8],
+    pub spki: &'a [u8],
+    /// The NameConstraints extension, if the certificate has one, or
+    /// else the ones NSS applies to it (see `constraints::builtin`).
+    pub name_constraints: Option<&'a [u8]>,
+}
+
+impl<'a> AnchorParts<'a> {
+    pub fn from_cert_info(info: &CertInfo<'a>) -> der::Result<AnchorParts<'a>> {
+        let name_constraints = match info.extension(oid::NAME_CONSTRAINTS) {
+            Some(ext) => Some(der::parse_one(ext.value, tag::SEQUENCE)?.value),
+            None => match constraints::builtin_der(info.subject) {
+                Some(nc) => Some(der::parse_one(nc, tag::SEQUENCE)?.value),
+                None => None,
+            },
+        };
+        Ok(AnchorParts {
+            subject: der::parse_one(info.subject, tag::SEQUENCE)?.value,
+            spki: der::parse_one(info.spki, tag::SEQUENCE)?.value,
+            name_constraints,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    /// `TrustAnchor { subject, spki, name_constraints }` structs.
+    #[default]
+    TrustAnchors,
+    /// `&[&[u8]]` of whole DER certificates.
+    Der,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodegenOptions {
+    pub format: Format,
+    /// The path of the struct used for `Format::TrustAnchors`, as it
+    /// should appear in the generated code.
+    pub anchor_type: String,
+    /// How the generated code refers to this crate, for
+    /// `write_cert_data_ref`.
+    pub crate_path: String,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        CodegenOptions {
+            format: Format::TrustAnchors,
+            anchor_type: "webpki::TrustAnchor".to_owned(),
+            crate_path: "::nss_certdata_parser".to_owned(),
+        }
+    }
+}
+
+const BYTES_PER_LINE: usize = 16;
+
+/// A byte string literal, continued over several lines if it's long;
+/// `indent` is the column the literal starts at.
+pub fn byte_literal(bytes: &[u8], indent: usize) -> String {
+    let mut out = String::from("b\"");
+    for (i, chunk) in bytes.chunks(BYTES_PER_LINE).enumerate() {
+        if i > 0 {
+            out.push_str("\\\n");
+            out.push_str(&" ".repeat(indent + 2));
+        }
+        for b in chunk {
+            out.push_str(&format!("\\x{:02x}", b));
+        }
+    }
+    out.push('"');
+    out
+}
+
+// Labels can contain anything but a newline would end the comment.
+fn comment(label: &str) -> String {
+    format!("{:?}", label)
+}
+
+/// Writes `pub static <name>` holding `certs`, in the given order.
+pub fn write_roots<W: Write>(out: &mut W, name: &str, certs: &[&Certificate],
+                             opts: &CodegenOptions) -> Result<(), Error> {
+    match opts.format {
+        Format::TrustAnchors => {
+            writeln!(out, "pub static {}: &[{}<'static>] = &[", name, opts.anchor_type)?;
+            for cert in certs {
+                let info = cert.x509()?;
+                let parts = AnchorParts::from_cert_info(&info)?;
+                writeln!(out, "    // {}", comment(&cert.label))?;
+                writeln!(out, "    // SHA-256: {}", colon_hex(&cert.sha256_fingerprint()))?;
+                writeln!(out, "    {} {{", opts.anchor_type)?;
+                writeln!(out, "        subject: {},", byte_literal(parts.subject, 17))?;
+                writeln!(out, "        spki: {},", byte_literal(parts.spki, 14))?;
+                match parts.name_constraints {
+                    None => writeln!(out, "        name_constraints: None,")?,
+                    Some(nc) => writeln!(out, "        name_constraints: Some({}),",
+                                         byte_literal(nc, 31))?,
+                }
+                writeln!(out, "    }},")?;
+            }
+        }
+        Format::Der => {
+            writeln!(out, "pub static {}: &[&[u8]] = &[", name)?;
+            for cert in certs {
+                writeln!(out, "    // {}", comment(&cert.label))?;
+                writeln!(out, "    {},", byte_literal(&cert.cert, 4))?;
+            }
+        }
+    }
+    writeln!(out, "];")?;
+    Ok(())
+}
+
+/// Writes `pub static <name>: &[(&[u8], &[u8])]`, the DER issuer Name
+/// and serialNumber INTEGER of each trust record, as in `CKA_ISSUER`
+/// and `CKA_SERIAL_NUMBER`.  Sorted, so that it can be binary searched.
+pub fn write_distrusts<W: Write>(out: &mut W, name: &str, trusts: &[&Trust])
+                                 -> Result<(), Error> {
+    let mut trusts = trusts.to_vec();
+    trusts.sort_by(|a, b| (&a.issuer, &a.serial).cmp(&(&b.issuer, &b.serial)));
+    trusts.dedup_by(|a, b| (&a.issuer, &a.serial) == (&b.issuer, &b.serial));
+    writeln!(out, "pub static {}: &[(&[u8], &[u8])] = &[", name)?;
+    for trust in trusts {
+        writeln!(out, "    // {}", comment(&trust.label))?;
+        writeln!(out, "    ({},", byte_literal(&trust.issuer, 5))?;
+        writeln!(out, "     {}),", byte_literal(&trust.serial, 5))?;
+    }
+    writeln!(out, "];")?;
+    Ok(())
+}
+
+fn option_time(krate: &str, time: Option<Time>) -> String {
+    time.map_or("None".to_owned(), |t| format!("Some({}::Time::from_unix({}))", krate, t.unix()))
+}
+
+fn option_bytes(bytes: Option<&[u8]>, indent: usize) -> String {
+    bytes.map_or("None".to_owned(), |b| format!("Some({})", byte_literal(b, indent + 5)))
+}
+
+/// Writes `pub static <name>: CertDataRef<'static>` holding all of
+/// `data`, so that compiled-in roots can be queried like parsed ones.
+pub fn write_cert_data_ref<W: Write>(out: &mut W, name: &str, data: &CertData,
+                                     opts: &CodegenOptions) -> Result<(), Error> {
+    let krate = &opts.crate_path;
+    writeln!(out, "pub static {}: {}::CertDataRef<'static> = {}::CertDataRef::new(&[",
+             name, krate, krate)?;
+    for cert in data.certs() {
+        writeln!(out, "    {}::CertificateRef {{", krate)?;
+        writeln!(out, "        label: {:?},", cert.label)?;
+        writeln!(out, "        cert: {},", byte_literal(&cert.cert, 14))?;
+        writeln!(out, "        issuer: {},", byte_literal(&cert.issuer, 16))?;
+        writeln!(out, "        serial: {},", byte_literal(&cert.serial, 16))?;
+        writeln!(out, "        subject: {},", byte_literal(&cert.subject, 17))?;
+        writeln!(out, "        server_distrust_after: {},",
+                 option_time(krate, cert.server_distrust_after))?;
+        writeln!(out, "        email_distrust_after: {},",
+                 option_time(krate, cert.email_distrust_after))?;
+        writeln!(out, "        mozilla_ca_policy: {:?},", cert.mozilla_ca_policy)?;
+        writeln!(out, "    }},")?;
+    }
+    writeln!(out, "], &[")?;
+    for trust in data.trusts() {
+        writeln!(out, "    {}::TrustRef {{", krate)?;
+        writeln!(out, "        label: {:?},", trust.label)?;
+        writeln!(out, "        issuer: {},", byte_literal(&trust.issuer, 16))?;
+        writeln!(out, "        serial: {},", byte_literal(&trust.serial, 16))?;
+        writeln!(out, "        tls_server_trust: {}::TrustLevel::{:?},", krate,
+                 trust.tls_server_trust)?;
+        writeln!(out, "        email_trust: {}::TrustLevel::{:?},", krate, trust.email_trust)?;
+        writeln!(out, "        code_signing_trust: {}::TrustLevel::{:?},", krate,
+                 trust.code_signing_trust)?;
+        writeln!(out, "        md5: {},", option_bytes(trust.md5.as_ref().map(|b| &b[..]), 13))?;
+        writeln!(out, "        sha1: {},", option_bytes(trust.sha1.as_ref().map(|b| &b[..]), 14))?;
+        writeln!(out, "    }},")?;
+    }
+    writeln!(out, "]);")?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{byte_literal, write_cert_data_ref, write_distrusts, write_roots, AnchorParts,
+                CodegenOptions, Format};
+    use collect::CertData;
+    use fixture;
+    use std::env;
+    use std::fs;
+    use std::io::Write;
+    use structured::{Object, Usage};
+    use x509::{oid, Extension};
+    use Error;
+
+    const GENERATED: &str = "tests/data/generated.rs";
+
+    // What tests/codegen.rs compiles, to check that the output is valid
+    // Rust and means what it should: a few of the fixture's objects,
+    // which between them have each kind of field, to keep it small.
+    fn generated() -> String {
+        let keep = ["ACCVRAIZ1", "E-Tugra Certification Authority",
+                    "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1",
+                    "Explicitly Distrust Example Compromised CA",
+                    "Explicitly Distrust Example Mis-issued Intermediate",
+                    "Explicitly Distrust Example Fraudulent Server"];
+        let all = fixture();
+        let certs = all.certs().iter().filter(|c| keep.contains(&&c.label[..]));
+        let trusts = all.trusts().iter().filter(|t| keep.contains(&&t.label[..]));
+        let objs = certs.cloned().map(Object::Certificate)
+            .chain(trusts.cloned().map(Object::Trust));
+        let data = CertData::from_iter(objs.map(Ok::<_, Error>)).unwrap();
+        let opts = CodegenOptions { anchor_type: "TrustAnchor".to_owned(),
+                                    ..CodegenOptions::default() };
+        let der = CodegenOptions { format: Format::Der, ..opts.clone() };
+        let mut out = Vec::new();
+        writeln!(out, "// Generated from certdata.txt by codegen::tests::test_generated.").unwrap();
+        write_roots(&mut out, "TLS_SERVER_ROOTS", &data.trusted_certs(Usage::TlsServer), &opts)
+            .unwrap();
+        write_roots(&mut out, "EMAIL_ROOTS", &data.trusted_certs(Usage::Email), &der).unwrap();
+        write_distrusts(&mut out, "TLS_SERVER_DISTRUSTS", &data.distrusts(Usage::TlsServer))
+            .unwrap();
+        write_cert_data_ref(&mut out, "CERTDATA", &data, &opts).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    // Set NSS_CERTDATA_BLESS to rewrite the file after changing the output.
+    #[test]
+    fn test_generated() {
+        let text = generated();
+        if env::var_os("NSS_CERTDATA_BLESS").is_some() {
+            fs::write(GENERATED, &text).unwrap();
+        }
+        assert!(fs::read_to_string(GENERATED).unwrap() == text,
+                "{} is out of date", GENERATED);
+    }
+
+    #[test]
+    fn test_byte_literal() {
+        assert_eq!(byte_literal(b"", 0), "b\"\"");
+        assert_eq!(byte_literal(b"\x00A\xff", 0), "b\"\\x00\\x41\\xff\"");
+        let long: Vec<u8> = (0..20).collect();
+        let lit = byte_literal(&long, 4);
+        assert_eq!(lit.lines().count(), 2);
+        assert!(lit.lines().next().unwrap().ends_with("\\x0f\\"));
+        assert!(lit.lines().nth(1).unwrap().starts_with("      \\x10"));
+    }
+
+    #[test]
+    fn test_anchor_parts() {
+        let data = fixture();
+        let cert = data.certs().iter().find(|c| c.label == "Amazon Root CA 1").unwrap();
+        let mut info = cert.x509().unwrap();
+        let parts = AnchorParts::from_cert_info(&info).unwrap();
+        assert_eq!(parts.subject, &cert.subject[2..]);
+        // AlgorithmIdentifier { rsaEncryption, NULL }
+        assert!(parts.spki.starts_with(b"\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01"));
+        assert_eq!(parts.name_constraints, None);
+
+        // permittedSubtrees { dNSName ".tr" }
+        info.extensions.push(Extension {
+            oid: oid::NAME_CONSTRAINTS,
+            critical: true,
+            value: b"\x30\x09\xa0\x07\x30\x05\x82\x03.tr",
+        });
+        let parts = AnchorParts::from_cert_info(&info).unwrap();
+        assert_eq!(parts.name_constraints, Some(&b"\xa0\x07\x30\x05\x82\x03.tr"[..]));
+
+        // NSS's own constraints for a root whose DER has none.
+        let cert = data.certs().iter()
+            .find(|c| c.label == "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1").unwrap();
+        let parts = AnchorParts::from_cert_info(&cert.x509().unwrap()).unwrap();
+        assert!(parts.name_constraints.unwrap().starts_with(b"\xa0\x63\x30\x09\x82\x07.gov.tr"));
+    }
+
+    #[test]
+    fn test_roots() {
+        let data = fixture();
+        let certs = data.trusted_certs(Usage::TlsServer);
+        let mut out = Vec::new();
+        write_roots(&mut out, "TLS_SERVER_ROOTS", &certs, &CodegenOptions::default()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("pub static TLS_SERVER_ROOTS: \
+                                  &[webpki::TrustAnchor<'static>] = &[\n"));
+        assert!(text.ends_with("\n];\n"));
+        assert_eq!(text.matches("    webpki::TrustAnchor {\n").count(), certs.len());
+        assert!(text.contains("    // \"ACCVRAIZ1\"\n    // SHA-256: 9A:6E:C0:12:"));
+
+        let opts = CodegenOptions { format: Format::Der, ..CodegenOptions::default() };
+        let mut out = Vec::new();
+        write_roots(&mut out, "ALL", &certs[..1], &opts).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("pub static ALL: &[&[u8]] = &[\n    // "));
+        assert_eq!(text.matches("b\"\\x30\\x82").count(), 1);
+    }
+
+    #[test]
+    fn test_distrusts() {
+        let data = fixture();
+        let trusts = data.distrusts(Usage::TlsServer);
+        let mut out = Vec::new();
+        write_distrusts(&mut out, "DISTRUSTS", &trusts).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text.matches("\n    (b\"").count(), trusts.len());
+        assert!(text.contains("// \"Explicitly Distrust Example Fraudulent Server\"\n"));
+    }
+
+    #[test]
+    fn test_cert_data_ref() {
+        let data = fixture();
+        let mut out = Vec::new();
+        write_cert_data_ref(&mut out, "CERTDATA", &data, &CodegenOptions::default()).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.starts_with("pub static CERTDATA: ::nss_certdata_parser::CertDataRef<'static> \
+                                  = ::nss_certdata_parser::CertDataRef::new(&[\n"));
+        assert!(text.ends_with("\n]);\n"));
+        assert_eq!(text.matches("    ::nss_certdata_parser::CertificateRef {\n").count(),
+                   data.certs().len());
+        assert_eq!(text.matches("    ::nss_certdata_parser::TrustRef {\n").count(),
+                   data.trusts().len());
+        assert!(text.contains("        server_distrust_after: \
+                               Some(::nss_certdata_parser::Time::from_unix(1675209600)),\n"));
+        assert!(text.contains("        tls_server_trust: \
+                               ::nss_certdata_parser::TrustLevel::TrustedDelegator,\n"));
+    }
+}
@@ -6,8 +6,11 @@
 
 use std::cmp::{Ord, Ordering};
 
+use der;
 use structured::{Certificate, Trust, Object, Usage};
 use structured::TrustLevel::*;
+use time::Time;
+use x509::CertInfo;
 
 fn cert_cmp(ca: &Certificate, cb: &Certificate) -> Ordering {
     ca.subject.cmp(&cb.subject)
@@ -19,6 +22,23 @@ fn trust_cmp(ta: &Trust, tb: &Trust) -> Ordering {
     trust_cmp_with(ta, &tb.issuer, &tb.serial)
 }
 
+/// Whether a certificate was a trust anchor at some point in time, and
+/// if not, why not.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrustStatus {
+    Trusted,
+    /// Before its notBefore.
+    NotYetValid(Time),
+    /// After its notAfter.
+    Expired(Time),
+    /// Still valid, but nothing issued since this date is trusted.
+    DistrustedAfter(Time),
+    /// Explicitly distrusted for the usage.
+    Distrusted,
+    /// No trust record, or one that leaves it to the certificate chain.
+    NoTrust,
+}
+
 pub struct CertData {
     certs: Box<[Certificate]>,
     trusts: Box<[Trust]>,
@@ -65,6 +85,16 @@ impl CertData {
         self.trust_for(&cert.issuer, &cert.serial)
     }
 
+    /// The trust object explicitly distrusting `cert` (any DER X.509
+    /// certificate, not only ones in the file) for `usage`, if there is
+    /// one; its label says which entry it is.  Such a certificate is
+    /// untrusted even if it chains to a trusted root.
+    pub fn is_distrusted(&self, cert: &[u8], usage: Usage) -> der::Result<Option<&Trust>> {
+        let info = CertInfo::parse(cert)?;
+        Ok(self.trust_for(info.issuer, info.serial)
+           .filter(|trust| trust.trust_level(usage) == Distrust))
+    }
+
     pub fn trusted_certs(&self, usage: Usage) -> Vec<&Certificate> {
         self.certs.iter()
             .filter(|cert| {
@@ -73,9 +103,123 @@ impl CertData {
                     == TrustedDelegator
             }).collect()
     }
+    /// The status of `cert` for `usage` at `date`, as far as this file
+    /// knows: the validity period and distrust-after dates only take
+    /// effect with the passage of time, but trust records are assumed
+    /// to have been the same then as now.
+    pub fn status_as_of(&self, cert: &Certificate, usage: Usage, date: Time)
+                        -> der::Result<TrustStatus> {
+        match self.trust_for_cert(cert).map_or(MustVerify, |t| t.trust_level(usage)) {
+            Distrust => return Ok(TrustStatus::Distrusted),
+            MustVerify => return Ok(TrustStatus::NoTrust),
+            TrustedDelegator => (),
+        }
+        let info = cert.x509()?;
+        Ok(if date < info.not_before {
+            TrustStatus::NotYetValid(info.not_before)
+        } else if date > info.not_after {
+            TrustStatus::Expired(info.not_after)
+        } else {
+            match cert.distrust_after(usage) {
+                Some(after) if date > after => TrustStatus::DistrustedAfter(after),
+                _ => TrustStatus::Trusted,
+            }
+        })
+    }
+
+    /// The certificates whose `status_as_of` is `Trusted`.  Ones with
+    /// unparseable DER are left out.
+    pub fn trusted_certs_as_of(&self, usage: Usage, date: Time) -> Vec<&Certificate> {
+        self.certs.iter()
+            .filter(|cert| self.status_as_of(cert, usage, date) == Ok(TrustStatus::Trusted))
+            .collect()
+    }
+
+    /// The roots trusted for TLS servers whose name constraints, including
+    /// the ones NSS applies by subject, permit `host`.
+    pub fn roots_for_host(&self, host: &str) -> der::Result<Vec<&Certificate>> {
+        let mut out = Vec::new();
+        for cert in self.trusted_certs(Usage::TlsServer) {
+            if cert.permits_host(host)? {
+                out.push(cert);
+            }
+        }
+        Ok(out)
+    }
+
+    /// The same for email roots and the address `addr`.
+    pub fn roots_for_email(&self, addr: &str) -> der::Result<Vec<&Certificate>> {
+        let mut out = Vec::new();
+        for cert in self.trusted_certs(Usage::Email) {
+            if cert.permits_email(addr)? {
+                out.push(cert);
+            }
+        }
+        Ok(out)
+    }
+
     pub fn distrusts(&self, usage: Usage) -> Vec<&Trust> {
         self.trusts.iter()
             .filter(|trust| trust.trust_level(usage) == Distrust)
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::TrustStatus;
+    use der::DerError;
+    use fixture;
+    use structured::Usage;
+    use time::Time;
+
+    #[test]
+    fn test_is_distrusted() {
+        let data = fixture();
+        let cert = |label| &data.certs().iter().find(|c| c.label == label).unwrap().cert[..];
+
+        let bad = cert("Explicitly Distrust Example Compromised CA");
+        let trust = data.is_distrusted(bad, Usage::TlsServer).unwrap().unwrap();
+        assert_eq!(trust.label, "Explicitly Distrust Example Compromised CA");
+        assert!(data.is_distrusted(cert("ACCVRAIZ1"), Usage::TlsServer).unwrap().is_none());
+        assert!(data.is_distrusted(b"\x30\x00", Usage::Email).is_err());
+        assert_eq!(data.is_distrusted(&bad[..10], Usage::Email).unwrap_err(),
+                   DerError::Truncated);
+    }
+
+    #[test]
+    fn test_status_as_of() {
+        let data = fixture();
+        let date = |y, m, d| Time::from_ymd_hms(y, m, d, 0, 0, 0).unwrap();
+        let status = |label, usage, when| {
+            let cert = data.certs().iter().find(|c| c.label == label).unwrap();
+            data.status_as_of(cert, usage, when).unwrap()
+        };
+
+        assert_eq!(status("ACCVRAIZ1", Usage::TlsServer, date(2023, 5, 1)), TrustStatus::Trusted);
+        match status("ACCVRAIZ1", Usage::TlsServer, date(2010, 1, 1)) {
+            TrustStatus::NotYetValid(t) => assert_eq!(t.date_string(), "2011-05-05"),
+            other => panic!("{:?}", other),
+        }
+        match status("ACCVRAIZ1", Usage::TlsServer, date(2031, 1, 1)) {
+            TrustStatus::Expired(t) => assert_eq!(t.date_string(), "2030-12-31"),
+            other => panic!("{:?}", other),
+        }
+        match status("E-Tugra Certification Authority", Usage::TlsServer, date(2023, 2, 15)) {
+            TrustStatus::DistrustedAfter(t) => assert_eq!(t.date_string(), "2023-02-01"),
+            other => panic!("{:?}", other),
+        }
+        assert_eq!(status("E-Tugra Certification Authority", Usage::TlsServer, date(2023, 1, 1)),
+                   TrustStatus::Trusted);
+        assert_eq!(status("ACCVRAIZ1", Usage::CodeSigning, date(2023, 5, 1)),
+                   TrustStatus::NoTrust);
+        assert_eq!(status("Explicitly Distrust Example Compromised CA", Usage::Email,
+                          date(2023, 5, 1)),
+                   TrustStatus::Distrusted);
+
+        let trusted = data.trusted_certs_as_of(Usage::TlsServer, date(2023, 5, 1));
+        assert!(trusted.iter().any(|c| c.label == "ACCVRAIZ1"));
+        assert!(trusted.len() < data.trusted_certs(Usage::TlsServer).len());
+        assert!(data.trusted_certs_as_of(Usage::TlsServer, date(1990, 1, 1)).is_empty());
+    }
+}
@@ -0,0 +1,321 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// The NameConstraints extension (RFC 5280 section 4.2.1.10), as some
+// roots carry in their DER, and checking names against it for the name
+// types that matter for TLS and S/MIME: DNS names, email addresses and
+// IP addresses.
+//
+// NSS also applies a few constraints of its own to particular roots,
+// which live in its source code rather than in `certdata.txt`; `builtin`
+// has those, and `Certificate::permits_host` and `permits_email` apply
+// them along with the DER's.
+
+use std::net::IpAddr;
+use std::str;
+
+use der::{self, tag, DerError};
+
+// GeneralName choices, as implicitly tagged.
+const RFC822_NAME: u8 = 0x81;
+const DNS_NAME: u8 = 0x82;
+const DIRECTORY_NAME: u8 = 0xa4;
+const IP_ADDRESS: u8 = 0x87;
+
+/// The `base` of a GeneralSubtree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Subtree<'a> {
+    /// A domain and its subdomains; with a leading `.`, only the
+    /// subdomains.
+    Dns(&'a str),
+    /// A mailbox if it has an `@`; otherwise a host, or with a leading
+    /// `.`, any subdomain of a domain.
+    Email(&'a str),
+    /// Network-order address and mask, both 4 or both 16 bytes.
+    Ip { addr: &'a [u8], mask: &'a [u8] },
+    /// The DER-encoded Name.
+    Directory(&'a [u8]),
+    /// The whole DER GeneralName, for the other kinds.
+    Other(&'a [u8]),
+}
+
+// The subjects of the roots NSS constrains in lib/certdb/genname.c, and
+// the NameConstraints it gives them, both in DER.
+const BUILTIN: &[(&[u8], &[u8])] = &[
+    // TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1
+    (b"\x30\x81\xd2\
+       \x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02TR\
+       \x31\x18\x30\x16\x06\x03\x55\x04\x07\x13\x0fGebze - Kocaeli\
+       \x31\x42\x30\x40\x06\x03\x55\x04\x0a\x13\x39\
+       Turkiye Bilimsel ve Teknolojik Arastirma Kurumu - TUBITAK\
+       \x31\x2d\x30\x2b\x06\x03\x55\x04\x0b\x13\x24Kamu Sertifikasyon Merkezi - Kamu SM\
+       \x31\x36\x30\x34\x06\x03\x55\x04\x03\x13\x2d\
+       TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1",
+     b"\x30\x65\xa0\x63\
+       \x30\x09\x82\x07.gov.tr\x30\x09\x82\x07.k12.tr\x30\x09\x82\x07.pol.tr\
+       \x30\x09\x82\x07.mil.tr\x30\x09\x82\x07.tsk.tr\x30\x09\x82\x07.kep.tr\
+       \x30\x09\x82\x07.bel.tr\x30\x09\x82\x07.edu.tr\x30\x09\x82\x07.org.tr"),
+    // ANSSI's IGC/A
+    (b"\x30\x81\x85\
+       \x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02FR\
+       \x31\x0f\x30\x0d\x06\x03\x55\x04\x08\x13\x06France\
+       \x31\x0e\x30\x0c\x06\x03\x55\x04\x07\x13\x05Paris\
+       \x31\x10\x30\x0e\x06\x03\x55\x04\x0a\x13\x07PM/SGDN\
+       \x31\x0e\x30\x0c\x06\x03\x55\x04\x0b\x13\x05DCSSI\
+       \x31\x0e\x30\x0c\x06\x03\x55\x04\x03\x13\x05IGC/A\
+       \x31\x23\x30\x21\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x09\x01\x16\x14\
+       igca@sgdn.pm.gouv.fr",
+     b"\x30\x5d\xa0\x5b\
+       \x30\x05\x82\x03.fr\x30\x05\x82\x03.gp\x30\x05\x82\x03.gf\x30\x05\x82\x03.mq\
+       \x30\x05\x82\x03.re\x30\x05\x82\x03.yt\x30\x05\x82\x03.pm\x30\x05\x82\x03.bl\
+       \x30\x05\x82\x03.mf\x30\x05\x82\x03.wf\x30\x05\x82\x03.pf\x30\x05\x82\x03.nc\
+       \x30\x05\x82\x03.tf"),
+];
+
+/// The constraints NSS applies to the root with this subject (a DER
+/// Name) on top of any in its DER, for the few roots that have them.
+pub fn builtin(subject: &[u8]) -> Option<NameConstraints<'static>> {
+    builtin_der(subject)
+        .map(|nc| NameConstraints::parse(nc).expect("built-in constraints are valid"))
+}
+
+/// `builtin`'s constraints as DER, like the extension's value.
+pub fn builtin_der(subject: &[u8]) -> Option<&'static [u8]> {
+    BUILTIN.iter().find(|&&(s, _)| s == subject).map(|&(_, nc)| nc)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct NameConstraints<'a> {
+    pub permitted: Vec<Subtree<'a>>,
+    pub excluded: Vec<Subtree<'a>>,
+}
+
+fn ia5(value: &[u8]) -> der::Result<&str> {
+    match str::from_utf8(value) {
+        Ok(s) if s.is_ascii() => Ok(s),
+        _ => Err(DerError::BadValue("IA5String")),
+    }
+}
+
+fn parse_subtrees(subtrees: der::Tlv) -> der::Result<Vec<Subtree>> {
+    let mut out = Vec::new();
+    let mut rd = subtrees.reader();
+    while !rd.is_empty() {
+        let mut subtree = rd.read(tag::SEQUENCE)?.reader();
+        let base = subtree.read_any()?;
+        // minimum and maximum, which RFC 5280 says aren't used.
+        let _ = subtree.read_optional(tag::context_primitive(0))?;
+        let _ = subtree.read_optional(tag::context_primitive(1))?;
+        subtree.finish()?;
+        out.push(match base.tag {
+            RFC822_NAME => Subtree::Email(ia5(base.value)?),
+            DNS_NAME => Subtree::Dns(ia5(base.value)?),
+            IP_ADDRESS => match base.value.len() {
+                8 | 32 => {
+                    let (addr, mask) = base.value.split_at(base.value.len() / 2);
+                    Subtree::Ip { addr, mask }
+                }
+                _ => return Err(DerError::BadValue("iPAddress constraint")),
+            },
+            DIRECTORY_NAME => Subtree::Directory(der::parse_one(base.value, tag::SEQUENCE)?.raw),
+            _ => Subtree::Other(base.raw),
+        });
+    }
+    Ok(out)
+}
+
+fn dns_matches(constraint: &str, name: &str) -> bool {
+    let name = name.strip_suffix('.').unwrap_or(name);
+    if constraint.is_empty() {
+        return true;
+    }
+    if constraint.starts_with('.') {
+        return name.len() > constraint.len() && ends_with_ignore_case(name, constraint);
+    }
+    name.eq_ignore_ascii_case(constraint) ||
+        (ends_with_ignore_case(name, constraint) &&
+         name.as_bytes()[name.len() - constraint.len() - 1] == b'.')
+}
+
+fn ends_with_ignore_case(s: &str, suffix: &str) -> bool {
+    s.len() >= suffix.len() &&
+        s.as_bytes()[s.len() - suffix.len()..].eq_ignore_ascii_case(suffix.as_bytes())
+}
+
+fn email_matches(constraint: &str, addr: &str) -> bool {
+    let (local, host) = match addr.rfind('@') {
+        Some(i) => (&addr[..i], &addr[i + 1..]),
+        None => return false,
+    };
+    match constraint.rfind('@') {
+        Some(i) => local == &constraint[..i] && host.eq_ignore_ascii_case(&constraint[i + 1..]),
+        None if constraint.starts_with('.') => dns_matches(constraint, host),
+        None => host.eq_ignore_ascii_case(constraint),
+    }
+}
+
+fn ip_matches(addr: &[u8], mask: &[u8], ip: IpAddr) -> bool {
+    let octets = match ip {
+        IpAddr::V4(ip) => ip.octets().to_vec(),
+        IpAddr::V6(ip) => ip.octets().to_vec(),
+    };
+    octets.len() == addr.len() &&
+        octets.iter().zip(addr).zip(mask).all(|((&o, &a), &m)| o & m == a & m)
+}
+
+impl<'a> NameConstraints<'a> {
+    /// Parses the extension's value (the contents of its OCTET STRING).
+    pub fn parse(value: &'a [u8]) -> der::Result<NameConstraints<'a>> {
+        let mut rd = der::parse_one(value, tag::SEQUENCE)?.reader();
+        let permitted = match rd.read_optional(tag::context(0))? {
+            None => Vec::new(),
+            Some(tlv) => parse_subtrees(tlv)?,
+        };
+        let excluded = match rd.read_optional(tag::context(1))? {
+            None => Vec::new(),
+            Some(tlv) => parse_subtrees(tlv)?,
+        };
+        rd.finish()?;
+        Ok(NameConstraints { permitted, excluded })
+    }
+
+    // `matches` is `None` for subtrees of other name types, which
+    // don't constrain this one.
+    fn permits<F>(&self, matches: F) -> bool
+        where F: Fn(&Subtree) -> Option<bool>
+    {
+        if self.excluded.iter().any(|s| matches(s) == Some(true)) {
+            return false;
+        }
+        let permitted: Vec<bool> = self.permitted.iter().filter_map(matches).collect();
+        permitted.is_empty() || permitted.contains(&true)
+    }
+
+    pub fn permits_dns_name(&self, name: &str) -> bool {
+        self.permits(|s| match *s {
+            Subtree::Dns(c) => Some(dns_matches(c, name)),
+            _ => None,
+        })
+    }
+
+    pub fn permits_email(&self, addr: &str) -> bool {
+        self.permits(|s| match *s {
+            Subtree::Email(c) => Some(email_matches(c, addr)),
+            _ => None,
+        })
+    }
+
+    pub fn permits_ip(&self, ip: IpAddr) -> bool {
+        self.permits(|s| match *s {
+            Subtree::Ip { addr, mask } => Some(ip_matches(addr, mask, ip)),
+            _ => None,
+        })
+    }
+
+    /// A TLS server name: an IP address literal, or else a DNS name.
+    pub fn permits_host(&self, host: &str) -> bool {
+        match host.parse() {
+            Ok(ip) => self.permits_ip(ip),
+            Err(_) => self.permits_dns_name(host),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{builtin, NameConstraints, Subtree, BUILTIN};
+    use der::{encode, tag, DerError};
+    use fixture;
+
+    fn subtrees(n: u8, names: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut trees = Vec::new();
+        for &(t, value) in names {
+            trees.extend(encode(tag::SEQUENCE, &encode(t, value)));
+        }
+        encode(tag::context(n), &trees)
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut value = subtrees(0, &[(0x82, b".gov.example"),
+                                      (0x81, b"example.org"),
+                                      (0x87, b"\x0a\x00\x00\x00\xff\x00\x00\x00")]);
+        value.extend(subtrees(1, &[(0x82, b"bad.gov.example"),
+                                   (0x86, b"http://x")]));
+        let value = encode(tag::SEQUENCE, &value);
+        let nc = NameConstraints::parse(&value).unwrap();
+        assert_eq!(nc.permitted, vec![Subtree::Dns(".gov.example"),
+                                      Subtree::Email("example.org"),
+                                      Subtree::Ip { addr: b"\x0a\x00\x00\x00",
+                                                    mask: b"\xff\x00\x00\x00" }]);
+        assert_eq!(nc.excluded, vec![Subtree::Dns("bad.gov.example"),
+                                     Subtree::Other(b"\x86\x08http://x")]);
+
+        assert!(nc.permits_host("www.gov.example"));
+        assert!(nc.permits_host("WWW.Gov.Example."));
+        assert!(!nc.permits_host("gov.example"));
+        assert!(!nc.permits_host("evilgov.example"));
+        assert!(!nc.permits_host("bad.gov.example"));
+        assert!(!nc.permits_host("x.bad.gov.example"));
+        assert!(nc.permits_host("10.1.2.3"));
+        assert!(!nc.permits_host("192.0.2.1"));
+        // No IPv6 subtrees, so no IPv6 addresses either.
+        assert!(!nc.permits_host("::1"));
+
+        assert!(nc.permits_email("someone@example.org"));
+        assert!(!nc.permits_email("someone@mail.example.org"));
+        assert!(!nc.permits_email("example.org"));
+
+        let bad = encode(tag::SEQUENCE, &subtrees(0, &[(0x87, b"\x0a\x00")]));
+        assert_eq!(NameConstraints::parse(&bad).unwrap_err(),
+                   DerError::BadValue("iPAddress constraint"));
+    }
+
+    #[test]
+    fn test_email() {
+        let value = encode(tag::SEQUENCE, &subtrees(0, &[(0x81, b"boss@example.com"),
+                                                         (0x81, b".example.net")]));
+        let nc = NameConstraints::parse(&value).unwrap();
+        assert!(nc.permits_email("boss@EXAMPLE.com"));
+        assert!(!nc.permits_email("Boss@example.com"));
+        assert!(nc.permits_email("anyone@mail.example.net"));
+        assert!(!nc.permits_email("anyone@example.net"));
+        // Other name types are unconstrained.
+        assert!(nc.permits_host("example.org"));
+    }
+
+    #[test]
+    fn test_certificate() {
+        let data = fixture();
+        let cert = data.certs().iter().find(|c| c.label == "Amazon Root CA 1").unwrap();
+        assert_eq!(cert.name_constraints().unwrap(), None);
+        assert_eq!(cert.all_name_constraints().unwrap(), vec![]);
+        assert!(cert.permits_host("anything.example").unwrap());
+    }
+
+    #[test]
+    fn test_builtin() {
+        for &(subject, _) in BUILTIN {
+            assert!(builtin(subject).is_some());
+        }
+        assert_eq!(builtin(b"\x30\x00"), None);
+
+        let data = fixture();
+        let cert = data.certs().iter()
+            .find(|c| c.label == "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1").unwrap();
+        assert_eq!(cert.name_constraints().unwrap(), None);
+        assert_eq!(cert.all_name_constraints().unwrap().len(), 1);
+        assert!(!cert.permits_host("www.example.com").unwrap());
+        assert!(!cert.permits_host("gov.tr").unwrap());
+        assert!(cert.permits_host("www.gov.tr").unwrap());
+        assert!(cert.permits_host("okul.meb.k12.tr").unwrap());
+        // Only DNS names are constrained.
+        assert!(cert.permits_email("someone@example.com").unwrap());
+
+        let hosts = data.roots_for_host("www.example.com").unwrap();
+        assert!(!hosts.contains(&cert));
+        assert!(hosts.iter().any(|c| c.label == "Amazon Root CA 1"));
+        assert!(data.roots_for_host("www.gov.tr").unwrap().contains(&cert));
+    }
+}
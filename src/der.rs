@@ -0,0 +1,310 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// A minimal DER reader: enough to pick apart certificates, not a
+// general ASN.1 library.  Only single-byte tags are supported, which
+// covers everything X.509 actually uses.
+
+use std::result;
+
+pub mod tag {
+    pub const BOOLEAN: u8 = 0x01;
+    pub const INTEGER: u8 = 0x02;
+    pub const BIT_STRING: u8 = 0x03;
+    pub const OCTET_STRING: u8 = 0x04;
+    pub const NULL: u8 = 0x05;
+    pub const OID: u8 = 0x06;
+    pub const UTF8_STRING: u8 = 0x0c;
+    pub const NUMERIC_STRING: u8 = 0x12;
+    pub const PRINTABLE_STRING: u8 = 0x13;
+    pub const TELETEX_STRING: u8 = 0x14;
+    pub const IA5_STRING: u8 = 0x16;
+    pub const UTC_TIME: u8 = 0x17;
+    pub const GENERALIZED_TIME: u8 = 0x18;
+    pub const VISIBLE_STRING: u8 = 0x1a;
+    pub const UNIVERSAL_STRING: u8 = 0x1c;
+    pub const BMP_STRING: u8 = 0x1e;
+    pub const SEQUENCE: u8 = 0x30;
+    pub const SET: u8 = 0x31;
+
+    pub fn context(n: u8) -> u8 {
+        0xa0 | n
+    }
+    pub fn context_primitive(n: u8) -> u8 {
+        0x80 | n
+    }
+}
+
+quick_error!{
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum DerError {
+        Truncated {
+            description("truncated DER value")
+        }
+        BadLength {
+            description("unsupported or non-minimal DER length")
+        }
+        BadTag(got: u8) {
+            description("unsupported DER tag")
+        }
+        UnexpectedTag(expected: u8, got: u8) {
+            description("unexpected DER tag")
+        }
+        TrailingData {
+            description("trailing data after DER value")
+        }
+        BadValue(what: &'static str) {
+            description("malformed DER value")
+        }
+    }
+}
+
+pub type Result<T> = result::Result<T, DerError>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tlv<'a> {
+    pub tag: u8,
+    /// The contents, without the tag and length.
+    pub value: &'a [u8],
+    /// The entire encoding, including the tag and length.
+    pub raw: &'a [u8],
+}
+
+impl<'a> Tlv<'a> {
+    pub fn reader(&self) -> Reader<'a> {
+        Reader::new(self.value)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Reader<'a> {
+    rest: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    pub fn new(buf: &'a [u8]) -> Self {
+        Reader { rest: buf }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn peek_tag(&self) -> Option<u8> {
+        self.rest.first().cloned()
+    }
+
+    pub fn read_any(&mut self) -> Result<Tlv<'a>> {
+        let buf = self.rest;
+        if buf.len() < 2 {
+            return Err(DerError::Truncated);
+        }
+        let tag = buf[0];
+        if tag & 0x1f == 0x1f {
+            return Err(DerError::BadTag(tag));
+        }
+        let (len, hdr) = match buf[1] {
+            l if l < 0x80 => (l as usize, 2),
+            0x80 => return Err(DerError::BadLength),
+            l => {
+                let n = (l & 0x7f) as usize;
+                if n > 4 || buf.len() < 2 + n {
+                    return Err(if n > 4 { DerError::BadLength } else { DerError::Truncated });
+                }
+                let len = buf[2..2 + n].iter().fold(0usize, |acc, &b| acc << 8 | b as usize);
+                if buf[2] == 0 || len < 0x80 {
+                    return Err(DerError::BadLength);
+                }
+                (len, 2 + n)
+            }
+        };
+        if buf.len() - hdr < len {
+            return Err(DerError::Truncated);
+        }
+        self.rest = &buf[hdr + len..];
+        Ok(Tlv {
+            tag,
+            value: &buf[hdr..hdr + len],
+            raw: &buf[..hdr + len],
+        })
+    }
+
+    pub fn read(&mut self, tag: u8) -> Result<Tlv<'a>> {
+        match self.peek_tag() {
+            Some(got) if got != tag => Err(DerError::UnexpectedTag(tag, got)),
+            _ => self.read_any(),
+        }
+    }
+
+    pub fn read_optional(&mut self, tag: u8) -> Result<Option<Tlv<'a>>> {
+        if self.peek_tag() == Some(tag) {
+            self.read_any().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub fn finish(&self) -> Result<()> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(DerError::TrailingData)
+        }
+    }
+}
+
+/// Parses a buffer that must hold exactly one value with the given tag.
+pub fn parse_one<'a>(buf: &'a [u8], tag: u8) -> Result<Tlv<'a>> {
+    let mut rd = Reader::new(buf);
+    let tlv = rd.read(tag)?;
+    rd.finish()?;
+    Ok(tlv)
+}
+
+/// Appends a value with the given tag and contents.
+pub fn write(out: &mut Vec<u8>, tag: u8, value: &[u8]) {
+    out.push(tag);
+    let len = value.len();
+    if len < 0x80 {
+        out.push(len as u8);
+    } else {
+        let be = (len as u64).to_be_bytes();
+        let skip = be.iter().take_while(|&&b| b == 0).count();
+        out.push(0x80 | (be.len() - skip) as u8);
+        out.extend_from_slice(&be[skip..]);
+    }
+    out.extend_from_slice(value);
+}
+
+pub fn encode(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(value.len() + 6);
+    write(&mut out, tag, value);
+    out
+}
+
+/// The magnitude of a non-negative INTEGER's contents, without the
+/// sign-padding zero byte if any.
+pub fn unsigned_int(value: &[u8]) -> Result<&[u8]> {
+    match value.split_first() {
+        None => Err(DerError::BadValue("empty INTEGER")),
+        Some((&b, _)) if b & 0x80 != 0 => Err(DerError::BadValue("negative INTEGER")),
+        Some((&0, rest)) if !rest.is_empty() => Ok(rest),
+        Some(_) => Ok(value),
+    }
+}
+
+/// Converts a small non-negative INTEGER.
+pub fn small_uint(value: &[u8]) -> Result<u64> {
+    let mag = unsigned_int(value)?;
+    if mag.len() > 8 {
+        return Err(DerError::BadValue("INTEGER too large"));
+    }
+    Ok(mag.iter().fold(0, |acc, &b| acc << 8 | u64::from(b)))
+}
+
+/// The contents of a minimal INTEGER encoding of `n`.
+pub fn uint_bytes(n: u64) -> Vec<u8> {
+    let bytes = n.to_be_bytes();
+    let skip = bytes.iter().take_while(|&&b| b == 0).count().min(7);
+    let mut out = if bytes[skip] & 0x80 != 0 { vec![0] } else { vec![] };
+    out.extend_from_slice(&bytes[skip..]);
+    out
+}
+
+/// The contents of a BIT STRING with no unused bits.
+pub fn bit_string_bytes(value: &[u8]) -> Result<&[u8]> {
+    match value.split_first() {
+        Some((&0, rest)) => Ok(rest),
+        _ => Err(DerError::BadValue("BIT STRING with unused bits")),
+    }
+}
+
+/// Dotted-decimal form of an OBJECT IDENTIFIER's contents.
+pub fn oid_to_string(value: &[u8]) -> Result<String> {
+    let mut arcs = Vec::new();
+    let mut acc: u64 = 0;
+    for (i, &b) in value.iter().enumerate() {
+        if acc == 0 && b == 0x80 {
+            return Err(DerError::BadValue("non-minimal OID arc"));
+        }
+        if acc > (u64::MAX >> 7) {
+            return Err(DerError::BadValue("OID arc too large"));
+        }
+        acc = acc << 7 | u64::from(b & 0x7f);
+        if b & 0x80 == 0 {
+            if arcs.is_empty() {
+                let first = if acc < 80 { acc / 40 } else { 2 };
+                arcs.push(first);
+                arcs.push(acc - first * 40);
+            } else {
+                arcs.push(acc);
+            }
+            acc = 0;
+        } else if i == value.len() - 1 {
+            return Err(DerError::Truncated);
+        }
+    }
+    if arcs.is_empty() {
+        return Err(DerError::BadValue("empty OID"));
+    }
+    Ok(arcs.iter().map(|a| a.to_string()).collect::<Vec<_>>().join("."))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Reader, DerError, parse_one, encode, unsigned_int, small_uint, uint_bytes,
+                oid_to_string, tag};
+
+    #[test]
+    fn test_lengths() {
+        let mut rd = Reader::new(b"\x04\x02ab\x05\x00");
+        let tlv = rd.read(tag::OCTET_STRING).unwrap();
+        assert_eq!(tlv.value, b"ab");
+        assert_eq!(tlv.raw, b"\x04\x02ab");
+        assert_eq!(rd.read(tag::OCTET_STRING), Err(DerError::UnexpectedTag(0x04, 0x05)));
+        assert_eq!(rd.read(tag::NULL).unwrap().value, b"");
+        assert!(rd.is_empty());
+
+        let long = [&b"\x04\x81\x80"[..], &[0u8; 0x80][..]].concat();
+        assert_eq!(parse_one(&long, tag::OCTET_STRING).unwrap().value.len(), 0x80);
+        assert_eq!(parse_one(b"\x04\x81\x7f", tag::OCTET_STRING), Err(DerError::BadLength));
+        assert_eq!(parse_one(b"\x04\x80\x00\x00", tag::OCTET_STRING), Err(DerError::BadLength));
+        assert_eq!(parse_one(b"\x04\x03ab", tag::OCTET_STRING), Err(DerError::Truncated));
+        assert_eq!(parse_one(b"\x04\x01ab", tag::OCTET_STRING), Err(DerError::TrailingData));
+        assert_eq!(parse_one(b"\x1f\x01\x00", 0x1f), Err(DerError::BadTag(0x1f)));
+    }
+
+    #[test]
+    fn test_encode() {
+        assert_eq!(encode(tag::NULL, b""), b"\x05\x00");
+        assert_eq!(encode(tag::OCTET_STRING, &[7; 0x7f])[..2], b"\x04\x7f"[..]);
+        assert_eq!(encode(tag::OCTET_STRING, &[7; 0x80])[..3], b"\x04\x81\x80"[..]);
+        assert_eq!(encode(tag::OCTET_STRING, &[7; 0x1234])[..4], b"\x04\x82\x12\x34"[..]);
+        let big = encode(tag::SEQUENCE, &[0; 0x10000]);
+        assert_eq!(parse_one(&big, tag::SEQUENCE).unwrap().value.len(), 0x10000);
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(unsigned_int(b"\x00\x80"), Ok(&b"\x80"[..]));
+        assert_eq!(unsigned_int(b"\x00"), Ok(&b"\x00"[..]));
+        assert!(unsigned_int(b"\x80").is_err());
+        assert!(unsigned_int(b"").is_err());
+        assert_eq!(uint_bytes(0), b"\x00");
+        assert_eq!(uint_bytes(0x7f), b"\x7f");
+        assert_eq!(uint_bytes(0x800), b"\x08\x00");
+        assert_eq!(uint_bytes(0x80), b"\x00\x80");
+        assert_eq!(small_uint(&uint_bytes(u64::MAX)), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_oids() {
+        assert_eq!(oid_to_string(b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b").unwrap(),
+                   "1.2.840.113549.1.1.11");
+        assert_eq!(oid_to_string(b"\x55\x1d\x13").unwrap(), "2.5.29.19");
+        assert_eq!(oid_to_string(b"\x88\x37\x03").unwrap(), "2.999.3");
+        assert!(oid_to_string(b"\x2a\x86").is_err());
+        assert!(oid_to_string(b"").is_err());
+    }
+}
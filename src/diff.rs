@@ -0,0 +1,256 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// What changed between two versions of certdata.txt, as a consumer of
+// the store would see it: certificates (matched by SHA-256 fingerprint)
+// appearing and disappearing, and their labels, trust levels and
+// distrust-after dates changing; and explicit distrusts of certificates
+// not in the file (matched by issuer and serial) being added or removed.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use collect::CertData;
+use structured::{Blob, Certificate, Trust, TrustLevel, Usage};
+use time::Time;
+
+const USAGES: &[Usage] = &[Usage::TlsServer, Usage::Email, Usage::CodeSigning];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// Followed by `Trust` and `DistrustAfter` changes from the
+    /// defaults (`MustVerify` and none) for anything set on it.
+    Added { label: String, sha256: [u8; 32] },
+    Removed { label: String, sha256: [u8; 32] },
+    Relabeled { sha256: [u8; 32], old: String, new: String },
+    Trust { label: String, sha256: [u8; 32], usage: Usage, old: TrustLevel, new: TrustLevel },
+    DistrustAfter {
+        label: String,
+        sha256: [u8; 32],
+        usage: Usage,
+        old: Option<Time>,
+        new: Option<Time>,
+    },
+    MozillaCaPolicy { label: String, sha256: [u8; 32], old: Option<bool>, new: Option<bool> },
+    DistrustAdded { label: String, issuer: Blob, serial: Blob },
+    DistrustRemoved { label: String, issuer: Blob, serial: Blob },
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fn date(t: Option<Time>) -> String {
+            t.map_or_else(|| "none".to_owned(), |t| t.date_string())
+        }
+        fn policy(p: Option<bool>) -> &'static str {
+            match p {
+                None => "unset",
+                Some(true) => "true",
+                Some(false) => "false",
+            }
+        }
+        match *self {
+            Change::Added { ref label, .. } => write!(f, "+ {}", label),
+            Change::Removed { ref label, .. } => write!(f, "- {}", label),
+            Change::Relabeled { ref old, ref new, .. } =>
+                write!(f, "~ {}: relabeled {:?}", old, new),
+            Change::Trust { ref label, usage, old, new, .. } =>
+                write!(f, "~ {}: {:?} trust {:?} -> {:?}", label, usage, old, new),
+            Change::DistrustAfter { ref label, usage, old, new, .. } =>
+                write!(f, "~ {}: {:?} distrust-after {} -> {}", label, usage, date(old), date(new)),
+            Change::MozillaCaPolicy { ref label, old, new, .. } =>
+                write!(f, "~ {}: Mozilla CA policy {} -> {}", label, policy(old), policy(new)),
+            Change::DistrustAdded { ref label, .. } => write!(f, "+ distrust {}", label),
+            Change::DistrustRemoved { ref label, .. } => write!(f, "- distrust {}", label),
+        }
+    }
+}
+
+// Trust settings of one certificate, with the defaults filled in.
+struct Entry<'a> {
+    cert: &'a Certificate,
+    trust: Vec<TrustLevel>,
+}
+
+fn entries(data: &CertData) -> BTreeMap<[u8; 32], Entry<'_>> {
+    data.certs().iter().map(|cert| {
+        let trust = data.trust_for_cert(cert);
+        let levels = USAGES.iter()
+            .map(|&u| trust.map_or(TrustLevel::MustVerify, |t| t.trust_level(u)))
+            .collect();
+        (cert.sha256_fingerprint(), Entry { cert, trust: levels })
+    }).collect()
+}
+
+// Trust records for certificates that aren't in the file, which
+// distrust them for something.
+fn explicit_distrusts(data: &CertData) -> BTreeMap<(&Blob, &Blob), &Trust> {
+    data.trusts().iter()
+        .filter(|t| USAGES.iter().any(|&u| t.trust_level(u) == TrustLevel::Distrust))
+        .filter(|t| !data.certs().iter().any(|c| c.issuer == t.issuer && c.serial == t.serial))
+        .map(|t| ((&t.issuer, &t.serial), t))
+        .collect()
+}
+
+fn entry_changes(sha256: [u8; 32], old: Option<&Entry>, new: &Entry, out: &mut Vec<Change>) {
+    let label = &new.cert.label;
+    let default_trust = vec![TrustLevel::MustVerify; USAGES.len()];
+    let old_trust = old.map_or(&default_trust, |e| &e.trust);
+    for (i, &usage) in USAGES.iter().enumerate() {
+        if old_trust[i] != new.trust[i] {
+            out.push(Change::Trust {
+                label: label.clone(),
+                sha256,
+                usage,
+                old: old_trust[i],
+                new: new.trust[i],
+            });
+        }
+    }
+    for &usage in USAGES {
+        let (before, after) = (old.and_then(|e| e.cert.distrust_after(usage)),
+                               new.cert.distrust_after(usage));
+        if before != after {
+            out.push(Change::DistrustAfter {
+                label: label.clone(),
+                sha256,
+                usage,
+                old: before,
+                new: after,
+            });
+        }
+    }
+    let old_policy = old.and_then(|e| e.cert.mozilla_ca_policy);
+    if old.is_some() && old_policy != new.cert.mozilla_ca_policy {
+        out.push(Change::MozillaCaPolicy {
+            label: label.clone(),
+            sha256,
+            old: old_policy,
+            new: new.cert.mozilla_ca_policy,
+        });
+    }
+}
+
+/// Everything that changed from `old` to `new`: certificate changes in
+/// label order (the old label, for removals), then explicit distrusts.
+pub fn diff(old: &CertData, new: &CertData) -> Vec<Change> {
+    let (old_entries, new_entries) = (entries(old), entries(new));
+    let mut keys: Vec<(&str, [u8; 32])> = new_entries.iter()
+        .map(|(&sha256, e)| (&e.cert.label[..], sha256))
+        .chain(old_entries.iter()
+               .filter(|&(sha256, _)| !new_entries.contains_key(sha256))
+               .map(|(&sha256, e)| (&e.cert.label[..], sha256)))
+        .collect();
+    keys.sort();
+
+    let mut out = Vec::new();
+    for (label, sha256) in keys {
+        match (old_entries.get(&sha256), new_entries.get(&sha256)) {
+            (None, Some(new)) => {
+                out.push(Change::Added { label: label.to_owned(), sha256 });
+                entry_changes(sha256, None, new, &mut out);
+            }
+            (Some(_), None) => out.push(Change::Removed { label: label.to_owned(), sha256 }),
+            (Some(old), Some(new)) => {
+                if old.cert.label != new.cert.label {
+                    out.push(Change::Relabeled {
+                        sha256,
+                        old: old.cert.label.clone(),
+                        new: new.cert.label.clone(),
+                    });
+                }
+                entry_changes(sha256, Some(old), new, &mut out);
+            }
+            (None, None) => unreachable!("key came from one of the maps"),
+        }
+    }
+
+    let (old_distrusts, new_distrusts) = (explicit_distrusts(old), explicit_distrusts(new));
+    let mut distrusts: Vec<_> = new_distrusts.iter()
+        .filter(|&(key, _)| !old_distrusts.contains_key(key))
+        .map(|(_, &t)| (t, true))
+        .chain(old_distrusts.iter()
+               .filter(|&(key, _)| !new_distrusts.contains_key(key))
+               .map(|(_, &t)| (t, false)))
+        .collect();
+    distrusts.sort_by(|&(a, _), &(b, _)| (&a.label, &a.issuer, &a.serial)
+                      .cmp(&(&b.label, &b.issuer, &b.serial)));
+    for (t, added) in distrusts {
+        let (label, issuer, serial) = (t.label.clone(), t.issuer.clone(), t.serial.clone());
+        out.push(if added {
+            Change::DistrustAdded { label, issuer, serial }
+        } else {
+            Change::DistrustRemoved { label, issuer, serial }
+        });
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{diff, Change};
+    use collect::CertData;
+    use reader::ObjectIter;
+    use structured::{Object, TrustLevel, Usage};
+    use time::Time;
+    use Error;
+
+    fn fixture_objects() -> Vec<Object> {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        ObjectIter::new(fixture).collect::<Result<_, _>>().unwrap()
+    }
+
+    fn collect(objs: Vec<Object>) -> CertData {
+        CertData::from_iter(objs.into_iter().map(Ok::<_, Error>)).unwrap()
+    }
+
+    #[test]
+    fn test_same() {
+        let data = collect(fixture_objects());
+        assert_eq!(diff(&data, &data), vec![]);
+    }
+
+    #[test]
+    fn test_changes() {
+        let old = collect(fixture_objects());
+        let mut objs = fixture_objects();
+        objs.retain(|obj| match *obj {
+            Object::Certificate(ref c) => c.label != "ACCVRAIZ1",
+            Object::Trust(ref t) => t.label != "ACCVRAIZ1" &&
+                t.label != "Explicitly Distrust Example Fraudulent Server",
+        });
+        for obj in &mut objs {
+            match *obj {
+                Object::Certificate(ref mut c) if c.label == "Amazon Root CA 1" =>
+                    c.server_distrust_after = Some(Time::from_unix(1700000000)),
+                Object::Certificate(ref mut c) if c.label == "Certigna" =>
+                    c.label = "Certigna (renamed)".to_owned(),
+                Object::Trust(ref mut t) if t.label == "Amazon Root CA 1" =>
+                    t.email_trust = TrustLevel::Distrust,
+                _ => (),
+            }
+        }
+        let new = collect(objs);
+
+        let changes: Vec<_> = diff(&old, &new).iter().map(Change::to_string).collect();
+        assert_eq!(changes, vec!["- ACCVRAIZ1",
+                                 "~ Amazon Root CA 1: Email trust TrustedDelegator -> Distrust",
+                                 "~ Amazon Root CA 1: TlsServer distrust-after none -> 2023-11-14",
+                                 "~ Certigna: relabeled \"Certigna (renamed)\"",
+                                 "- distrust Explicitly Distrust Example Fraudulent Server"]);
+
+        // And the other way round.
+        let changes = diff(&new, &old);
+        assert_eq!(changes.iter().map(Change::to_string).take(3).collect::<Vec<_>>(),
+                   vec!["+ ACCVRAIZ1",
+                        "~ ACCVRAIZ1: TlsServer trust MustVerify -> TrustedDelegator",
+                        "~ ACCVRAIZ1: Email trust MustVerify -> TrustedDelegator"]);
+        match *changes.last().unwrap() {
+            Change::DistrustAdded { ref label, .. } =>
+                assert_eq!(label, "Explicitly Distrust Example Fraudulent Server"),
+            ref other => panic!("{:?}", other),
+        }
+        let code_signing = |c: &Change| matches!(*c, Change::Trust { usage: Usage::CodeSigning, .. });
+        assert!(!changes.iter().any(code_signing));
+    }
+}
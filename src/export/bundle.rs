@@ -0,0 +1,130 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// A concatenation of PEM certificates, like Debian's
+// /etc/ssl/certs/ca-certificates.crt or curl's cacert.pem.
+
+use std::io::Write;
+
+use super::{colon_hex, one_line, select, Order};
+use collect::CertData;
+use pem;
+use structured::Usage;
+use time::Time;
+use Error;
+
+/// With everything turned off (the default) the output is just the
+/// certificates, which is what Debian's `update-ca-certificates`
+/// produces.  With `label` alone it's laid out like curl's
+/// `cacert.pem` from `mk-ca-bundle`, minus that file's preamble; the
+/// fingerprint and validity comments are this crate's own.
+#[derive(Debug, Clone, Default)]
+pub struct PemOptions {
+    /// Precede each certificate with its label, underlined with `=`.
+    pub label: bool,
+    /// ...and a comment with its SHA-256 fingerprint.
+    pub fingerprint: bool,
+    /// ...and comments with its validity period.
+    pub validity: bool,
+    /// Leave out certificates whose distrust-after date for the usage
+    /// is before this time.
+    pub distrust_as_of: Option<Time>,
+    pub order: Order,
+}
+
+pub fn write_pem_bundle<W: Write>(out: &mut W, data: &CertData, usage: Usage, opts: &PemOptions)
+                                  -> Result<(), Error> {
+    let commented = opts.label || opts.fingerprint || opts.validity;
+    for (i, cert) in select(data, usage, opts.distrust_as_of, opts.order).into_iter().enumerate() {
+        if commented && i > 0 {
+            writeln!(out)?;
+        }
+        if opts.label {
+            let label = one_line(&cert.label);
+            writeln!(out, "{}\n{}", label, "=".repeat(label.chars().count()))?;
+        }
+        if opts.fingerprint {
+            writeln!(out, "# SHA256 Fingerprint: {}", colon_hex(&cert.sha256_fingerprint()))?;
+        }
+        if opts.validity {
+            let info = cert.x509()?;
+            writeln!(out, "# Not Valid Before: {}", info.not_before)?;
+            writeln!(out, "# Not Valid After: {}", info.not_after)?;
+        }
+        out.write_all(pem::encode("CERTIFICATE", &cert.cert).as_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_pem_bundle, PemOptions};
+    use collect::CertData;
+    use fixture;
+    use pem;
+    use structured::Usage;
+
+    fn bundle(data: &CertData, usage: Usage, opts: &PemOptions) -> String {
+        let mut out = Vec::new();
+        write_pem_bundle(&mut out, data, usage, opts).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_plain() {
+        let data = fixture();
+        let text = bundle(&data, Usage::TlsServer, &PemOptions::default());
+        assert!(!text.contains('#'));
+        let blocks = pem::decode_all(&text).unwrap();
+        assert_eq!(blocks.len(), data.trusted_certs(Usage::TlsServer).len());
+        assert!(blocks.iter().all(|b| b.label == "CERTIFICATE"));
+        assert_eq!(text, bundle(&data, Usage::TlsServer, &PemOptions::default()));
+    }
+
+    #[test]
+    fn test_comments() {
+        let data = fixture();
+        let opts = PemOptions {
+            label: true,
+            fingerprint: true,
+            validity: true,
+            ..PemOptions::default()
+        };
+        let text = bundle(&data, Usage::Email, &opts);
+        assert!(text.starts_with("ACCVRAIZ1\n\
+                                  =========\n\
+                                  # SHA256 Fingerprint: 9A:6E:C0:12:E1:A7:DA:9D:BE:34:19:4D:\
+                                  47:8A:D7:C0:DB:18:22:FB:07:1D:F1:29:81:49:6E:D1:04:38:41:13\n\
+                                  # Not Valid Before: 2011-05-05T09:37:37Z\n\
+                                  # Not Valid After: 2030-12-31T09:37:37Z\n\
+                                  -----BEGIN CERTIFICATE-----\n"));
+        assert!(text.contains("-----END CERTIFICATE-----\n\nActalis Authentication Root CA\n\
+                               ==============================\n# SHA256 Fingerprint: "));
+    }
+
+    #[test]
+    fn test_distrust_after() {
+        let data = fixture();
+        let labels = |as_of: &str| {
+            let opts = PemOptions {
+                label: true,
+                distrust_as_of: Some(as_of.parse().unwrap()),
+                ..PemOptions::default()
+            };
+            let text = bundle(&data, Usage::TlsServer, &opts);
+            let lines: Vec<_> = text.lines().collect();
+            let mut dropped: Vec<_> = data.trusted_certs(Usage::TlsServer).into_iter()
+                .map(|cert| cert.label.clone())
+                .filter(|label| !lines.windows(2).any(|w| w[0] == label && w[1].starts_with('=')))
+                .collect();
+            dropped.sort();
+            dropped
+        };
+        assert_eq!(labels("2023-01-01"), Vec::<String>::new());
+        // Distrusted for TLS after 2023-02-01.
+        assert_eq!(labels("2023-03-01"), vec!["E-Tugra Certification Authority",
+                                              "E-Tugra Global Root CA ECC v3",
+                                              "E-Tugra Global Root CA RSA v3"]);
+    }
+}
@@ -0,0 +1,209 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// One row per certificate, for spreadsheets.  Fields are quoted as
+// RFC 4180 says (only when they contain the separator, a quote or a
+// line break), and lines end in CRLF, with either separator.
+
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
+use super::{sort_certs, Order};
+use collect::CertData;
+use name;
+use report::{hex, serial_hex};
+use structured::{Certificate, TrustLevel, Usage};
+use x509::PublicKey;
+use Error;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Label,
+    Subject,
+    Issuer,
+    Serial,
+    Sha1,
+    Sha256,
+    NotBefore,
+    NotAfter,
+    KeyAlgorithm,
+    KeySize,
+    TlsServerTrust,
+    EmailTrust,
+    CodeSigningTrust,
+}
+
+const NAMES: &[(Column, &str)] = &[(Column::Label, "label"),
+                                   (Column::Subject, "subject"),
+                                   (Column::Issuer, "issuer"),
+                                   (Column::Serial, "serial"),
+                                   (Column::Sha1, "sha1"),
+                                   (Column::Sha256, "sha256"),
+                                   (Column::NotBefore, "not_before"),
+                                   (Column::NotAfter, "not_after"),
+                                   (Column::KeyAlgorithm, "key_algorithm"),
+                                   (Column::KeySize, "key_size"),
+                                   (Column::TlsServerTrust, "tls_server_trust"),
+                                   (Column::EmailTrust, "email_trust"),
+                                   (Column::CodeSigningTrust, "code_signing_trust")];
+
+impl Column {
+    /// Every column, in the default order.
+    pub fn all() -> Vec<Column> {
+        NAMES.iter().map(|&(col, _)| col).collect()
+    }
+
+    /// The name used in the header row and by `from_str`.
+    pub fn name(self) -> &'static str {
+        NAMES.iter().find(|&&(col, _)| col == self).expect("every column is named").1
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownColumn(pub String);
+
+impl fmt::Display for UnknownColumn {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown column {:?}", self.0)
+    }
+}
+
+impl FromStr for Column {
+    type Err = UnknownColumn;
+    fn from_str(s: &str) -> Result<Column, UnknownColumn> {
+        NAMES.iter().find(|&&(_, name)| name == s).map(|&(col, _)| col)
+            .ok_or_else(|| UnknownColumn(s.to_owned()))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub columns: Vec<Column>,
+    /// `,` for CSV, `\t` for TSV.
+    pub separator: char,
+    pub header: bool,
+    pub order: Order,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            columns: Column::all(),
+            separator: ',',
+            header: true,
+            order: Order::Label,
+        }
+    }
+}
+
+/// Quotes a field if it needs it.
+pub fn quote_field(field: &str, separator: char) -> String {
+    if field.contains(&[separator, '"', '\r', '\n'][..]) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_owned()
+    }
+}
+
+fn trust_level(data: &CertData, cert: &Certificate, usage: Usage) -> String {
+    let level = data.trust_for_cert(cert).map_or(TrustLevel::MustVerify, |t| t.trust_level(usage));
+    format!("{:?}", level)
+}
+
+fn row(data: &CertData, cert: &Certificate, columns: &[Column]) -> Result<Vec<String>, Error> {
+    let info = cert.x509()?;
+    let key = PublicKey::parse(info.spki)?;
+    columns.iter().map(|&col| Ok(match col {
+        Column::Label => cert.label.clone(),
+        Column::Subject => name::to_rfc4514(&cert.subject)?,
+        Column::Issuer => name::to_rfc4514(&cert.issuer)?,
+        Column::Serial => serial_hex(&cert.serial)?,
+        Column::Sha1 => hex(&cert.sha1_fingerprint()),
+        Column::Sha256 => hex(&cert.sha256_fingerprint()),
+        Column::NotBefore => info.not_before.to_string(),
+        Column::NotAfter => info.not_after.to_string(),
+        Column::KeyAlgorithm => key.algorithm(),
+        Column::KeySize => key.bits().map_or(String::new(), |b| b.to_string()),
+        Column::TlsServerTrust => trust_level(data, cert, Usage::TlsServer),
+        Column::EmailTrust => trust_level(data, cert, Usage::Email),
+        Column::CodeSigningTrust => trust_level(data, cert, Usage::CodeSigning),
+    })).collect()
+}
+
+/// Writes every certificate in `data`, trusted or not.
+pub fn write_csv<W: Write>(out: &mut W, data: &CertData, opts: &CsvOptions) -> Result<(), Error> {
+    let sep = opts.separator.to_string();
+    let mut write_row = |fields: Vec<String>| -> Result<(), Error> {
+        let quoted: Vec<_> = fields.iter().map(|f| quote_field(f, opts.separator)).collect();
+        write!(out, "{}\r\n", quoted.join(&sep))?;
+        Ok(())
+    };
+    if opts.header {
+        write_row(opts.columns.iter().map(|col| col.name().to_owned()).collect())?;
+    }
+    for cert in sort_certs(data.certs().iter().collect(), opts.order) {
+        write_row(row(data, cert, &opts.columns)?)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quote_field, write_csv, Column, CsvOptions};
+    use collect::CertData;
+    use fixture;
+
+    fn csv(data: &CertData, opts: &CsvOptions) -> String {
+        let mut out = Vec::new();
+        write_csv(&mut out, data, opts).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_quoting() {
+        assert_eq!(quote_field("plain", ','), "plain");
+        assert_eq!(quote_field("a,b", ','), "\"a,b\"");
+        assert_eq!(quote_field("a,b", '\t'), "a,b");
+        assert_eq!(quote_field("say \"hi\"", ','), "\"say \"\"hi\"\"\"");
+        assert_eq!(quote_field("two\nlines", '\t'), "\"two\nlines\"");
+    }
+
+    #[test]
+    fn test_columns() {
+        assert_eq!("sha256".parse::<Column>(), Ok(Column::Sha256));
+        assert!("sha512".parse::<Column>().is_err());
+        for col in Column::all() {
+            assert_eq!(col.name().parse::<Column>(), Ok(col));
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        let data = fixture();
+        let text = csv(&data, &CsvOptions::default());
+        let lines: Vec<_> = text.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), data.certs().len() + 1);
+        assert_eq!(lines[0], "label,subject,issuer,serial,sha1,sha256,not_before,not_after,\
+                              key_algorithm,key_size,tls_server_trust,email_trust,\
+                              code_signing_trust");
+        let accv = lines.iter().find(|l| l.starts_with("ACCVRAIZ1,")).unwrap();
+        assert_eq!(*accv, "ACCVRAIZ1,\"C=ES,O=ACCV,OU=PKIACCV,CN=ACCVRAIZ1\",\
+                           \"C=ES,O=ACCV,OU=PKIACCV,CN=ACCVRAIZ1\",5EC3B7A6437FA4E0,\
+                           93057A8815C64FCE882FFA9116522878BC536417,\
+                           9A6EC012E1A7DA9DBE34194D478AD7C0DB1822FB071DF12981496ED104384113,\
+                           2011-05-05T09:37:37Z,2030-12-31T09:37:37Z,RSA,4096,\
+                           TrustedDelegator,TrustedDelegator,MustVerify");
+
+        let opts = CsvOptions {
+            columns: vec![Column::Label, Column::CodeSigningTrust],
+            separator: '\t',
+            header: false,
+            ..CsvOptions::default()
+        };
+        let text = csv(&data, &opts);
+        assert!(text.contains("\r\nMicrosec e-Szigno Root CA 2009\tTrustedDelegator\r\n"));
+        assert!(text.contains("\r\nExplicitly Distrust Example Compromised CA\tDistrust\r\n"));
+    }
+}
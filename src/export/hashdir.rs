@@ -0,0 +1,149 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// An OpenSSL `-CApath` directory, as `c_rehash` / `openssl rehash`
+// would lay it out: one PEM file per certificate, named
+// `<subject hash>.<n>` where `n` counts up from 0 among certificates
+// whose subjects hash the same.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+
+use super::{select, Order};
+use collect::CertData;
+use name;
+use pem;
+use structured::{Certificate, Usage};
+use time::Time;
+use Error;
+
+#[derive(Debug, Clone, Default)]
+pub struct HashDirOptions {
+    /// Also write each certificate under its pre-1.0 OpenSSL (MD5)
+    /// subject hash, for very old clients.
+    pub old_hash: bool,
+    /// Leave out certificates whose distrust-after date for the usage
+    /// is before this time.
+    pub distrust_as_of: Option<Time>,
+}
+
+/// The file names that `write_hash_dir` would use, and for what.
+/// Certificates are numbered in label order, so the names are stable
+/// as long as the set of roots with a given hash doesn't change.
+pub fn hash_dir_entries<'a>(data: &'a CertData, usage: Usage, opts: &HashDirOptions)
+                            -> Result<Vec<(String, &'a Certificate)>, Error> {
+    let mut seen = HashSet::new();
+    let mut counts = HashMap::new();
+    let mut entries = Vec::new();
+    for cert in select(data, usage, opts.distrust_as_of, Order::Label) {
+        if !seen.insert(cert.sha256_fingerprint()) {
+            continue;
+        }
+        let mut hashes = vec![name::openssl_hash(&cert.subject)?];
+        if opts.old_hash {
+            hashes.push(name::openssl_hash_old(&cert.subject));
+        }
+        for hash in hashes {
+            let n = counts.entry(hash).or_insert(0);
+            entries.push((format!("{:08x}.{}", hash, n), cert));
+            *n += 1;
+        }
+    }
+    Ok(entries)
+}
+
+/// Writes the directory, creating it if needed.  Existing files with
+/// the same names are replaced; anything else in it is left alone.
+pub fn write_hash_dir<P: AsRef<Path>>(dir: P, data: &CertData, usage: Usage,
+                                      opts: &HashDirOptions) -> Result<Vec<String>, Error> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let entries = hash_dir_entries(data, usage, opts)?;
+    for &(ref file_name, cert) in &entries {
+        let mut file = File::create(dir.join(file_name))?;
+        file.write_all(pem::encode("CERTIFICATE", &cert.cert).as_bytes())?;
+    }
+    Ok(entries.into_iter().map(|(file_name, _)| file_name).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{hash_dir_entries, write_hash_dir, HashDirOptions};
+    use collect::CertData;
+    use fixture;
+    use pem;
+    use reader::ObjectIter;
+    use std::env;
+    use std::fs;
+    use structured::Usage;
+
+    #[test]
+    fn test_entries() {
+        let data = fixture();
+        let entries = hash_dir_entries(&data, Usage::TlsServer, &HashDirOptions::default())
+            .unwrap();
+        assert_eq!(entries.len(), data.trusted_certs(Usage::TlsServer).len());
+        let find = |label: &str| {
+            entries.iter().filter(|e| e.1.label == label).map(|e| &e.0[..]).collect::<Vec<_>>()
+        };
+        assert_eq!(find("ACCVRAIZ1"), vec!["a94d09e5.0"]);
+
+        let opts = HashDirOptions { old_hash: true, ..HashDirOptions::default() };
+        let entries = hash_dir_entries(&data, Usage::TlsServer, &opts).unwrap();
+        let find = |label: &str| {
+            entries.iter().filter(|e| e.1.label == label).map(|e| &e.0[..]).collect::<Vec<_>>()
+        };
+        assert_eq!(find("ACCVRAIZ1"), vec!["a94d09e5.0", "3c9a4d3b.0"]);
+    }
+
+    // The fixture with a copy of ACCVRAIZ1's certificate object under
+    // another label, its signature altered if `altered`.
+    fn with_copy(altered: bool) -> CertData {
+        let text = ::std::str::from_utf8(include_bytes!("../../tests/data/certdata.txt")).unwrap();
+        let start = text.find("# Certificate \"ACCVRAIZ1\"").unwrap();
+        let end = start + text[start..].find("\n\n").unwrap() + 2;
+        let mut copy = text[start..end].replace("UTF8 \"ACCVRAIZ1\"", "UTF8 \"ACCVRAIZ1 copy\"");
+        if altered {
+            let value = copy.find("CKA_VALUE MULTILINE_OCTAL").unwrap();
+            let last = value + copy[value..].find("\nEND").unwrap();
+            let byte = if &copy[last - 4..last] == "\\000" { "\\001" } else { "\\000" };
+            copy.replace_range(last - 4..last, byte);
+        }
+        let text = format!("{}{}{}", &text[..end], copy, &text[end..]);
+        CertData::from_iter(ObjectIter::new(text.as_bytes())).unwrap()
+    }
+
+    #[test]
+    fn test_collisions() {
+        let opts = HashDirOptions { old_hash: true, ..HashDirOptions::default() };
+        let data = with_copy(true);
+        let names: Vec<_> = hash_dir_entries(&data, Usage::TlsServer, &opts).unwrap()
+            .into_iter().filter(|e| e.1.label.starts_with("ACCVRAIZ1"))
+            .map(|e| (e.0, e.1.label.clone())).collect();
+        assert_eq!(names, vec![("a94d09e5.0".to_owned(), "ACCVRAIZ1".to_owned()),
+                               ("3c9a4d3b.0".to_owned(), "ACCVRAIZ1".to_owned()),
+                               ("a94d09e5.1".to_owned(), "ACCVRAIZ1 copy".to_owned()),
+                               ("3c9a4d3b.1".to_owned(), "ACCVRAIZ1 copy".to_owned())]);
+
+        // The same certificate twice is only written once.
+        let data = with_copy(false);
+        let entries = hash_dir_entries(&data, Usage::TlsServer, &opts).unwrap();
+        assert_eq!(entries.iter().filter(|e| e.1.label.starts_with("ACCVRAIZ1")).count(), 2);
+    }
+
+    #[test]
+    fn test_write() {
+        let data = fixture();
+        let dir = env::temp_dir().join(format!("certdata-hashdir-{}", ::std::process::id()));
+        let names = write_hash_dir(&dir, &data, Usage::Email, &HashDirOptions::default()).unwrap();
+        assert_eq!(names.len(), data.trusted_certs(Usage::Email).len());
+        let text = fs::read_to_string(dir.join(&names[0])).unwrap();
+        let blocks = pem::decode_all(&text).unwrap();
+        assert_eq!(blocks.len(), 1);
+        assert_eq!(blocks[0].label, "CERTIFICATE");
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
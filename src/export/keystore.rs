@@ -0,0 +1,372 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Java truststores: the legacy JKS format and PKCS#12 (RFC 7292), the
+// latter laid out the way `keytool` writes it with
+// `keystore.pkcs12.certProtectionAlgorithm=NONE`, i.e. certificate
+// bags in a plain `data` ContentInfo, with an HMAC-SHA-256 over the
+// whole thing.  Only trusted certificate entries are written, so
+// there's nothing to encrypt.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+use sha1::{Digest, Sha1};
+use sha2::Sha256;
+
+use super::{select, Order};
+use collect::CertData;
+use der::{self, tag};
+use structured::{Certificate, Usage};
+use time::Time;
+use x509::oid as x509_oid;
+use Error;
+
+mod oid {
+    // 1.2.840.113549.1.7.1
+    pub const DATA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x07\x01";
+    // 1.2.840.113549.1.12.10.1.3
+    pub const CERT_BAG: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x0c\x0a\x01\x03";
+    // 1.2.840.113549.1.9.22.1
+    pub const X509_CERTIFICATE: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x09\x16\x01";
+    // 1.2.840.113549.1.9.20
+    pub const FRIENDLY_NAME: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x09\x14";
+    // 2.16.840.1.113894.746875.1.1: the attribute that makes Java treat
+    // a certificate bag as a trusted certificate entry.
+    pub const ORACLE_TRUSTED_KEY_USAGE: &[u8] = b"\x60\x86\x48\x01\x86\xf9\x66\xad\xca\x7b\x01\x01";
+    // 2.16.840.1.101.3.4.2.1
+    pub const SHA256: &[u8] = b"\x60\x86\x48\x01\x65\x03\x04\x02\x01";
+}
+
+#[derive(Debug, Clone)]
+pub struct KeyStoreOptions {
+    /// The store password, which only protects integrity here.
+    pub password: String,
+    /// Leave out certificates whose distrust-after date for the usage
+    /// is before this time.
+    pub distrust_as_of: Option<Time>,
+    /// The creation date JKS records for each entry; defaults to now.
+    pub created: Option<Time>,
+    /// PKCS#12 MAC key derivation iterations.
+    pub iterations: u32,
+}
+
+impl Default for KeyStoreOptions {
+    fn default() -> Self {
+        // Java's defaults for its own `cacerts`.
+        KeyStoreOptions {
+            password: "changeit".to_owned(),
+            distrust_as_of: None,
+            created: None,
+            iterations: 10000,
+        }
+    }
+}
+
+/// Aliases for `certs`, in the same order: the label, with ` (2)`,
+/// ` (3)`, etc. appended to repeats.  Uniqueness is case-insensitive
+/// because Java's keystores are.
+pub fn unique_aliases(certs: &[&Certificate]) -> Vec<String> {
+    let mut taken = HashSet::new();
+    certs.iter().map(|cert| {
+        let base = if cert.label.is_empty() { "certificate" } else { &cert.label[..] };
+        let mut alias = base.to_owned();
+        let mut n = 1;
+        while !taken.insert(alias.to_lowercase()) {
+            n += 1;
+            alias = format!("{} ({})", base, n);
+        }
+        alias
+    }).collect()
+}
+
+fn invalid(what: &'static str) -> Error {
+    Error::IOError(io::Error::new(io::ErrorKind::InvalidInput, what))
+}
+
+// Java's DataOutput.writeUTF: "modified UTF-8" (NUL as two bytes,
+// supplementary characters as surrogate pairs) with a 16-bit length.
+fn java_utf(out: &mut Vec<u8>, s: &str) -> Result<(), Error> {
+    let mut buf = Vec::with_capacity(s.len());
+    for unit in s.encode_utf16() {
+        match unit {
+            0x0001..=0x007f => buf.push(unit as u8),
+            0x0000..=0x07ff => {
+                buf.push(0xc0 | (unit >> 6) as u8);
+                buf.push(0x80 | (unit & 0x3f) as u8);
+            }
+            _ => {
+                buf.push(0xe0 | (unit >> 12) as u8);
+                buf.push(0x80 | (unit >> 6 & 0x3f) as u8);
+                buf.push(0x80 | (unit & 0x3f) as u8);
+            }
+        }
+    }
+    if buf.len() > 0xffff {
+        return Err(invalid("string too long for a Java keystore"));
+    }
+    out.extend_from_slice(&(buf.len() as u16).to_be_bytes());
+    out.extend_from_slice(&buf);
+    Ok(())
+}
+
+fn utf16_be(s: &str) -> Vec<u8> {
+    s.encode_utf16().flat_map(|unit| unit.to_be_bytes().to_vec()).collect()
+}
+
+/// The JKS integrity check: SHA-1 over the password, a fixed string,
+/// and everything before it in the file.
+pub fn jks_digest(password: &str, contents: &[u8]) -> [u8; 20] {
+    let mut hash = Sha1::new();
+    hash.update(utf16_be(password));
+    hash.update(b"Mighty Aphrodite");
+    hash.update(contents);
+    hash.finalize().into()
+}
+
+/// Writes the certificates trusted for `usage` as JKS trusted
+/// certificate entries, in label order.  Aliases are lower-cased, as
+/// `keytool` does.
+pub fn write_jks<W: Write>(out: &mut W, data: &CertData, usage: Usage, opts: &KeyStoreOptions)
+                           -> Result<(), Error> {
+    let certs = select(data, usage, opts.distrust_as_of, Order::Label);
+    let created = opts.created.unwrap_or_else(Time::now).unix() * 1000;
+    let mut buf = Vec::new();
+    buf.extend_from_slice(&0xfeedfeed_u32.to_be_bytes());
+    buf.extend_from_slice(&2_u32.to_be_bytes());
+    buf.extend_from_slice(&(certs.len() as u32).to_be_bytes());
+    for (cert, alias) in certs.iter().zip(unique_aliases(&certs)) {
+        // Tag 2 is a trusted certificate entry.
+        buf.extend_from_slice(&2_u32.to_be_bytes());
+        java_utf(&mut buf, &alias.to_lowercase())?;
+        buf.extend_from_slice(&created.to_be_bytes());
+        java_utf(&mut buf, "X.509")?;
+        buf.extend_from_slice(&(cert.cert.len() as u32).to_be_bytes());
+        buf.extend_from_slice(&cert.cert);
+    }
+    let digest = jks_digest(&opts.password, &buf);
+    buf.extend_from_slice(&digest);
+    out.write_all(&buf)?;
+    Ok(())
+}
+
+fn hmac_sha256(key: &[u8], msg: &[u8]) -> [u8; 32] {
+    let mut block = [0u8; 64];
+    if key.len() > block.len() {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+    let pad = |byte: u8| block.iter().map(|b| b ^ byte).collect::<Vec<_>>();
+    let inner = Sha256::new().chain_update(pad(0x36)).chain_update(msg).finalize();
+    Sha256::new().chain_update(pad(0x5c)).chain_update(inner).finalize().into()
+}
+
+// RFC 7292 appendix B.2 with SHA-256 (u = 32, v = 64), for `id` 3
+// (MAC key) and a 32-byte output, which is all that's needed here.
+fn pkcs12_mac_key(password: &str, salt: &[u8], iterations: u32) -> [u8; 32] {
+    const V: usize = 64;
+    let mut bmp = utf16_be(password);
+    bmp.extend_from_slice(&[0, 0]);
+    let fill = |s: &[u8]| -> Vec<u8> {
+        let len = s.len().div_ceil(V) * V;
+        s.iter().cycle().take(len).cloned().collect()
+    };
+    let mut hash = Sha256::new().chain_update([3u8; V]).chain_update(fill(salt))
+        .chain_update(fill(&bmp)).finalize();
+    for _ in 1..iterations {
+        hash = Sha256::digest(hash);
+    }
+    hash.into()
+}
+
+/// The PKCS#12 MAC over `auth_safe` (the contents of the PFX's
+/// outer `data` ContentInfo).
+pub fn pkcs12_mac(password: &str, salt: &[u8], iterations: u32, auth_safe: &[u8]) -> [u8; 32] {
+    hmac_sha256(&pkcs12_mac_key(password, salt, iterations), auth_safe)
+}
+
+fn seq(parts: &[&[u8]]) -> Vec<u8> {
+    der::encode(tag::SEQUENCE, &parts.concat())
+}
+
+// ContentInfo { data, [0] EXPLICIT OCTET STRING }
+fn data_content_info(content: &[u8]) -> Vec<u8> {
+    let octets = der::encode(tag::OCTET_STRING, content);
+    seq(&[&der::encode(tag::OID, oid::DATA), &der::encode(tag::context(0), &octets)])
+}
+
+fn cert_bag(cert: &Certificate, alias: &str) -> Vec<u8> {
+    let value = der::encode(tag::context(0), &der::encode(tag::OCTET_STRING, &cert.cert));
+    let bag = seq(&[&der::encode(tag::OID, oid::X509_CERTIFICATE), &value]);
+
+    let friendly_name = seq(&[&der::encode(tag::OID, oid::FRIENDLY_NAME),
+                              &der::encode(tag::SET, &der::encode(tag::BMP_STRING,
+                                                                  &utf16_be(alias)))]);
+    let trusted = seq(&[&der::encode(tag::OID, oid::ORACLE_TRUSTED_KEY_USAGE),
+                        &der::encode(tag::SET, &der::encode(tag::OID,
+                                                            x509_oid::ANY_EXT_KEY_USAGE))]);
+    seq(&[&der::encode(tag::OID, oid::CERT_BAG),
+          &der::encode(tag::context(0), &bag),
+          &der::encode(tag::SET, &[friendly_name, trusted].concat())])
+}
+
+fn pkcs12_with_salt(certs: &[&Certificate], opts: &KeyStoreOptions, salt: Option<&[u8]>)
+                    -> Vec<u8> {
+    let bags: Vec<_> = certs.iter().zip(unique_aliases(certs))
+        .map(|(cert, alias)| cert_bag(cert, &alias)).collect();
+    let auth_safe = seq(&[&data_content_info(&seq(&[&bags.concat()]))]);
+
+    // The salt only needs to be unique, not secret, so derive it from
+    // the contents rather than pulling in a random number generator;
+    // this also makes the output reproducible.
+    let derived;
+    let salt = match salt {
+        Some(salt) => salt,
+        None => {
+            derived = Sha256::digest(&auth_safe);
+            &derived[..20]
+        }
+    };
+    let mac = pkcs12_mac(&opts.password, salt, opts.iterations, &auth_safe);
+    let algorithm = seq(&[&der::encode(tag::OID, oid::SHA256), &der::encode(tag::NULL, b"")]);
+    let mac_data = seq(&[&seq(&[&algorithm, &der::encode(tag::OCTET_STRING, &mac)]),
+                         &der::encode(tag::OCTET_STRING, salt),
+                         &der::encode(tag::INTEGER, &der::uint_bytes(opts.iterations.into()))]);
+    seq(&[&der::encode(tag::INTEGER, b"\x03"), &data_content_info(&auth_safe), &mac_data])
+}
+
+/// Writes the certificates trusted for `usage` as a PKCS#12 truststore,
+/// in label order, with each alias as the bag's friendlyName.
+pub fn write_pkcs12<W: Write>(out: &mut W, data: &CertData, usage: Usage,
+                              opts: &KeyStoreOptions) -> Result<(), Error> {
+    if opts.iterations == 0 {
+        return Err(invalid("PKCS#12 MAC iteration count must be positive"));
+    }
+    let certs = select(data, usage, opts.distrust_as_of, Order::Label);
+    out.write_all(&pkcs12_with_salt(&certs, opts, None))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{jks_digest, pkcs12_mac, pkcs12_with_salt, unique_aliases, write_jks,
+                write_pkcs12, KeyStoreOptions};
+    use collect::CertData;
+    use der::{self, tag};
+    use fixture;
+    use structured::{Certificate, Usage};
+
+    // Both made with `keytool -importcert` (JDK 17) from the three
+    // certificates below, password "changeit"; the PKCS#12 one with
+    // `-J-Dkeystore.pkcs12.certProtectionAlgorithm=NONE
+    // -J-Dkeystore.pkcs12.macAlgorithm=HmacPBESHA256
+    // -J-Dkeystore.pkcs12.macIterationCount=2048`.
+    const JKS: &[u8] = include_bytes!("../../tests/data/truststore.jks");
+    const PKCS12: &[u8] = include_bytes!("../../tests/data/truststore.p12");
+    const LABELS: &[&str] = &["ACCVRAIZ1", "Amazon Root CA 1",
+                              "NetLock Arany =Class Gold= Főtanúsítvány"];
+
+    fn some_certs(data: &CertData) -> Vec<&Certificate> {
+        LABELS.iter().map(|&l| data.certs().iter().find(|c| c.label == l).unwrap()).collect()
+    }
+
+    fn u32_at(buf: &[u8], pos: usize) -> usize {
+        u32::from_be_bytes([buf[pos], buf[pos + 1], buf[pos + 2], buf[pos + 3]]) as usize
+    }
+
+    // (alias, certificate) pairs, after checking the digest.
+    fn read_jks(buf: &[u8]) -> Vec<(String, Vec<u8>)> {
+        let (body, digest) = buf.split_at(buf.len() - 20);
+        assert_eq!(jks_digest("changeit", body), digest);
+        assert_eq!(&body[..8], b"\xfe\xed\xfe\xed\x00\x00\x00\x02");
+        let utf = |pos: usize| {
+            let len = (body[pos] as usize) << 8 | body[pos + 1] as usize;
+            (String::from_utf8(body[pos + 2..pos + 2 + len].to_vec()).unwrap(), pos + 2 + len)
+        };
+        let mut pos = 12;
+        let mut entries = Vec::new();
+        for _ in 0..u32_at(body, 8) {
+            assert_eq!(u32_at(body, pos), 2);
+            let (alias, next) = utf(pos + 4);
+            let (kind, next) = utf(next + 8);
+            assert_eq!(kind, "X.509");
+            let len = u32_at(body, next);
+            entries.push((alias, body[next + 4..next + 4 + len].to_vec()));
+            pos = next + 4 + len;
+        }
+        assert_eq!(pos, body.len());
+        entries.sort();
+        entries
+    }
+
+    // (salt, iterations, authSafe), after checking the MAC.
+    fn read_pkcs12(buf: &[u8]) -> (Vec<u8>, u32, Vec<u8>) {
+        let mut pfx = der::parse_one(buf, tag::SEQUENCE).unwrap().reader();
+        assert_eq!(pfx.read(tag::INTEGER).unwrap().value, b"\x03");
+        let mut info = pfx.read(tag::SEQUENCE).unwrap().reader();
+        info.read(tag::OID).unwrap();
+        let auth_safe = der::parse_one(info.read(tag::context(0)).unwrap().value,
+                                       tag::OCTET_STRING).unwrap().value;
+        let mut mac_data = pfx.read(tag::SEQUENCE).unwrap().reader();
+        let mut digest_info = mac_data.read(tag::SEQUENCE).unwrap().reader();
+        digest_info.read(tag::SEQUENCE).unwrap();
+        let mac = digest_info.read(tag::OCTET_STRING).unwrap().value;
+        let salt = mac_data.read(tag::OCTET_STRING).unwrap().value;
+        let iterations = der::small_uint(mac_data.read(tag::INTEGER).unwrap().value).unwrap();
+        assert_eq!(pkcs12_mac("changeit", salt, iterations as u32, auth_safe), mac);
+        (salt.to_vec(), iterations as u32, auth_safe.to_vec())
+    }
+
+    #[test]
+    fn test_aliases() {
+        let data = fixture();
+        let mut certs = some_certs(&data);
+        certs.push(certs[0]);
+        certs.push(certs[1]);
+        certs.push(certs[0]);
+        assert_eq!(unique_aliases(&certs),
+                   vec!["ACCVRAIZ1", "Amazon Root CA 1", "NetLock Arany =Class Gold= Főtanúsítvány",
+                        "ACCVRAIZ1 (2)", "Amazon Root CA 1 (2)", "ACCVRAIZ1 (3)"]);
+    }
+
+    #[test]
+    fn test_jks() {
+        let data = fixture();
+        let expected = read_jks(JKS);
+        assert_eq!(expected.len(), 3);
+
+        let mut out = Vec::new();
+        write_jks(&mut out, &data, Usage::TlsServer, &KeyStoreOptions::default()).unwrap();
+        let entries = read_jks(&out);
+        assert_eq!(entries.len(), data.trusted_certs(Usage::TlsServer).len());
+        for entry in &expected {
+            assert!(entries.contains(entry), "missing {}", entry.0);
+        }
+    }
+
+    #[test]
+    fn test_pkcs12() {
+        let data = fixture();
+        let (salt, iterations, _) = read_pkcs12(PKCS12);
+        let opts = KeyStoreOptions { iterations, ..KeyStoreOptions::default() };
+        assert_eq!(pkcs12_with_salt(&some_certs(&data), &opts, Some(&salt)), PKCS12);
+
+        let mut out = Vec::new();
+        write_pkcs12(&mut out, &data, Usage::Email, &opts).unwrap();
+        let (_, _, auth_safe) = read_pkcs12(&out);
+        let mut rd = der::parse_one(&auth_safe, tag::SEQUENCE).unwrap().reader();
+        let mut info = rd.read(tag::SEQUENCE).unwrap().reader();
+        info.read(tag::OID).unwrap();
+        let contents = der::parse_one(info.read(tag::context(0)).unwrap().value,
+                                      tag::OCTET_STRING).unwrap().value;
+        let mut bags = der::parse_one(contents, tag::SEQUENCE).unwrap().reader();
+        let mut n = 0;
+        while !bags.is_empty() {
+            bags.read(tag::SEQUENCE).unwrap();
+            n += 1;
+        }
+        assert_eq!(n, data.trusted_certs(Usage::Email).len());
+    }
+}
@@ -0,0 +1,83 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Writers for the formats that other software wants its roots in.
+
+pub mod bundle;
+pub mod csv;
+pub mod hashdir;
+pub mod keystore;
+pub mod openssl;
+
+pub use self::bundle::{PemOptions, write_pem_bundle};
+pub use self::csv::{Column, CsvOptions, write_csv};
+pub use self::hashdir::{HashDirOptions, write_hash_dir};
+pub use self::keystore::{KeyStoreOptions, write_jks, write_pkcs12};
+pub use self::openssl::{OpenSslOptions, write_openssl_bundle};
+
+use collect::CertData;
+use structured::{Certificate, Usage};
+use time::Time;
+
+/// Output order; ties are broken by SHA-256 fingerprint, so the
+/// output doesn't depend on the order of `certdata.txt`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Order {
+    #[default]
+    Label,
+    Subject,
+    Fingerprint,
+}
+
+/// Upper-case hex with colons, the way `openssl` and `certdata.txt`
+/// comments print fingerprints.
+pub fn colon_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect::<Vec<_>>().join(":")
+}
+
+// Labels for comments and headers, which end at a newline: control
+// characters are escaped the way Rust writes them, and the rest is left
+// readable.
+pub(crate) fn one_line(label: &str) -> String {
+    let mut out = String::with_capacity(label.len());
+    for c in label.chars() {
+        if c.is_control() {
+            out.extend(c.escape_default());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+// Whether `cert`'s distrust-after date for `usage` has passed as of
+// `as_of`.  Treating that as not trusted at all is more conservative
+// than NSS, which still accepts certificates issued before that date,
+// but it's what mk-ca-bundle does, because a flat list of roots can't
+// express anything else.
+fn past_distrust_after(cert: &Certificate, usage: Usage, as_of: Option<Time>) -> bool {
+    match (cert.distrust_after(usage), as_of) {
+        (Some(after), Some(now)) => after <= now,
+        _ => false,
+    }
+}
+
+/// Puts certificates in `order`, for writers that choose their own set.
+pub fn sort_certs(certs: Vec<&Certificate>, order: Order) -> Vec<&Certificate> {
+    let mut keyed: Vec<_> = certs.into_iter().map(|cert| (cert.sha256_fingerprint(), cert)).collect();
+    keyed.sort_by(|&(ref fa, ca), &(ref fb, cb)| match order {
+        Order::Label => (&ca.label, fa).cmp(&(&cb.label, fb)),
+        Order::Subject => (&ca.subject, fa).cmp(&(&cb.subject, fb)),
+        Order::Fingerprint => fa.cmp(fb),
+    });
+    keyed.into_iter().map(|(_, cert)| cert).collect()
+}
+
+pub(crate) fn select(data: &CertData, usage: Usage, distrust_as_of: Option<Time>, order: Order)
+          -> Vec<&Certificate> {
+    let certs = data.trusted_certs(usage).into_iter()
+        .filter(|cert| !past_distrust_after(cert, usage, distrust_as_of))
+        .collect();
+    sort_certs(certs, order)
+}
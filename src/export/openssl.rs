@@ -0,0 +1,189 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// OpenSSL's "trusted certificate" PEM format, as produced by
+// `openssl x509 -trustout` and `trust extract --format=openssl-bundle`:
+// the DER certificate immediately followed by an X509_CERT_AUX,
+//
+//     X509_CERT_AUX ::= SEQUENCE {
+//         trust       SEQUENCE OF OBJECT IDENTIFIER OPTIONAL,
+//         reject  [0] IMPLICIT SEQUENCE OF OBJECT IDENTIFIER OPTIONAL,
+//         alias       UTF8String OPTIONAL,
+//         keyid       OCTET STRING OPTIONAL,
+//         other   [1] SEQUENCE OF AlgorithmIdentifier OPTIONAL }
+//
+// which can say "trusted for" and "rejected for" per purpose, unlike
+// a plain PEM bundle.
+
+use std::io::Write;
+
+use super::{past_distrust_after, sort_certs, Order};
+use collect::CertData;
+use der::{self, tag};
+use pem;
+use structured::{Certificate, Trust, TrustLevel, Usage};
+use time::Time;
+use x509::oid;
+use Error;
+
+const PURPOSES: &[(Usage, &[u8])] = &[(Usage::TlsServer, oid::KP_SERVER_AUTH),
+                                      (Usage::Email, oid::KP_EMAIL_PROTECTION),
+                                      (Usage::CodeSigning, oid::KP_CODE_SIGNING)];
+
+#[derive(Debug, Clone, Default)]
+pub struct OpenSslOptions {
+    /// Precede each certificate with a `# Label: "..."` comment.
+    pub label: bool,
+    /// Don't list a purpose as trusted if the certificate's
+    /// distrust-after date for it is before this time.  (It isn't
+    /// listed as rejected either, since certificates issued before
+    /// that date are still fine.)
+    pub distrust_as_of: Option<Time>,
+    pub order: Order,
+}
+
+// The (trust, reject) purpose OIDs for a certificate.
+fn purposes(cert: &Certificate, trust: &Trust, distrust_as_of: Option<Time>)
+            -> (Vec<&'static [u8]>, Vec<&'static [u8]>) {
+    let mut trusted = Vec::new();
+    let mut rejected = Vec::new();
+    for &(usage, kp) in PURPOSES {
+        match trust.trust_level(usage) {
+            TrustLevel::TrustedDelegator => if !past_distrust_after(cert, usage, distrust_as_of) {
+                trusted.push(kp);
+            },
+            TrustLevel::Distrust => rejected.push(kp),
+            TrustLevel::MustVerify => (),
+        }
+    }
+    (trusted, rejected)
+}
+
+fn oid_seq(out: &mut Vec<u8>, tag: u8, oids: &[&[u8]]) {
+    if !oids.is_empty() {
+        let mut body = Vec::new();
+        for oid in oids {
+            der::write(&mut body, tag::OID, oid);
+        }
+        der::write(out, tag, &body);
+    }
+}
+
+/// The contents of a `TRUSTED CERTIFICATE` PEM block: `Distrust`
+/// becomes a reject OID, `TrustedDelegator` a trust OID, and the
+/// label becomes the alias.
+pub fn trusted_certificate(cert: &Certificate, trust: &Trust, distrust_as_of: Option<Time>)
+                           -> Vec<u8> {
+    let (trusted, rejected) = purposes(cert, trust, distrust_as_of);
+    let mut aux = Vec::new();
+    oid_seq(&mut aux, tag::SEQUENCE, &trusted);
+    oid_seq(&mut aux, tag::context(0), &rejected);
+    der::write(&mut aux, tag::UTF8_STRING, cert.label.as_bytes());
+
+    let mut out = cert.cert.to_vec();
+    der::write(&mut out, tag::SEQUENCE, &aux);
+    out
+}
+
+/// Writes every certificate whose trust record has something to say
+/// in this format, including explicitly distrusted ones.  Pure-distrust
+/// records without a certificate can't be represented and are skipped,
+/// as are certificates that would end up with neither trust nor reject
+/// OIDs, because OpenSSL would fall back to trusting those if they're
+/// self-signed.
+pub fn write_openssl_bundle<W: Write>(out: &mut W, data: &CertData, opts: &OpenSslOptions)
+                                      -> Result<(), Error> {
+    let certs = data.certs().iter()
+        .filter(|cert| data.trust_for_cert(cert).is_some_and(|trust| {
+            let (trusted, rejected) = purposes(cert, trust, opts.distrust_as_of);
+            !trusted.is_empty() || !rejected.is_empty()
+        }))
+        .collect();
+    for (i, cert) in sort_certs(certs, opts.order).into_iter().enumerate() {
+        let trust = data.trust_for_cert(cert).expect("filtered on trust above");
+        if opts.label {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            writeln!(out, "# Label: {:?}", cert.label)?;
+        }
+        let body = trusted_certificate(cert, trust, opts.distrust_as_of);
+        out.write_all(pem::encode("TRUSTED CERTIFICATE", &body).as_bytes())?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{write_openssl_bundle, OpenSslOptions};
+    use collect::CertData;
+    use der::{self, tag};
+    use fixture;
+    use pem;
+    use x509::oid;
+
+    type Oids = Vec<Vec<u8>>;
+
+    // (trust OIDs, reject OIDs, alias) for each block.
+    fn decode(text: &str) -> Vec<(Oids, Oids, String)> {
+        pem::decode_all(text).unwrap().into_iter().map(|block| {
+            assert_eq!(block.label, "TRUSTED CERTIFICATE");
+            let mut rd = der::Reader::new(&block.data);
+            rd.read(tag::SEQUENCE).unwrap();
+            let mut aux = rd.read(tag::SEQUENCE).unwrap().reader();
+            rd.finish().unwrap();
+            let oids = |seq: Option<der::Tlv>| seq.map_or(vec![], |seq| {
+                let mut rd = seq.reader();
+                let mut v = vec![];
+                while !rd.is_empty() {
+                    v.push(rd.read(tag::OID).unwrap().value.to_vec());
+                }
+                v
+            });
+            let trust = oids(aux.read_optional(tag::SEQUENCE).unwrap());
+            let reject = oids(aux.read_optional(tag::context(0)).unwrap());
+            let alias = aux.read(tag::UTF8_STRING).unwrap().value.to_vec();
+            aux.finish().unwrap();
+            (trust, reject, String::from_utf8(alias).unwrap())
+        }).collect()
+    }
+
+    fn bundle(data: &CertData, opts: &OpenSslOptions) -> String {
+        let mut out = Vec::new();
+        write_openssl_bundle(&mut out, data, opts).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_trust_and_reject() {
+        let data = fixture();
+        let entries = decode(&bundle(&data, &OpenSslOptions::default()));
+        assert_eq!(entries.len(), data.certs().len());
+
+        let find = |alias: &str| entries.iter().find(|e| e.2 == alias).unwrap().clone();
+        assert_eq!(find("Amazon Root CA 1"),
+                   (vec![oid::KP_SERVER_AUTH.to_vec(), oid::KP_EMAIL_PROTECTION.to_vec()],
+                    vec![], "Amazon Root CA 1".to_owned()));
+        assert_eq!(find("Microsec e-Szigno Root CA 2009"),
+                   (vec![oid::KP_EMAIL_PROTECTION.to_vec(), oid::KP_CODE_SIGNING.to_vec()],
+                    vec![], "Microsec e-Szigno Root CA 2009".to_owned()));
+        assert_eq!(find("Explicitly Distrust Example Compromised CA").1,
+                   vec![oid::KP_SERVER_AUTH.to_vec(), oid::KP_EMAIL_PROTECTION.to_vec(),
+                        oid::KP_CODE_SIGNING.to_vec()]);
+        assert_eq!(find("Explicitly Distrust Example Compromised CA").0, Oids::new());
+    }
+
+    #[test]
+    fn test_distrust_after() {
+        let data = fixture();
+        let opts = OpenSslOptions {
+            distrust_as_of: Some("2024-01-01".parse().unwrap()),
+            ..OpenSslOptions::default()
+        };
+        let entries = decode(&bundle(&data, &opts));
+        // The E-Tugra roots were only trusted for TLS, and that's over.
+        assert_eq!(entries.len(), data.certs().len() - 3);
+        assert!(!entries.iter().any(|e| e.2.starts_with("E-Tugra")));
+    }
+}
@@ -0,0 +1,407 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Rewriting certdata.txt in the layout NSS itself uses, so that
+// hand-edited copies can be kept tidy and compared as text:
+//
+// * LF line endings and no trailing whitespace;
+// * single spaces between an attribute's name, type and value;
+// * `UTF8` values with everything but printable ASCII as `\xHH`;
+// * `MULTILINE_OCTAL` values with 16 bytes per line;
+// * objects separated by a blank line, and certificates and their trust
+//   objects preceded by the usual comment block (`# Certificate "..."`,
+//   issuer, serial number and so on), regenerated from the certificate;
+// * distrust-after dates preceded by a `# For Server Distrust After: ...`
+//   or `# For Email Distrust After: ...` comment, also regenerated.
+//
+// The text before `BEGINDATA` is kept, as are comments before objects
+// whose comments can't be regenerated, such as trust objects for
+// certificates that aren't in the file, and comments between the other
+// attributes.  Attributes stay in the order they were written in.
+
+use std::collections::HashMap;
+
+use export::colon_hex;
+use name;
+use reader::AttrIter;
+use syntax::{Attr, Value};
+use time::Time;
+use x509::CertInfo;
+use Error;
+
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// Put certificates in label order, each followed by its trust
+    /// objects, then the remaining trust objects in label order.
+    /// Objects of other classes stay at the start.
+    pub sort: bool,
+}
+
+struct Object {
+    attrs: Vec<Attr>,
+    // The comment lines written before it, normalized.
+    comments: Vec<String>,
+    // ...and before each attribute after the first.
+    attr_comments: Vec<Vec<String>>,
+}
+
+impl Object {
+    fn get(&self, key: &str) -> Option<&Value> {
+        self.attrs.iter().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+    fn token(&self, key: &str) -> Option<&str> {
+        match self.get(key) {
+            Some(Value::Token(_, t)) => Some(t),
+            _ => None,
+        }
+    }
+    fn binary(&self, key: &str) -> Option<&[u8]> {
+        match self.get(key) {
+            Some(Value::Binary(b)) => Some(b),
+            _ => None,
+        }
+    }
+    fn label(&self) -> &str {
+        match self.get("CKA_LABEL") {
+            Some(Value::String(s)) => s,
+            _ => "",
+        }
+    }
+    fn class(&self) -> Option<&str> {
+        self.token("CKA_CLASS")
+    }
+    fn issuer_serial(&self) -> Option<(&[u8], &[u8])> {
+        match (self.binary("CKA_ISSUER"), self.binary("CKA_SERIAL_NUMBER")) {
+            (Some(issuer), Some(serial)) => Some((issuer, serial)),
+            _ => None,
+        }
+    }
+}
+
+fn normalize(line: &str) -> String {
+    line.trim_end().to_owned()
+}
+
+// Splits the text before `BEGINDATA` from the rest, and collects the
+// comment lines before each attribute.
+fn scan(text: &str) -> (Vec<String>, Vec<Vec<String>>) {
+    let mut preamble = Vec::new();
+    let mut blocks = Vec::new();
+    let mut comments = Vec::new();
+    let mut in_data = false;
+    let mut in_octal = false;
+    for line in text.lines() {
+        let trimmed = line.trim();
+        if !in_data {
+            if trimmed.starts_with("BEGINDATA") {
+                in_data = true;
+            } else {
+                preamble.push(normalize(line));
+            }
+            continue;
+        }
+        if in_octal {
+            in_octal = trimmed != "END";
+        } else if trimmed.starts_with('#') {
+            comments.push(normalize(trimmed));
+        } else if trimmed.is_empty() {
+            comments.clear();
+        } else {
+            blocks.push(comments.clone());
+            comments.clear();
+            in_octal = trimmed.split_whitespace().nth(1) == Some("MULTILINE_OCTAL");
+        }
+    }
+    (preamble, blocks)
+}
+
+// As in `UTF8` values.
+fn quote(s: &str) -> String {
+    let mut out = String::from("\"");
+    for &b in s.as_bytes() {
+        if b == b'"' || b == b'\\' || !(0x20..0x7f).contains(&b) {
+            out.push_str(&format!("\\x{:02X}", b));
+        } else {
+            out.push(b as char);
+        }
+    }
+    out.push('"');
+    out
+}
+
+pub(crate) fn write_attr(out: &mut String, (key, value): &Attr) {
+    match value {
+        Value::Token(ty, tok) => out.push_str(&format!("{} {} {}\n", key, ty, tok)),
+        Value::String(s) => out.push_str(&format!("{} UTF8 {}\n", key, quote(s))),
+        Value::Binary(bytes) => {
+            out.push_str(&format!("{} MULTILINE_OCTAL\n", key));
+            for line in bytes.chunks(16) {
+                for b in line {
+                    out.push_str(&format!("\\{:03o}", b));
+                }
+                out.push('\n');
+            }
+            out.push_str("END\n");
+        }
+    }
+}
+
+// `Thu May 05 09:37:37 2011`, as NSS's comments have it.
+fn comment_date(t: Time) -> String {
+    const DAYS: [&str; 7] = ["Sun", "Mon", "Tue", "Wed", "Thu", "Fri", "Sat"];
+    const MONTHS: [&str; 12] = ["Jan", "Feb", "Mar", "Apr", "May", "Jun",
+                                "Jul", "Aug", "Sep", "Oct", "Nov", "Dec"];
+    let (y, mo, d, h, mi, s) = t.to_ymd_hms();
+    let weekday = (t.unix().div_euclid(86400) + 4).rem_euclid(7) as usize;
+    format!("{} {} {:02} {:02}:{:02}:{:02} {}",
+            DAYS[weekday], MONTHS[mo as usize - 1], d, h, mi, s, y)
+}
+
+// The INTEGER's contents as NSS's `SECU_PrintInteger` writes them
+// after `Serial Number:`: up to four bytes as a number, in decimal and
+// hex, and anything longer as colon-separated bytes, sign byte and all.
+fn comment_serial(serial: &[u8]) -> String {
+    if serial.len() <= 4 {
+        let negative = serial.first().is_some_and(|&b| b & 0x80 != 0);
+        let n = serial.iter().fold(if negative { -1 } else { 0 }, |n: i32, &b| {
+            n << 8 | i32::from(b)
+        });
+        format!(" {} (0x{:x})", n, n)
+    } else {
+        serial.iter().map(|b| format!("{:02x}", b)).collect::<Vec<_>>().join(":")
+    }
+}
+
+// The comment NSS puts before a distrust-after date.
+fn distrust_after_comment((key, value): &Attr) -> Option<Vec<String>> {
+    let which = match &key[..] {
+        "CKA_NSS_SERVER_DISTRUST_AFTER" => "Server",
+        "CKA_NSS_EMAIL_DISTRUST_AFTER" => "Email",
+        _ => return None,
+    };
+    Some(match value {
+        Value::Binary(date) => Time::from_utc_time(date).map(|t| {
+            format!("# For {} Distrust After: {}", which, comment_date(t))
+        }).into_iter().collect(),
+        _ => Vec::new(),
+    })
+}
+
+// Everything after the `# Certificate` or `# Trust for` line.
+fn cert_comments(der: &[u8]) -> Option<Vec<String>> {
+    use sha1::Sha1;
+    use sha2::{Digest, Sha256};
+
+    let info = CertInfo::parse(der).ok()?;
+    let serial = ::der::parse_one(info.serial, ::der::tag::INTEGER).ok()?.value;
+    Some(vec![format!("# Issuer: {}", name::to_certdata_comment(info.issuer).ok()?),
+              format!("# Serial Number:{}", comment_serial(serial)),
+              format!("# Subject: {}", name::to_certdata_comment(info.subject).ok()?),
+              format!("# Not Valid Before: {}", comment_date(info.not_before)),
+              format!("# Not Valid After : {}", comment_date(info.not_after)),
+              format!("# Fingerprint (SHA-256): {}", colon_hex(&Sha256::digest(der))),
+              format!("# Fingerprint (SHA1): {}", colon_hex(&Sha1::digest(der)))])
+}
+
+fn comments_for(obj: &Object, certs: &HashMap<(&[u8], &[u8]), &[u8]>) -> Vec<String> {
+    let regenerated = match obj.class() {
+        Some("CKO_CERTIFICATE") => obj.binary("CKA_VALUE").and_then(cert_comments).map(|lines| {
+            let mut out = vec!["#".to_owned(), format!("# Certificate \"{}\"", obj.label()),
+                               "#".to_owned()];
+            out.extend(lines);
+            out
+        }),
+        Some("CKO_NSS_TRUST") => obj.issuer_serial().and_then(|key| certs.get(&key))
+            .and_then(|der| cert_comments(der)).map(|lines| {
+                let mut out = vec![format!("# Trust for \"{}\"", obj.label())];
+                out.extend(lines);
+                out
+            }),
+        _ => None,
+    };
+    regenerated.unwrap_or_else(|| obj.comments.clone())
+}
+
+fn sort_objects(objs: Vec<Object>) -> Vec<Object> {
+    let (mut others, mut certs, mut trusts) = (Vec::new(), Vec::new(), Vec::new());
+    for obj in objs {
+        match obj.class() {
+            Some("CKO_CERTIFICATE") => certs.push(obj),
+            Some("CKO_NSS_TRUST") => trusts.push(Some(obj)),
+            _ => others.push(obj),
+        }
+    }
+    // Sorting is stable, so ties keep their original order.
+    certs.sort_by(|a, b| a.label().cmp(b.label()));
+    for cert in certs {
+        let key = cert.issuer_serial().map(|(i, s)| (i.to_vec(), s.to_vec()));
+        others.push(cert);
+        for slot in &mut trusts {
+            let matches = match *slot {
+                Some(ref t) => key.is_some() &&
+                    t.issuer_serial().map(|(i, s)| (i.to_vec(), s.to_vec())) == key,
+                None => false,
+            };
+            if matches {
+                others.extend(slot.take());
+            }
+        }
+    }
+    let mut rest: Vec<_> = trusts.into_iter().flatten().collect();
+    rest.sort_by(|a, b| a.label().cmp(b.label()));
+    others.extend(rest);
+    others
+}
+
+/// Reformats a whole certdata.txt.
+pub fn format_certdata(src: &[u8], opts: &FormatOptions) -> Result<String, Error> {
+    let text = String::from_utf8_lossy(src);
+    let (preamble, mut comment_blocks) = scan(&text);
+
+    let mut objs: Vec<Object> = Vec::new();
+    let mut comment_blocks = comment_blocks.drain(..);
+    for attr in AttrIter::new(src) {
+        let attr = attr?;
+        let comments = comment_blocks.next().unwrap_or_default();
+        if attr.0 == "CKA_CLASS" || objs.is_empty() {
+            objs.push(Object { attrs: Vec::new(), comments, attr_comments: Vec::new() });
+        } else {
+            let obj = objs.last_mut().expect("not empty");
+            obj.attr_comments.push(distrust_after_comment(&attr).unwrap_or(comments));
+        }
+        objs.last_mut().expect("just pushed one").attrs.push(attr);
+    }
+
+    let mut out = String::new();
+    for line in &preamble {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if objs.is_empty() && !text.lines().any(|l| l.trim().starts_with("BEGINDATA")) {
+        return Ok(out);
+    }
+    out.push_str("BEGINDATA\n");
+
+    let certs: HashMap<(&[u8], &[u8]), &[u8]> = objs.iter()
+        .filter(|obj| obj.class() == Some("CKO_CERTIFICATE"))
+        .filter_map(|obj| match (obj.issuer_serial(), obj.binary("CKA_VALUE")) {
+            (Some(key), Some(der)) => Some((key, der)),
+            _ => None,
+        }).collect();
+    let comments: Vec<_> = objs.iter().map(|obj| comments_for(obj, &certs)).collect();
+    for (obj, comments) in objs.iter_mut().zip(comments) {
+        obj.comments = comments;
+    }
+    if opts.sort {
+        objs = sort_objects(objs);
+    }
+
+    for (i, obj) in objs.iter().enumerate() {
+        if i > 0 {
+            out.push('\n');
+        }
+        for line in &obj.comments {
+            out.push_str(line);
+            out.push('\n');
+        }
+        for (j, attr) in obj.attrs.iter().enumerate() {
+            if j > 0 {
+                for line in &obj.attr_comments[j - 1] {
+                    out.push_str(line);
+                    out.push('\n');
+                }
+            }
+            write_attr(&mut out, attr);
+        }
+    }
+    Ok(out)
+}
+
+/// Whether `format_certdata` would leave `src` unchanged.
+pub fn is_formatted(src: &[u8], opts: &FormatOptions) -> Result<bool, Error> {
+    Ok(format_certdata(src, opts)?.as_bytes() == src)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{comment_date, comment_serial, format_certdata, is_formatted, quote,
+                FormatOptions};
+    use reader::RawObjectIter;
+    use time::Time;
+
+    const FIXTURE: &[u8] = include_bytes!("../tests/data/certdata.txt");
+    // Objects as NSS writes them, including distrust-after dates and
+    // serial numbers of both lengths.
+    const UPSTREAM: &[u8] = include_bytes!("../tests/data/upstream-excerpt.txt");
+
+    #[test]
+    fn test_pieces() {
+        assert_eq!(quote("Főt \"a\\b\""), "\"F\\xC5\\x91t \\x22a\\x5Cb\\x22\"");
+        assert_eq!(comment_serial(&[0x02]), " 2 (0x2)");
+        assert_eq!(comment_serial(&[0x00, 0xfe, 0xdc]), " 65244 (0xfedc)");
+        assert_eq!(comment_serial(&[0xfe, 0xdc]), " -292 (0xfffffedc)");
+        assert_eq!(comment_serial(&[0x00, 0xfe, 0xdc, 0xe3, 0x01]), "00:fe:dc:e3:01");
+        assert_eq!(comment_serial(&[0x0d, 0xd3, 0xe3, 0xbc, 0x6c]), "0d:d3:e3:bc:6c");
+        let t = Time::from_ymd_hms(2011, 5, 5, 9, 37, 37).unwrap();
+        assert_eq!(comment_date(t), "Thu May 05 09:37:37 2011");
+    }
+
+    #[test]
+    fn test_fixture_is_canonical() {
+        let formatted = format_certdata(FIXTURE, &FormatOptions::default()).unwrap();
+        assert_eq!(formatted, String::from_utf8(FIXTURE.to_vec()).unwrap());
+    }
+
+    #[test]
+    fn test_upstream() {
+        let opts = FormatOptions::default();
+        assert!(is_formatted(UPSTREAM, &opts).unwrap());
+
+        // Comments between attributes are kept, except that distrust-after
+        // comments are written from the date.
+        let text = String::from_utf8(UPSTREAM.to_vec()).unwrap();
+        let edited = text
+            .replace("CKA_TRUST_CODE_SIGNING", "# Not for code.\nCKA_TRUST_CODE_SIGNING")
+            .replacen("# For Server Distrust After: Sat Nov 30 23:59:59 2024\n", "", 1)
+            .replacen("# For Email Distrust After: Sat Nov 30 23:59:59 2024",
+                      "# For Email Distrust After: 2024-11-30", 1);
+        let formatted = format_certdata(edited.as_bytes(), &opts).unwrap();
+        assert_eq!(formatted.matches("# Not for code.\nCKA_TRUST_CODE_SIGNING").count(), 3);
+        assert_eq!(formatted.replace("# Not for code.\n", ""), text);
+    }
+
+    #[test]
+    fn test_messy() {
+        let messy = String::from_utf8(FIXTURE.to_vec()).unwrap()
+            .replace("# Certificate \"ACCVRAIZ1\"", "# ACCV's root  ")
+            .replace("\nCKA_TOKEN CK_BBOOL CK_TRUE\n", "\nCKA_TOKEN  CK_BBOOL\tCK_TRUE   \r\n")
+            .replace("=Class Gold= F\\xC5\\x91tan", "=Class Gold= F\u{151}tan")
+            .replacen("\\002\\010\\136\\303\\267\\246\\103\\177\\244\\340\nEND",
+                      "\\002\\010\\136\n\\303\\267\\246\\103\\177\\244\\340\nEND", 1);
+        let opts = FormatOptions::default();
+        assert!(!is_formatted(messy.as_bytes(), &opts).unwrap());
+        assert_eq!(format_certdata(messy.as_bytes(), &opts).unwrap().as_bytes(), FIXTURE);
+    }
+
+    #[test]
+    fn test_sort() {
+        let opts = FormatOptions { sort: true };
+        let sorted = format_certdata(FIXTURE, &opts).unwrap();
+        assert!(is_formatted(sorted.as_bytes(), &opts).unwrap());
+        assert!(!is_formatted(FIXTURE, &opts).unwrap());
+
+        let objs: Vec<_> = RawObjectIter::new(sorted.as_bytes()).collect::<Result<_, _>>()
+            .unwrap();
+        let orig: Vec<_> = RawObjectIter::new(FIXTURE).collect::<Result<_, _>>().unwrap();
+        assert_eq!(objs.len(), orig.len());
+        for obj in &orig {
+            assert!(objs.contains(obj));
+        }
+        let start = sorted.find("# Certificate \"ACCVRAIZ1\"").unwrap();
+        let trust = sorted.find("# Trust for \"ACCVRAIZ1\"").unwrap();
+        let next = sorted.find("# Certificate \"Actalis Authentication Root CA\"").unwrap();
+        assert!(start < trust && trust < next);
+        assert!(sorted.ends_with("CKA_TRUST_STEP_UP_APPROVED CK_BBOOL CK_FALSE\n"));
+    }
+}
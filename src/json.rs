@@ -0,0 +1,120 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Just enough JSON output for the fixed-shape documents this crate
+// writes, without making serde mandatory.  Object members keep their
+// insertion order, so output is stable.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Json {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(String),
+    Array(Vec<Json>),
+    Object(Vec<(&'static str, Json)>),
+}
+
+impl Json {
+    pub fn str<S: Into<String>>(s: S) -> Json {
+        Json::Str(s.into())
+    }
+
+    pub fn opt<T, F: FnOnce(T) -> Json>(value: Option<T>, f: F) -> Json {
+        value.map_or(Json::Null, f)
+    }
+
+    /// Two-space indented, with a trailing newline.
+    pub fn to_pretty(&self) -> String {
+        let mut out = String::new();
+        self.write(&mut out, 0);
+        out.push('\n');
+        out
+    }
+
+    fn write(&self, out: &mut String, depth: usize) {
+        let indent = |out: &mut String, depth: usize| {
+            out.push('\n');
+            out.push_str(&"  ".repeat(depth));
+        };
+        match *self {
+            Json::Null => out.push_str("null"),
+            Json::Bool(b) => out.push_str(if b { "true" } else { "false" }),
+            Json::Int(n) => out.push_str(&n.to_string()),
+            Json::Str(ref s) => out.push_str(&quote(s)),
+            Json::Array(ref items) if items.is_empty() => out.push_str("[]"),
+            Json::Array(ref items) => {
+                out.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    indent(out, depth + 1);
+                    item.write(out, depth + 1);
+                }
+                indent(out, depth);
+                out.push(']');
+            }
+            Json::Object(ref members) if members.is_empty() => out.push_str("{}"),
+            Json::Object(ref members) => {
+                out.push('{');
+                for (i, &(key, ref value)) in members.iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    indent(out, depth + 1);
+                    out.push_str(&quote(key));
+                    out.push_str(": ");
+                    value.write(out, depth + 1);
+                }
+                indent(out, depth);
+                out.push('}');
+            }
+        }
+    }
+}
+
+/// A JSON string literal.
+pub fn quote(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{quote, Json};
+
+    #[test]
+    fn test_quote() {
+        assert_eq!(quote("a\"b\\c\nd\x01é"), "\"a\\\"b\\\\c\\nd\\u0001é\"");
+    }
+
+    #[test]
+    fn test_pretty() {
+        let doc = Json::Object(vec![("a", Json::Int(-1)),
+                                    ("b", Json::Array(vec![Json::Null, Json::Bool(true)])),
+                                    ("c", Json::Object(vec![])),
+                                    ("d", Json::Array(vec![]))]);
+        assert_eq!(doc.to_pretty(),
+                   "{\n  \"a\": -1,\n  \"b\": [\n    null,\n    true\n  ],\n  \"c\": {},\n  \
+                    \"d\": []\n}\n");
+    }
+}
@@ -6,16 +6,67 @@
 extern crate nom;
 #[macro_use]
 extern crate quick_error;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "verify")]
+extern crate ed25519_dalek;
+extern crate md5;
+#[cfg(feature = "verify")]
+extern crate p256;
+#[cfg(feature = "verify")]
+extern crate p384;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "verify")]
+extern crate rsa;
+extern crate sha1;
+extern crate sha2;
 
+pub mod borrowed;
+pub mod build;
+pub mod ccadb;
+pub mod codegen;
 pub mod collect;
+pub mod constraints;
+pub mod der;
+pub mod diff;
+pub mod export;
+pub mod format;
+pub mod json;
+pub mod lint;
+pub mod name;
+pub mod p11kit;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod pem;
 pub mod reader;
+pub mod report;
+#[cfg(feature = "serde")]
+mod serialize;
+pub mod snapshot;
 pub mod structured;
 pub mod syntax;
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+pub mod time;
+pub mod timeline;
+#[cfg(feature = "verify")]
+pub mod verify;
+pub mod x509;
 
-pub use collect::CertData;
+pub use borrowed::{CertDataRef, CertificateRef, TrustRef};
+pub use ccadb::CcadbError;
+pub use collect::{CertData, TrustStatus};
+pub use der::DerError;
+pub use p11kit::P11KitError;
 pub use reader::{ParseError, ObjectIter};
+pub use snapshot::SnapshotError;
 pub use structured::{StructureError, TypeError, ValueError,
                      Object, Certificate, Trust, TrustLevel, Usage};
+pub use time::Time;
+#[cfg(feature = "verify")]
+pub use verify::VerifyError;
 
 use std::io;
 
@@ -25,14 +76,44 @@ quick_error! {
         IOError(err: io::Error) {
             from()
             description(err.description())
+            display("I/O error: {}", err)
         }
         ParseError(err: ParseError) {
             from()
             description("parse error")
+            display("syntax error at byte {}", err.byte_offset)
         }
         StructureError(err: StructureError) {
             from()
             description(err.description())
+            display("bad object: {}", err)
+        }
+        DerError(err: DerError) {
+            from()
+            description(err.description())
+            display("bad DER: {}", err)
+        }
+        P11KitError(err: P11KitError) {
+            from()
+            description(err.description())
+            display("p11-kit: {}", err)
+        }
+        CcadbError(err: CcadbError) {
+            from()
+            description(err.description())
+            display("CCADB report: {}", err)
+        }
+        SnapshotError(err: SnapshotError) {
+            from()
+            description(err.description())
+            display("snapshot: {}", err)
         }
     }
 }
+
+// The bundled certdata.txt, which most of the modules' tests use.
+#[cfg(test)]
+fn fixture() -> CertData {
+    CertData::from_iter(ObjectIter::new(&include_bytes!("../tests/data/certdata.txt")[..]))
+        .unwrap()
+}
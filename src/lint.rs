@@ -0,0 +1,477 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Policy checks on a certdata.txt that parses: things that are allowed
+// by the syntax and by `Object::from_raw` but are probably mistakes, or
+// at least worth a second look.  Each check is a `Rule`, which can be
+// turned off; each finding is a `Diagnostic` pointing at the object it's
+// about.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use nom::IResult;
+
+use collect::CertData;
+use reader::{AttrIter, RawObject};
+use structured::{Certificate, Object, Trust, TrustLevel, Usage};
+use syntax::{begindata, Value};
+use time::Time;
+use x509::{oid, PublicKey};
+use Error;
+
+const USAGES: &[Usage] = &[Usage::TlsServer, Usage::Email, Usage::CodeSigning];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Worth knowing, but not a problem.
+    Note,
+    Warning,
+    Error,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match *self {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    /// An object of a known class that `Object::from_raw` rejects.
+    BadObject,
+    /// A `CKA_VALUE` that isn't an X.509 certificate.
+    BadCertificate,
+    /// Two certificates, or two trust objects, with the same label.
+    DuplicateLabel,
+    /// A `# Certificate "..."` or `# Trust for "..."` comment naming
+    /// something other than the object's label.
+    CommentLabel,
+    /// `CKA_SUBJECT`, `CKA_ISSUER` or `CKA_SERIAL_NUMBER` not matching the
+    /// certificate, or a trust object's SHA-1 or MD5 hash not matching it.
+    DerMismatch,
+    /// A certificate without a trust object.
+    MissingTrust,
+    /// A trust object without `CKA_CERT_SHA1_HASH` or `CKA_CERT_MD5_HASH`.
+    MissingHash,
+    /// A trusted root signed with MD2, MD5 or SHA-1.
+    WeakSignature,
+    /// A trusted root with an RSA key under 2048 bits.
+    WeakKey,
+    /// A root that has expired but is still trusted.
+    ExpiredTrusted,
+    /// A distrust-after date that hasn't come yet.
+    FutureDistrustAfter,
+}
+
+const RULES: &[(Rule, &str, Severity)] =
+    &[(Rule::BadObject, "bad-object", Severity::Error),
+      (Rule::BadCertificate, "bad-certificate", Severity::Error),
+      (Rule::DuplicateLabel, "duplicate-label", Severity::Error),
+      (Rule::CommentLabel, "comment-label", Severity::Warning),
+      (Rule::DerMismatch, "der-mismatch", Severity::Error),
+      (Rule::MissingTrust, "missing-trust", Severity::Warning),
+      (Rule::MissingHash, "missing-hash", Severity::Warning),
+      (Rule::WeakSignature, "weak-signature", Severity::Warning),
+      (Rule::WeakKey, "weak-key", Severity::Warning),
+      (Rule::ExpiredTrusted, "expired-trusted", Severity::Warning),
+      (Rule::FutureDistrustAfter, "future-distrust-after", Severity::Note)];
+
+impl Rule {
+    /// Every rule, in the order diagnostics for one object are given in.
+    pub fn all() -> Vec<Rule> {
+        RULES.iter().map(|&(rule, _, _)| rule).collect()
+    }
+
+    /// The rule's id, as shown in diagnostics and used by `from_str`.
+    pub fn name(self) -> &'static str {
+        RULES.iter().find(|&&(rule, _, _)| rule == self).expect("every rule is named").1
+    }
+
+    pub fn severity(self) -> Severity {
+        RULES.iter().find(|&&(rule, _, _)| rule == self).expect("every rule is named").2
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnknownRule(pub String);
+
+impl fmt::Display for UnknownRule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown lint rule {:?}", self.0)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = UnknownRule;
+    fn from_str(s: &str) -> Result<Rule, UnknownRule> {
+        RULES.iter().find(|&&(_, name, _)| name == s).map(|&(rule, _, _)| rule)
+            .ok_or_else(|| UnknownRule(s.to_owned()))
+    }
+}
+
+/// Where an object is in the file: from the start of its first attribute
+/// (after any comments) to the end of its last, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    pub start: usize,
+    pub end: usize,
+    /// Of `start`, counting from 1.
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub rule: Rule,
+    pub span: Span,
+    /// The object's `CKA_LABEL`, if it has one.
+    pub label: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn severity(&self) -> Severity {
+        self.rule.severity()
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}: {}: {} [{}]", self.span.line, self.severity(), self.label,
+               self.message, self.rule.name())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LintOptions {
+    pub rules: Vec<Rule>,
+    /// For the rules about dates.
+    pub now: Time,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        LintOptions {
+            rules: Rule::all(),
+            now: Time::now(),
+        }
+    }
+}
+
+// An object as written, with what's needed to point at it.
+struct Located {
+    raw: RawObject,
+    span: Span,
+    // The label in the `# Certificate "..."` or `# Trust for "..."`
+    // comment before it, if there is one.
+    comment_label: Option<String>,
+}
+
+// The label in a comment such as `# Certificate "Foo"`.
+fn comment_label(line: &str) -> Option<String> {
+    let line = line.trim();
+    let rest = line.strip_prefix("# Certificate \"")
+        .or_else(|| line.strip_prefix("# Trust for \""))?;
+    rest.strip_suffix('"').map(|s| s.to_owned())
+}
+
+// Skips blank and comment lines from `pos`, which is at the start of a
+// line, to the start of the first line with anything else on it.
+fn skip_junk(src: &[u8], mut pos: usize) -> usize {
+    while pos < src.len() {
+        let line_end = src[pos..].iter().position(|&b| b == b'\n').map_or(src.len(), |i| pos + i);
+        let line = &src[pos..line_end];
+        match line.iter().find(|&&b| b != b' ' && b != b'\t' && b != b'\r') {
+            None | Some(&b'#') => pos = (line_end + 1).min(src.len()),
+            Some(_) => return pos,
+        }
+    }
+    pos
+}
+
+fn locate(src: &[u8]) -> Result<Vec<Located>, Error> {
+    let mut objs: Vec<Located> = Vec::new();
+    let mut attrs = AttrIter::new(src);
+    // Where the text before the next attribute starts.
+    let mut before = match begindata(src) {
+        IResult::Done(rest, ()) => src.len() - rest.len(),
+        _ => 0,
+    };
+    while let Some(attr) = attrs.next() {
+        let (key, value) = attr?;
+        let after = attrs.offset() as usize;
+        if key == "CKA_CLASS" || objs.is_empty() {
+            let start = skip_junk(src, before);
+            let comments = String::from_utf8_lossy(&src[before..start]);
+            objs.push(Located {
+                raw: HashMap::new(),
+                span: Span {
+                    start,
+                    end: after,
+                    line: 1 + src[..start].iter().filter(|&&b| b == b'\n').count(),
+                },
+                comment_label: comments.lines().filter_map(comment_label).next_back(),
+            });
+        }
+        let obj = objs.last_mut().expect("just pushed one");
+        obj.raw.insert(key, value);
+        obj.span.end = after;
+        before = after;
+    }
+    Ok(objs)
+}
+
+struct Linter<'a> {
+    opts: &'a LintOptions,
+    out: Vec<Diagnostic>,
+}
+
+impl<'a> Linter<'a> {
+    fn report<S: Into<String>>(&mut self, rule: Rule, span: Span, label: &str, message: S) {
+        if self.opts.rules.contains(&rule) {
+            self.out.push(Diagnostic {
+                rule,
+                span,
+                label: label.to_owned(),
+                message: message.into(),
+            });
+        }
+    }
+
+    fn check_cert(&mut self, data: &CertData, cert: &Certificate, span: Span) {
+        let label = &cert.label[..];
+        let info = match cert.x509() {
+            Ok(info) => info,
+            Err(err) => {
+                self.report(Rule::BadCertificate, span, label,
+                            format!("CKA_VALUE doesn't parse: {}", err));
+                return;
+            }
+        };
+        for &(attr, value, der) in &[("CKA_SUBJECT", &cert.subject, info.subject),
+                                     ("CKA_ISSUER", &cert.issuer, info.issuer),
+                                     ("CKA_SERIAL_NUMBER", &cert.serial, info.serial)] {
+            if value[..] != *der {
+                self.report(Rule::DerMismatch, span, label,
+                            format!("{} doesn't match the certificate", attr));
+            }
+        }
+        let trust = data.trust_for_cert(cert);
+        if trust.is_none() {
+            self.report(Rule::MissingTrust, span, label, "no trust object");
+        }
+
+        let trusted: Vec<_> = USAGES.iter().cloned()
+            .filter(|&u| trust.is_some_and(|t| t.trust_level(u) == TrustLevel::TrustedDelegator))
+            .collect();
+        if !trusted.is_empty() {
+            let weak = match info.signature_oid() {
+                Ok(oid::MD2_WITH_RSA) => Some("MD2"),
+                Ok(oid::MD5_WITH_RSA) => Some("MD5"),
+                Ok(oid::SHA1_WITH_RSA) | Ok(oid::ECDSA_WITH_SHA1) | Ok(oid::DSA_WITH_SHA1) =>
+                    Some("SHA-1"),
+                _ => None,
+            };
+            if let Some(hash) = weak {
+                self.report(Rule::WeakSignature, span, label, format!("signed with {}", hash));
+            }
+            if let Ok(key @ PublicKey::Rsa { .. }) = PublicKey::parse(info.spki) {
+                match key.bits() {
+                    Some(bits) if bits < 2048 =>
+                        self.report(Rule::WeakKey, span, label,
+                                    format!("{}-bit RSA key", bits)),
+                    _ => (),
+                }
+            }
+            if info.not_after < self.opts.now {
+                let usages: Vec<_> = trusted.iter().map(|u| format!("{:?}", u)).collect();
+                self.report(Rule::ExpiredTrusted, span, label,
+                            format!("expired {} but trusted for {}",
+                                    info.not_after.date_string(), usages.join(", ")));
+            }
+        }
+
+        for &usage in USAGES {
+            match cert.distrust_after(usage) {
+                Some(t) if t > self.opts.now =>
+                    self.report(Rule::FutureDistrustAfter, span, label,
+                                format!("{:?} distrust-after {} is in the future", usage,
+                                        t.date_string())),
+                _ => (),
+            }
+        }
+    }
+
+    fn check_trust(&mut self, certs: &[(&Certificate, Span)], trust: &Trust, span: Span) {
+        let label = &trust.label[..];
+        let cert = certs.iter()
+            .find(|&&(c, _)| c.issuer == trust.issuer && c.serial == trust.serial)
+            .map(|&(c, _)| c);
+        for &(attr, hash) in &[("CKA_CERT_SHA1_HASH", &trust.sha1),
+                                   ("CKA_CERT_MD5_HASH", &trust.md5)] {
+            if hash.is_none() {
+                self.report(Rule::MissingHash, span, label, format!("no {}", attr));
+            }
+        }
+        if let Some(cert) = cert {
+            if trust.sha1.as_ref().is_some_and(|h| h[..] != cert.sha1_fingerprint()) {
+                self.report(Rule::DerMismatch, span, label,
+                            "CKA_CERT_SHA1_HASH doesn't match the certificate");
+            }
+            if trust.md5.as_ref().is_some_and(|h| h[..] != cert.md5_fingerprint()) {
+                self.report(Rule::DerMismatch, span, label,
+                            "CKA_CERT_MD5_HASH doesn't match the certificate");
+            }
+        }
+    }
+}
+
+/// Checks a whole certdata.txt.  Syntax errors are returned as errors;
+/// everything else is a diagnostic, in file order.
+pub fn lint(src: &[u8], opts: &LintOptions) -> Result<Vec<Diagnostic>, Error> {
+    let mut linter = Linter { opts, out: Vec::new() };
+
+    let mut objs = Vec::new();
+    for located in locate(src)? {
+        let label = match located.raw.get("CKA_LABEL") {
+            Some(Value::String(s)) => s.clone(),
+            _ => String::new(),
+        };
+        match Object::from_raw(located.raw) {
+            Ok(Some(obj)) => objs.push((obj, located.span, located.comment_label)),
+            Ok(None) => (),
+            Err(err) => linter.report(Rule::BadObject, located.span, &label, err.to_string()),
+        }
+    }
+    let data = CertData::from_iter(objs.iter().map(|(obj, _, _)| Ok::<_, Error>(obj.clone())))
+        .expect("no errors to collect");
+    let certs: Vec<_> = objs.iter().filter_map(|&(ref obj, span, _)| match *obj {
+        Object::Certificate(ref cert) => Some((cert, span)),
+        _ => None,
+    }).collect();
+
+    let mut cert_labels: HashMap<&str, Span> = HashMap::new();
+    let mut trust_labels: HashMap<&str, Span> = HashMap::new();
+    for &(ref obj, span, ref comment) in &objs {
+        let (label, labels, what) = match *obj {
+            Object::Certificate(ref cert) => (&cert.label[..], &mut cert_labels, "certificate"),
+            Object::Trust(ref trust) => (&trust.label[..], &mut trust_labels, "trust object"),
+        };
+        if let Some(first) = labels.get(label) {
+            linter.report(Rule::DuplicateLabel, span, label,
+                          format!("same label as the {} at line {}", what, first.line));
+        } else {
+            labels.insert(label, span);
+        }
+        match *comment {
+            Some(ref c) if c != label =>
+                linter.report(Rule::CommentLabel, span, label,
+                              format!("the comment before it says {:?}", c)),
+            _ => (),
+        }
+        match *obj {
+            Object::Certificate(ref cert) => linter.check_cert(&data, cert, span),
+            Object::Trust(ref trust) => linter.check_trust(&certs, trust, span),
+        }
+    }
+
+    let mut out = linter.out;
+    // Sorting is stable, so each object's diagnostics stay in the order
+    // they were found in.
+    out.sort_by_key(|d| d.span.start);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{lint, Diagnostic, LintOptions, Rule, Severity};
+    use time::Time;
+
+    const FIXTURE: &[u8] = include_bytes!("../tests/data/certdata.txt");
+
+    fn opts() -> LintOptions {
+        LintOptions {
+            now: Time::from_ymd_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+            ..LintOptions::default()
+        }
+    }
+
+    fn found(diags: &[Diagnostic], rule: Rule) -> Vec<(String, String)> {
+        diags.iter().filter(|d| d.rule == rule)
+            .map(|d| (d.label.clone(), d.message.clone())).collect()
+    }
+
+    #[test]
+    fn test_rules() {
+        assert_eq!("weak-key".parse::<Rule>(), Ok(Rule::WeakKey));
+        assert!("weak-keys".parse::<Rule>().is_err());
+        for rule in Rule::all() {
+            assert_eq!(rule.name().parse::<Rule>(), Ok(rule));
+        }
+        assert!(Severity::Error > Severity::Note);
+    }
+
+    #[test]
+    fn test_fixture() {
+        let diags = lint(FIXTURE, &opts()).unwrap();
+        for rule in &[Rule::BadObject, Rule::BadCertificate, Rule::DuplicateLabel,
+                      Rule::CommentLabel, Rule::DerMismatch, Rule::WeakKey] {
+            assert_eq!(found(&diags, *rule), vec![], "{:?}", rule);
+        }
+        let expired = found(&diags, Rule::ExpiredTrusted);
+        assert_eq!(expired.len(), 3);
+        assert_eq!(expired[1], ("Hongkong Post Root CA 1".to_owned(),
+                                "expired 2023-05-15 but trusted for TlsServer".to_owned()));
+        assert!(found(&diags, Rule::WeakSignature)
+                .contains(&("ACCVRAIZ1".to_owned(), "signed with SHA-1".to_owned())));
+        assert!(found(&diags, Rule::FutureDistrustAfter).iter()
+                .any(|(label, _)| label == "Certigna"));
+
+        let accv = diags.iter().find(|d| d.label == "ACCVRAIZ1").unwrap();
+        let start = accv.span.start;
+        assert!(FIXTURE[start..].starts_with(b"CKA_CLASS CK_OBJECT_CLASS CKO_CERTIFICATE\n"));
+        let end = b"CKA_NSS_EMAIL_DISTRUST_AFTER CK_BBOOL CK_FALSE\n";
+        assert!(FIXTURE[..accv.span.end].ends_with(end));
+        let line = FIXTURE.split(|&b| b == b'\n').nth(accv.span.line - 1).unwrap();
+        assert_eq!(line, b"CKA_CLASS CK_OBJECT_CLASS CKO_CERTIFICATE");
+        assert_eq!(accv.to_string(),
+                   format!("{}: warning: ACCVRAIZ1: signed with SHA-1 [weak-signature]",
+                           accv.span.line));
+
+        let only = LintOptions { rules: vec![Rule::MissingHash], ..opts() };
+        let diags = lint(FIXTURE, &only).unwrap();
+        assert!(!diags.is_empty());
+        assert!(diags.iter().all(|d| d.rule == Rule::MissingHash));
+    }
+
+    #[test]
+    fn test_broken() {
+        let text = String::from_utf8(FIXTURE.to_vec()).unwrap()
+            .replace("CKA_LABEL UTF8 \"Amazon Root CA 2\"", "CKA_LABEL UTF8 \"Amazon Root CA 1\"")
+            .replacen("# Trust for \"ACCVRAIZ1\"", "# Trust for \"ACCV\"", 1)
+            .replacen("CKA_CERTIFICATE_TYPE CK_CERTIFICATE_TYPE CKC_X_509\n", "", 1)
+            .replacen("\\107\\304\\127\\035\n", "\\107\\304\\127\\036\n", 1);
+        let diags = lint(text.as_bytes(), &opts()).unwrap();
+        assert_eq!(found(&diags, Rule::BadObject),
+                   vec![("ACCVRAIZ1".to_owned(), "missing CKA_CERTIFICATE_TYPE".to_owned())]);
+        assert_eq!(found(&diags, Rule::MissingTrust), vec![]);
+        assert_eq!(found(&diags, Rule::CommentLabel),
+                   vec![("ACCVRAIZ1".to_owned(), "the comment before it says \"ACCV\"".to_owned()),
+                        ("Amazon Root CA 1".to_owned(),
+                         "the comment before it says \"Amazon Root CA 2\"".to_owned()),
+                        ("Amazon Root CA 1".to_owned(),
+                         "the comment before it says \"Amazon Root CA 2\"".to_owned())]);
+        assert_eq!(found(&diags, Rule::DerMismatch),
+                   vec![("AC RAIZ FNMT-RCM".to_owned(),
+                         "CKA_CERT_MD5_HASH doesn't match the certificate".to_owned())]);
+        let dups = found(&diags, Rule::DuplicateLabel);
+        assert_eq!(dups.len(), 2);
+        assert!(dups[0].1.starts_with("same label as the certificate at line "));
+    }
+}
@@ -0,0 +1,384 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// X.501 distinguished names, as found in `CKA_SUBJECT` and `CKA_ISSUER`.
+
+use md5::Md5;
+use sha1::{Digest, Sha1};
+
+use der::{self, tag, DerError};
+
+/// One attribute of a relative distinguished name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ava<'a> {
+    /// Contents of the attribute type's OBJECT IDENTIFIER.
+    pub oid: &'a [u8],
+    /// The value's tag; usually a string type.
+    pub tag: u8,
+    /// The value's contents.
+    pub value: &'a [u8],
+}
+
+pub type Rdn<'a> = Vec<Ava<'a>>;
+
+/// Splits a DER-encoded Name into its RDNs, in order.
+pub fn parse(name: &[u8]) -> der::Result<Vec<Rdn<'_>>> {
+    let mut rdns = Vec::new();
+    let mut seq = der::parse_one(name, tag::SEQUENCE)?.reader();
+    while !seq.is_empty() {
+        let mut set = seq.read(tag::SET)?.reader();
+        let mut rdn = Vec::new();
+        while !set.is_empty() {
+            let mut ava = set.read(tag::SEQUENCE)?.reader();
+            let oid = ava.read(tag::OID)?.value;
+            let value = ava.read_any()?;
+            ava.finish()?;
+            rdn.push(Ava { oid, tag: value.tag, value: value.value });
+        }
+        if rdn.is_empty() {
+            return Err(DerError::BadValue("empty RDN"));
+        }
+        rdns.push(rdn);
+    }
+    Ok(rdns)
+}
+
+/// Decodes the ASN.1 string types that show up in names; T61String is
+/// treated as Latin-1, which is what everyone does in practice.
+pub fn decode_string(tag: u8, value: &[u8]) -> Option<String> {
+    match tag {
+        tag::UTF8_STRING => String::from_utf8(value.to_vec()).ok(),
+        tag::PRINTABLE_STRING | tag::IA5_STRING | tag::VISIBLE_STRING | tag::NUMERIC_STRING =>
+            if value.is_ascii() { String::from_utf8(value.to_vec()).ok() } else { None },
+        tag::TELETEX_STRING => Some(value.iter().map(|&b| b as char).collect()),
+        tag::BMP_STRING => {
+            if !value.len().is_multiple_of(2) {
+                return None;
+            }
+            let units: Vec<u16> = value.chunks(2)
+                .map(|c| u16::from(c[0]) << 8 | u16::from(c[1])).collect();
+            String::from_utf16(&units).ok()
+        }
+        tag::UNIVERSAL_STRING => {
+            if !value.len().is_multiple_of(4) {
+                return None;
+            }
+            value.chunks(4)
+                .map(|c| ::std::char::from_u32(u32::from_be_bytes([c[0], c[1], c[2], c[3]])))
+                .collect()
+        }
+        _ => None,
+    }
+}
+
+// The characters C's isspace() accepts, which is what OpenSSL uses.
+fn is_c_space(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\n' | '\x0b' | '\x0c' | '\r')
+}
+
+// OpenSSL's asn1_string_canon(): trim and collapse whitespace and
+// lower-case ASCII, leaving other characters alone.
+fn canon_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut in_space = false;
+    for c in s.trim_matches(is_c_space).chars() {
+        if is_c_space(c) {
+            if !in_space {
+                out.push(' ');
+            }
+            in_space = true;
+        } else {
+            out.push(c.to_ascii_lowercase());
+            in_space = false;
+        }
+    }
+    out
+}
+
+/// OpenSSL's canonical encoding of a name (`x509_name_canon`), which
+/// is what its subject hashes are computed over: string values are
+/// re-encoded as normalized UTF8String and the RDN SETs are
+/// concatenated without the outer SEQUENCE.
+pub fn openssl_canonical(name: &[u8]) -> der::Result<Vec<u8>> {
+    let mut out = Vec::new();
+    for rdn in parse(name)? {
+        let mut avas = Vec::new();
+        for ava in rdn {
+            let mut body = der::encode(tag::OID, ava.oid);
+            match ava.tag {
+                tag::UTF8_STRING | tag::BMP_STRING | tag::UNIVERSAL_STRING |
+                tag::PRINTABLE_STRING | tag::TELETEX_STRING | tag::IA5_STRING |
+                tag::VISIBLE_STRING => {
+                    let s = decode_string(ava.tag, ava.value)
+                        .ok_or(DerError::BadValue("undecodable string in name"))?;
+                    der::write(&mut body, tag::UTF8_STRING, canon_string(&s).as_bytes());
+                }
+                other => der::write(&mut body, other, ava.value),
+            }
+            avas.push(der::encode(tag::SEQUENCE, &body));
+        }
+        // DER's SET OF ordering.
+        avas.sort();
+        der::write(&mut out, tag::SET, &avas.concat());
+    }
+    Ok(out)
+}
+
+// Short names for attribute types, as NSS prints them.
+const ATTRIBUTE_NAMES: &[(&[u8], &str)] = &[
+    (b"\x55\x04\x03", "CN"),
+    (b"\x55\x04\x04", "SN"),
+    (b"\x55\x04\x05", "serialNumber"),
+    (b"\x55\x04\x06", "C"),
+    (b"\x55\x04\x07", "L"),
+    (b"\x55\x04\x08", "ST"),
+    (b"\x55\x04\x09", "STREET"),
+    (b"\x55\x04\x0a", "O"),
+    (b"\x55\x04\x0b", "OU"),
+    (b"\x55\x04\x0c", "title"),
+    (b"\x55\x04\x2a", "givenName"),
+    (b"\x55\x04\x61", "organizationIdentifier"),
+    (b"\x09\x92\x26\x89\x93\xf2\x2c\x64\x01\x01", "UID"),
+    (b"\x09\x92\x26\x89\x93\xf2\x2c\x64\x01\x19", "DC"),
+    (b"\x2a\x86\x48\x86\xf7\x0d\x01\x09\x01", "E"),
+];
+
+pub fn attribute_name(oid: &[u8]) -> Option<&'static str> {
+    ATTRIBUTE_NAMES.iter().find(|&&(o, _)| o == oid).map(|&(_, name)| name)
+}
+
+// RFC 4514 section 2.4.
+fn escape_value(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let last = s.chars().count().saturating_sub(1);
+    for (i, c) in s.chars().enumerate() {
+        match c {
+            '"' | '+' | ',' | ';' | '<' | '>' | '\\' => {
+                out.push('\\');
+                out.push(c);
+            }
+            '#' if i == 0 => out.push_str("\\#"),
+            ' ' if i == 0 || i == last => out.push_str("\\ "),
+            '\0' => out.push_str("\\00"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+// The keywords RFC 4514 section 3 lists.
+const RFC4514_KEYWORDS: &[&str] = &["CN", "L", "ST", "O", "OU", "C", "STREET", "DC", "UID"];
+
+fn rfc4514_with(name: &[u8], keyword: fn(&[u8]) -> Option<&'static str>) -> der::Result<String> {
+    let mut rdns = Vec::new();
+    for rdn in parse(name)?.into_iter().rev() {
+        let mut avas = Vec::new();
+        for ava in rdn {
+            let attr = match keyword(ava.oid) {
+                Some(short) => short.to_owned(),
+                None => der::oid_to_string(ava.oid)?,
+            };
+            let value = match (attribute_name(ava.oid), decode_string(ava.tag, ava.value)) {
+                (Some(_), Some(s)) => escape_value(&s),
+                _ => {
+                    let raw = der::encode(ava.tag, ava.value);
+                    format!("#{}", raw.iter().map(|b| format!("{:02x}", b)).collect::<String>())
+                }
+            };
+            avas.push(format!("{}={}", attr, value));
+        }
+        rdns.push(avas.join("+"));
+    }
+    Ok(rdns.join(","))
+}
+
+/// A name as an RFC 4514 string, e.g. `CN=Amazon Root CA 1,O=Amazon,C=US`
+/// (most specific RDN first, which is the reverse of the DER order).
+/// Attribute types without a short name are written as dotted OIDs,
+/// and values that aren't strings as `#` and the hex DER.
+pub fn to_rfc4514(name: &[u8]) -> der::Result<String> {
+    rfc4514_with(name, attribute_name)
+}
+
+/// Like `to_rfc4514`, but using only the keywords RFC 4514 itself
+/// defines, so that e.g. `serialNumber` is written as `2.5.4.5`.
+pub fn to_rfc4514_strict(name: &[u8]) -> der::Result<String> {
+    rfc4514_with(name, |oid| attribute_name(oid).filter(|k| RFC4514_KEYWORDS.contains(k)))
+}
+
+// What NSS's `CERT_NameToAscii` does with a value: quote it if it has
+// special characters or stray spaces, and backslash-escape `"` and `\`.
+// Bytes outside ASCII become `.`, as in certdata.txt, which is ASCII.
+fn nss_value(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut quote = false;
+    let mut out = String::with_capacity(s.len());
+    for (i, &b) in bytes.iter().enumerate() {
+        match b {
+            b'"' | b'\\' => {
+                out.push('\\');
+                out.push(b as char);
+            }
+            0..=0x1f => out.push_str(&format!("\\{:02X}", b)),
+            0x80..=0xff => out.push('.'),
+            b',' | b'=' | b'+' | b'<' | b'>' | b'#' | b';' => {
+                quote = true;
+                out.push(b as char);
+            }
+            b' ' => {
+                quote |= i == 0 || i == bytes.len() - 1 || bytes[i - 1] == b' ';
+                out.push(' ');
+            }
+            _ => out.push(b as char),
+        }
+    }
+    if quote {
+        format!("\"{}\"", out)
+    } else {
+        out
+    }
+}
+
+/// A name as the comments in NSS's certdata.txt have it, which is how
+/// `CERT_NameToAscii` writes it: the RDNs in the same order as RFC 4514,
+/// but with values quoted rather than escaped, other attribute types as
+/// `OID.` and the dotted OID, and `.` for each byte outside ASCII.
+pub fn to_certdata_comment(name: &[u8]) -> der::Result<String> {
+    let mut rdns = Vec::new();
+    for rdn in parse(name)?.into_iter().rev() {
+        let mut avas = Vec::new();
+        for ava in rdn {
+            let attr = match attribute_name(ava.oid) {
+                Some("organizationIdentifier") | None =>
+                    format!("OID.{}", der::oid_to_string(ava.oid)?),
+                Some(short) => short.to_owned(),
+            };
+            let value = match decode_string(ava.tag, ava.value) {
+                Some(s) => nss_value(&s),
+                None => {
+                    let raw = der::encode(ava.tag, ava.value);
+                    format!("#{}", raw.iter().map(|b| format!("{:02X}", b)).collect::<String>())
+                }
+            };
+            avas.push(format!("{}={}", attr, value));
+        }
+        rdns.push(avas.join("+"));
+    }
+    Ok(rdns.join(","))
+}
+
+fn le32(digest: &[u8]) -> u32 {
+    u32::from_le_bytes([digest[0], digest[1], digest[2], digest[3]])
+}
+
+/// `X509_NAME_hash`, as in `openssl x509 -subject_hash` and the
+/// file names in a `-CApath` directory.
+pub fn openssl_hash(name: &[u8]) -> der::Result<u32> {
+    Ok(le32(&Sha1::digest(openssl_canonical(name)?)))
+}
+
+/// `X509_NAME_hash_old`, from OpenSSL before 1.0.0: MD5 over the DER
+/// as-is.
+pub fn openssl_hash_old(name: &[u8]) -> u32 {
+    le32(&Md5::digest(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{canon_string, decode_string, openssl_canonical, openssl_hash, openssl_hash_old,
+                parse, to_certdata_comment, to_rfc4514, to_rfc4514_strict};
+    use collect::CertData;
+    use der::tag;
+    use reader::ObjectIter;
+
+    // SEQUENCE { SET { SEQUENCE { 2.5.4.6, PrintableString "US" } },
+    //            SET { SEQUENCE { 2.5.4.3, T61String "  Foo   B\xe4r  " } } }
+    const NAME: &[u8] = b"\x30\x25\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02US\
+                          \x31\x16\x30\x14\x06\x03\x55\x04\x03\x14\x0d  Foo   B\xe4r  ";
+
+    #[test]
+    fn test_parse() {
+        let rdns = parse(NAME).unwrap();
+        assert_eq!(rdns.len(), 2);
+        assert_eq!(rdns[0][0].oid, b"\x55\x04\x06");
+        assert_eq!(rdns[1][0].tag, tag::TELETEX_STRING);
+        assert!(parse(b"\x30\x02\x31\x00").is_err());
+    }
+
+    #[test]
+    fn test_strings() {
+        assert_eq!(decode_string(tag::BMP_STRING, b"\x00A\x00\xe9").unwrap(), "Aé");
+        assert_eq!(decode_string(tag::UNIVERSAL_STRING, b"\x00\x01\xf6\x00").unwrap(), "😀");
+        assert_eq!(decode_string(tag::TELETEX_STRING, b"B\xe4r").unwrap(), "Bär");
+        assert_eq!(decode_string(tag::PRINTABLE_STRING, b"B\xe4r"), None);
+        assert_eq!(decode_string(tag::BMP_STRING, b"\x00"), None);
+    }
+
+    #[test]
+    fn test_canon() {
+        assert_eq!(canon_string("  Foo \t\n  BÄR  "), "foo bÄr");
+        assert_eq!(openssl_canonical(NAME).unwrap(),
+                   &b"\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x0c\x02us\
+                      \x31\x11\x30\x0f\x06\x03\x55\x04\x03\x0c\x08foo b\xc3\xa4r"[..]);
+    }
+
+    #[test]
+    fn test_hashes() {
+        // Cross-checked with `openssl x509 -subject_hash -subject_hash_old`.
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let hashes = |label: &str| {
+            let cert = data.certs().iter().find(|c| c.label == label).unwrap();
+            (openssl_hash(&cert.subject).unwrap(), openssl_hash_old(&cert.subject))
+        };
+        assert_eq!(hashes("ACCVRAIZ1"), (0xa94d09e5, 0x3c9a4d3b));
+        assert_eq!(hashes("NetLock Arany =Class Gold= Főtanúsítvány"), (0x988a38cb, 0x60afe812));
+        assert_eq!(hashes("Hongkong Post Root CA 3"), (0x68dd7389, 0xbd43e1dd));
+    }
+
+    #[test]
+    fn test_rfc4514() {
+        assert_eq!(to_rfc4514(NAME).unwrap(), "CN=\\  Foo   B\u{e4}r \\ ,C=US");
+        // SEQUENCE { SET { SEQUENCE { 2.5.4.3, "#a,b" }, SEQUENCE { 1.2.3, INTEGER 5 } } }
+        let name = b"\x30\x15\x31\x13\x30\x0b\x06\x03\x55\x04\x03\x0c\x04#a,b\
+                     \x30\x04\x06\x02\x2a\x03\x02\x01\x05";
+        assert!(to_rfc4514(name).is_err());
+        let name = b"\x30\x18\x31\x16\x30\x0b\x06\x03\x55\x04\x03\x0c\x04#a,b\
+                     \x30\x07\x06\x02\x2a\x03\x02\x01\x05";
+        assert_eq!(to_rfc4514(name).unwrap(), "CN=\\#a\\,b+1.2.3=#020105");
+
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let cert = data.certs().iter().find(|c| c.label == "ACCVRAIZ1").unwrap();
+        // Its RDNs are in the unusual order, so this comes out backwards.
+        assert_eq!(to_rfc4514(&cert.subject).unwrap(), "C=ES,O=ACCV,OU=PKIACCV,CN=ACCVRAIZ1");
+        let cert = data.certs().iter().find(|c| c.label == "Amazon Root CA 1").unwrap();
+        assert_eq!(to_rfc4514(&cert.subject).unwrap(), "CN=Amazon Root CA 1,O=Amazon,C=US");
+        assert_eq!(to_rfc4514_strict(&cert.subject).unwrap(),
+                   "CN=Amazon Root CA 1,O=Amazon,C=US");
+        let cert = data.certs().iter().find(|c| c.label == "ANF Secure Server Root CA").unwrap();
+        assert!(to_rfc4514(&cert.subject).unwrap().ends_with(",C=ES,serialNumber=G63287510"));
+        assert!(to_rfc4514_strict(&cert.subject).unwrap().ends_with(",C=ES,2.5.4.5=G63287510"));
+        assert!(to_certdata_comment(&cert.subject).unwrap()
+                .ends_with(",C=ES,serialNumber=G63287510"));
+
+        let cert = data.certs().iter().find(|c| c.label == "Entrust Root Certification Authority")
+            .unwrap();
+        assert_eq!(to_certdata_comment(&cert.subject).unwrap(),
+                   "CN=Entrust Root Certification Authority,OU=\"(c) 2006 Entrust, Inc.\",\
+                    OU=www.entrust.net/CPS is incorporated by reference,O=\"Entrust, Inc.\",C=US");
+        let cert = data.certs().iter().find(|c| c.label == "E-Tugra Certification Authority")
+            .unwrap();
+        assert!(to_certdata_comment(&cert.subject).unwrap()
+                .contains(",O=E-Tu..ra EBG Bili..im Teknolojileri ve Hizmetleri A....,"));
+    }
+
+    #[test]
+    fn test_certdata_comment() {
+        assert_eq!(to_certdata_comment(NAME).unwrap(), "CN=\"  Foo   B..r  \",C=US");
+        // SEQUENCE { SET { SEQUENCE { 2.5.4.97, UTF8String "a\"b\\" } } }
+        let name = b"\x30\x10\x31\x0e\x30\x0c\x06\x03\x55\x04\x61\x0c\x05a\"b\\\x01";
+        assert_eq!(to_certdata_comment(name).unwrap(), "OID.2.5.4.97=a\\\"b\\\\\\01");
+    }
+}
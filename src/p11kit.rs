@@ -0,0 +1,498 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// p11-kit's persistent object format (`.p11-kit` files), which is how
+// Fedora, Arch and others ship their root stores:
+//
+//     [p11-kit-object-v1]
+//     class: certificate
+//     label: "Amazon Root CA 1"
+//     trusted: true
+//     -----BEGIN CERTIFICATE-----
+//     ...
+//
+// Per-purpose trust is expressed with separate `x-certificate-extension`
+// objects attached to the certificate's public key: an extKeyUsage
+// listing the purposes it's trusted for, and OpenSSL's pseudo-extension
+// listing the ones it's rejected for.  Distrusting a certificate
+// entirely is `x-distrusted: true`, which also works on objects that
+// have only an issuer and serial number.
+
+use std::collections::HashMap;
+use std::io::{BufRead, Write};
+
+use collect::CertData;
+use der::{self, tag};
+use export::{one_line, sort_certs, Order};
+use pem;
+use structured::{Certificate, Object, Trust, TrustLevel, Usage};
+use time::Time;
+use x509::oid;
+use Error;
+
+const PURPOSES: &[(Usage, &[u8])] = &[(Usage::TlsServer, oid::KP_SERVER_AUTH),
+                                      (Usage::Email, oid::KP_EMAIL_PROTECTION),
+                                      (Usage::CodeSigning, oid::KP_CODE_SIGNING)];
+const ALL_USAGES: &[Usage] = &[Usage::TlsServer, Usage::Email, Usage::CodeSigning];
+
+const HEADER: &str = "[p11-kit-object-v1]";
+
+quick_error!{
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum P11KitError {
+        Syntax(line: usize, what: &'static str) {
+            description("p11-kit syntax error")
+            display("line {}: {}", line, what)
+        }
+        BadObject(line: usize, what: &'static str) {
+            description("unusable p11-kit object")
+            display("object at line {}: {}", line, what)
+        }
+    }
+}
+
+// p11-kit percent-encodes everything but these in quoted values.
+fn is_verbatim(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"-_.~/ ".contains(&b)
+}
+
+fn quote(value: &[u8]) -> String {
+    let mut out = String::from("\"");
+    for &b in value {
+        if is_verbatim(b) {
+            out.push(b as char);
+        } else {
+            out.push_str(&format!("%{:02x}", b));
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn unquote(value: &str) -> Option<Vec<u8>> {
+    let inner = value.strip_prefix('"')?.strip_suffix('"')?.as_bytes();
+    let mut out = Vec::with_capacity(inner.len());
+    let mut i = 0;
+    while i < inner.len() {
+        if inner[i] == b'%' {
+            let hex = inner.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(::std::str::from_utf8(hex).ok()?, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(inner[i]);
+            i += 1;
+        }
+    }
+    Some(out)
+}
+
+// The DER of an `Extension` whose value is a list of purposes.
+fn purpose_extension(ext_oid: &[u8], purposes: &[&[u8]]) -> Vec<u8> {
+    let mut list = Vec::new();
+    for kp in purposes {
+        der::write(&mut list, tag::OID, kp);
+    }
+    let mut body = Vec::new();
+    der::write(&mut body, tag::OID, ext_oid);
+    der::write(&mut body, tag::OCTET_STRING, &der::encode(tag::SEQUENCE, &list));
+    der::encode(tag::SEQUENCE, &body)
+}
+
+fn write_extension<W: Write>(out: &mut W, label: &str, ext_oid: &[u8], purposes: &[&[u8]],
+                             spki: &[u8]) -> Result<(), Error> {
+    writeln!(out, "{}", HEADER)?;
+    writeln!(out, "class: x-certificate-extension")?;
+    writeln!(out, "label: {}", quote(label.as_bytes()))?;
+    writeln!(out, "object-id: {}", der::oid_to_string(ext_oid)?)?;
+    writeln!(out, "value: {}", quote(&purpose_extension(ext_oid, purposes)))?;
+    writeln!(out, "modifiable: false")?;
+    out.write_all(pem::encode("PUBLIC KEY", spki).as_bytes())?;
+    writeln!(out)?;
+    Ok(())
+}
+
+fn write_cert<W: Write>(out: &mut W, cert: &Certificate, trust: Option<&Trust>)
+                        -> Result<(), Error> {
+    let level = |usage| trust.map_or(TrustLevel::MustVerify, |t| t.trust_level(usage));
+    let trusted: Vec<_> = PURPOSES.iter()
+        .filter(|&&(usage, _)| level(usage) == TrustLevel::TrustedDelegator)
+        .map(|&(_, kp)| kp).collect();
+    let rejected: Vec<_> = PURPOSES.iter()
+        .filter(|&&(usage, _)| level(usage) == TrustLevel::Distrust)
+        .map(|&(_, kp)| kp).collect();
+
+    writeln!(out, "# {}", one_line(&cert.label))?;
+    writeln!(out, "{}", HEADER)?;
+    writeln!(out, "class: certificate")?;
+    writeln!(out, "certificate-type: x-509")?;
+    writeln!(out, "label: {}", quote(cert.label.as_bytes()))?;
+    writeln!(out, "modifiable: false")?;
+    if rejected.len() == PURPOSES.len() {
+        writeln!(out, "x-distrusted: true")?;
+    } else if !trusted.is_empty() {
+        writeln!(out, "trusted: true")?;
+    }
+    if let Some(policy) = cert.mozilla_ca_policy {
+        writeln!(out, "nss-mozilla-ca-policy: {}", policy)?;
+    }
+    let dates = [("nss-server-distrust-after", cert.server_distrust_after),
+                 ("nss-email-distrust-after", cert.email_distrust_after)];
+    for &(key, date) in &dates {
+        if let Some(t) = date.and_then(|t| t.to_utc_time()) {
+            writeln!(out, "{}: {}", key, quote(t.as_bytes()))?;
+        }
+    }
+    out.write_all(pem::encode("CERTIFICATE", &cert.cert).as_bytes())?;
+    writeln!(out)?;
+
+    let rejected_some = !rejected.is_empty() && rejected.len() < PURPOSES.len();
+    if !trusted.is_empty() || rejected_some {
+        let spki = cert.x509()?.spki;
+        if !trusted.is_empty() {
+            write_extension(out, &cert.label, oid::EXT_KEY_USAGE, &trusted, spki)?;
+        }
+        if rejected_some {
+            write_extension(out, &cert.label, oid::OPENSSL_REJECT, &rejected, spki)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_distrust<W: Write>(out: &mut W, trust: &Trust) -> Result<(), Error> {
+    writeln!(out, "# {}", one_line(&trust.label))?;
+    writeln!(out, "{}", HEADER)?;
+    writeln!(out, "class: certificate")?;
+    writeln!(out, "certificate-type: x-509")?;
+    writeln!(out, "label: {}", quote(trust.label.as_bytes()))?;
+    writeln!(out, "modifiable: false")?;
+    writeln!(out, "issuer: {}", quote(&trust.issuer))?;
+    writeln!(out, "serial-number: {}", quote(&trust.serial))?;
+    writeln!(out, "x-distrusted: true")?;
+    writeln!(out)?;
+    Ok(())
+}
+
+/// Writes every certificate (in label order), with its trust, followed
+/// by the trust records that don't have a certificate; of those, only
+/// the ones that distrust for every purpose can be represented.
+pub fn write_p11kit<W: Write>(out: &mut W, data: &CertData) -> Result<(), Error> {
+    for cert in sort_certs(data.certs().iter().collect(), Order::Label) {
+        write_cert(out, cert, data.trust_for_cert(cert))?;
+    }
+    let mut orphans: Vec<_> = data.trusts().iter()
+        .filter(|trust| !data.certs().iter().any(|cert| {
+            cert.issuer == trust.issuer && cert.serial == trust.serial
+        }))
+        .filter(|trust| ALL_USAGES.iter().all(|&u| trust.trust_level(u) == TrustLevel::Distrust))
+        .collect();
+    orphans.sort_by(|a, b| a.label.cmp(&b.label));
+    for trust in orphans {
+        write_distrust(out, trust)?;
+    }
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Value {
+    Bytes(Vec<u8>),
+    Word(String),
+}
+
+#[derive(Debug, Default)]
+struct Section {
+    line: usize,
+    attrs: HashMap<String, Value>,
+    blocks: Vec<pem::Block>,
+}
+
+impl Section {
+    fn bytes(&self, key: &str) -> Option<&[u8]> {
+        match self.attrs.get(key) {
+            Some(Value::Bytes(b)) => Some(b),
+            _ => None,
+        }
+    }
+    fn word(&self, key: &str) -> Option<&str> {
+        match self.attrs.get(key) {
+            Some(Value::Word(w)) => Some(w),
+            _ => None,
+        }
+    }
+    fn flag(&self, key: &str) -> bool {
+        self.word(key) == Some("true")
+    }
+    fn block(&self, label: &str) -> Option<&[u8]> {
+        self.blocks.iter().find(|b| b.label == label).map(|b| &b.data[..])
+    }
+    fn label(&self) -> String {
+        self.bytes("label").map_or_else(String::new, |l| String::from_utf8_lossy(l).into_owned())
+    }
+    fn date(&self, key: &str) -> Result<Option<Time>, P11KitError> {
+        match self.bytes(key) {
+            None => Ok(None),
+            Some(t) => Time::from_utc_time(t).map(Some)
+                .ok_or(P11KitError::BadObject(self.line, "bad distrust-after date")),
+        }
+    }
+}
+
+fn parse_sections(text: &str) -> Result<Vec<Section>, P11KitError> {
+    let mut sections: Vec<Section> = Vec::new();
+    let mut pem_text: Option<String> = None;
+    for (i, line) in text.lines().enumerate() {
+        let lineno = i + 1;
+        if let Some(mut body) = pem_text.take() {
+            body.push_str(line);
+            body.push('\n');
+            if line.trim_start().starts_with("-----END ") {
+                let blocks = pem::decode_all(&body)
+                    .ok_or(P11KitError::Syntax(lineno, "bad PEM block"))?;
+                sections.last_mut().expect("PEM only starts inside a section")
+                    .blocks.extend(blocks);
+            } else {
+                pem_text = Some(body);
+            }
+            continue;
+        }
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        if line == HEADER {
+            sections.push(Section { line: lineno, ..Section::default() });
+            continue;
+        }
+        let section = sections.last_mut()
+            .ok_or(P11KitError::Syntax(lineno, "expected [p11-kit-object-v1]"))?;
+        if line.starts_with("-----BEGIN ") {
+            pem_text = Some(format!("{}\n", line));
+            continue;
+        }
+        let colon = line.find(':').ok_or(P11KitError::Syntax(lineno, "expected `key: value`"))?;
+        let (key, value) = (line[..colon].trim(), line[colon + 1..].trim());
+        let value = if value.starts_with('"') {
+            Value::Bytes(unquote(value).ok_or(P11KitError::Syntax(lineno, "bad quoted value"))?)
+        } else {
+            Value::Word(value.to_owned())
+        };
+        section.attrs.insert(key.to_owned(), value);
+    }
+    if pem_text.is_some() {
+        return Err(P11KitError::Syntax(text.lines().count(), "unterminated PEM block"));
+    }
+    Ok(sections)
+}
+
+// An extKeyUsage-shaped `x-certificate-extension` object.
+struct PurposeExt {
+    spki: Vec<u8>,
+    oid: Vec<u8>,
+    usages: Vec<Usage>,
+}
+
+fn extension_purposes(ext: &[u8]) -> der::Result<(Vec<u8>, Vec<Usage>)> {
+    let mut rd = der::parse_one(ext, tag::SEQUENCE)?.reader();
+    let ext_oid = rd.read(tag::OID)?.value.to_vec();
+    rd.read_optional(tag::BOOLEAN)?;
+    let value = rd.read(tag::OCTET_STRING)?.value;
+    rd.finish()?;
+    let mut list = der::parse_one(value, tag::SEQUENCE)?.reader();
+    let mut usages = Vec::new();
+    while !list.is_empty() {
+        let kp = list.read(tag::OID)?.value;
+        if kp == oid::ANY_EXT_KEY_USAGE {
+            usages.extend_from_slice(ALL_USAGES);
+        }
+        usages.extend(PURPOSES.iter().filter(|&&(_, p)| p == kp).map(|&(u, _)| u));
+    }
+    Ok((ext_oid, usages))
+}
+
+fn cert_objects(sec: &Section, der_cert: &[u8], exts: &[PurposeExt])
+                -> Result<Vec<Object>, Error> {
+    let mut cert = Certificate::from_der(sec.label(), der_cert.to_vec())?;
+    cert.mozilla_ca_policy = sec.word("nss-mozilla-ca-policy").map(|w| w == "true");
+    cert.server_distrust_after = sec.date("nss-server-distrust-after")?;
+    cert.email_distrust_after = sec.date("nss-email-distrust-after")?;
+
+    let spki = cert.x509()?.spki.to_vec();
+    let listed = |which: &[u8]| -> Option<Vec<Usage>> {
+        let mut found = None;
+        for ext in exts.iter().filter(|ext| ext.spki == spki && ext.oid == which) {
+            found.get_or_insert_with(Vec::new).extend_from_slice(&ext.usages);
+        }
+        found
+    };
+    let distrusted = sec.flag("x-distrusted");
+    // Without an extKeyUsage, an anchor is trusted for everything.
+    let trusted = if sec.flag("trusted") && !distrusted {
+        listed(oid::EXT_KEY_USAGE).unwrap_or_else(|| ALL_USAGES.to_vec())
+    } else {
+        Vec::new()
+    };
+    let rejected = listed(oid::OPENSSL_REJECT).unwrap_or_default();
+    let level = |usage| if distrusted || rejected.contains(&usage) {
+        TrustLevel::Distrust
+    } else if trusted.contains(&usage) {
+        TrustLevel::TrustedDelegator
+    } else {
+        TrustLevel::MustVerify
+    };
+
+    let mut objs = Vec::new();
+    if ALL_USAGES.iter().any(|&u| level(u) != TrustLevel::MustVerify) {
+        objs.push(Object::Trust(Trust {
+            label: cert.label.clone(),
+            issuer: cert.issuer.clone(),
+            serial: cert.serial.clone(),
+            tls_server_trust: level(Usage::TlsServer),
+            email_trust: level(Usage::Email),
+            code_signing_trust: level(Usage::CodeSigning),
+            md5: None,
+            sha1: None,
+        }));
+    }
+    objs.insert(0, Object::Certificate(cert));
+    Ok(objs)
+}
+
+/// Reads a p11-kit file back into `certdata.txt`-style objects: a
+/// `Certificate` for each certificate, and a `Trust` for each one
+/// that's trusted or distrusted for anything.  Objects of other classes
+/// are ignored.
+pub fn read_p11kit<R: BufRead>(mut src: R) -> Result<Vec<Object>, Error> {
+    let mut text = String::new();
+    src.read_to_string(&mut text)?;
+    let sections = parse_sections(&text)?;
+
+    let mut exts = Vec::new();
+    for sec in &sections {
+        if sec.word("class") == Some("x-certificate-extension") {
+            let spki = sec.block("PUBLIC KEY").or_else(|| sec.bytes("public-key"))
+                .ok_or(P11KitError::BadObject(sec.line, "extension without public key"))?;
+            let value = sec.bytes("value")
+                .ok_or(P11KitError::BadObject(sec.line, "extension without value"))?;
+            let (oid, usages) = extension_purposes(value)?;
+            exts.push(PurposeExt {
+                spki: spki.to_vec(),
+                oid,
+                usages,
+            });
+        }
+    }
+
+    let mut objs = Vec::new();
+    for sec in &sections {
+        let der_cert = sec.block("CERTIFICATE").or_else(|| sec.bytes("value"));
+        match (sec.word("class"), der_cert) {
+            (Some("certificate"), Some(der_cert)) | (None, Some(der_cert)) =>
+                objs.extend(cert_objects(sec, der_cert, &exts)?),
+            (Some("certificate"), None) => {
+                if !sec.flag("x-distrusted") {
+                    continue;
+                }
+                match (sec.bytes("issuer"), sec.bytes("serial-number")) {
+                    (Some(issuer), Some(serial)) => objs.push(Object::Trust(Trust {
+                        label: sec.label(),
+                        issuer: issuer.to_vec().into(),
+                        serial: serial.to_vec().into(),
+                        tls_server_trust: TrustLevel::Distrust,
+                        email_trust: TrustLevel::Distrust,
+                        code_signing_trust: TrustLevel::Distrust,
+                        md5: None,
+                        sha1: None,
+                    })),
+                    _ => return Err(P11KitError::BadObject(sec.line, "distrust without issuer \
+                                                                      and serial").into()),
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(objs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{read_p11kit, write_p11kit, quote, unquote};
+    use collect::CertData;
+    use fixture;
+    use reader::ObjectIter;
+    use structured::{Object, TrustLevel, Usage};
+    use Error;
+
+    #[test]
+    fn test_quoting() {
+        assert_eq!(quote(b"AC Ra\xc3\xadz \"1\""), "\"AC Ra%c3%adz %221%22\"");
+        assert_eq!(unquote("\"AC Ra%c3%ADz %221%22\"").unwrap(), b"AC Ra\xc3\xadz \"1\"");
+        assert_eq!(unquote("\"%3\""), None);
+        assert_eq!(unquote("no quotes"), None);
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let data = fixture();
+        let mut out = Vec::new();
+        write_p11kit(&mut out, &data).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("# Amazon Root CA 1\n[p11-kit-object-v1]\nclass: certificate\n"));
+        assert!(text.contains("nss-server-distrust-after: \"230201000000Z\"\n"));
+
+        let objs = read_p11kit(text.as_bytes()).unwrap();
+        let back = CertData::from_iter(objs.into_iter().map(Ok::<_, Error>)).unwrap();
+        assert_eq!(back.certs().len(), data.certs().len());
+        for (a, b) in data.certs().iter().zip(back.certs()) {
+            assert_eq!(a.cert, b.cert);
+            assert_eq!(a.label, b.label);
+            assert_eq!(a.server_distrust_after, b.server_distrust_after);
+            assert_eq!(a.email_distrust_after, b.email_distrust_after);
+            assert_eq!(a.mozilla_ca_policy, b.mozilla_ca_policy);
+        }
+        assert_eq!(back.trusts().len(), data.trusts().len());
+        for (a, b) in data.trusts().iter().zip(back.trusts()) {
+            assert_eq!((&a.label, &a.issuer, &a.serial), (&b.label, &b.issuer, &b.serial));
+            for &usage in &[Usage::TlsServer, Usage::Email, Usage::CodeSigning] {
+                assert_eq!(a.trust_level(usage), b.trust_level(usage), "{}", a.label);
+            }
+        }
+    }
+
+    #[test]
+    fn test_label_comments() {
+        // A label can't start a line of its own.
+        let evil = "Evil\n[p11-kit-object-v1]\nclass: private-key\r\u{85}";
+        let objs = ObjectIter::new(&include_bytes!("../tests/data/certdata.txt")[..])
+            .map(|obj| obj.map(|obj| match obj {
+                Object::Certificate(mut c) => { c.label = evil.to_owned(); Object::Certificate(c) }
+                Object::Trust(mut t) => { t.label = evil.to_owned(); Object::Trust(t) }
+            }));
+        let data = CertData::from_iter(objs).unwrap();
+        let mut out = Vec::new();
+        write_p11kit(&mut out, &data).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("# Evil\\n[p11-kit-object-v1]\\nclass: private-key\\r\\u{85}\n"));
+        assert!(!text.lines().any(|line| line.starts_with("class: private-key")));
+        let back = read_p11kit(text.as_bytes()).unwrap();
+        assert!(back.iter().all(|obj| match *obj {
+            Object::Certificate(ref c) => c.label == evil,
+            Object::Trust(ref t) => t.label == evil,
+        }));
+    }
+
+    #[test]
+    fn test_defaults() {
+        // An anchor without an extKeyUsage object is trusted for everything.
+        let data = fixture();
+        let cert = data.certs().iter().find(|c| c.label == "ISRG Root X1").unwrap();
+        let text = format!("[p11-kit-object-v1]\ntrusted: true\nlabel: \"X1\"\n{}",
+                           ::pem::encode("CERTIFICATE", &cert.cert));
+        let objs = read_p11kit(text.as_bytes()).unwrap();
+        let back = CertData::from_iter(objs.into_iter().map(Ok::<_, Error>)).unwrap();
+        let trust = back.trust_for_cert(&back.certs()[0]).unwrap();
+        assert_eq!(trust.trust_level(Usage::CodeSigning), TrustLevel::TrustedDelegator);
+
+        assert!(read_p11kit(&b"label: \"orphan\"\n"[..]).is_err());
+        assert!(read_p11kit(&b"[p11-kit-object-v1]\nbogus\n"[..]).is_err());
+    }
+}
@@ -0,0 +1,150 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Parsing an in-memory certdata.txt on several threads: the part after
+// `BEGINDATA` is cut into chunks at lines starting with `CKA_CLASS`
+// (where `RawObjectIter` would start a new object anyway), each chunk
+// is parsed as usual, and the results are put back in order.  Syntax
+// errors have offsets in the whole buffer, and the error returned is
+// the first one in the file, so the result is the same as collecting
+// an `ObjectIter`.
+
+use nom::IResult;
+use rayon::prelude::*;
+
+use collect::CertData;
+use reader::{AttrIter, ObjectIter, Offset, RawObjectIter};
+use structured::Object;
+use syntax::begindata;
+use Error;
+
+// Chunks are at least this big, so small files aren't split for nothing.
+const MIN_CHUNK: usize = 64 * 1024;
+
+// Objects start with their `CKA_CLASS` attribute.
+fn starts_object(line: &[u8]) -> bool {
+    const CLASS: &[u8] = b"CKA_CLASS";
+    line.starts_with(CLASS) && matches!(line.get(CLASS.len()), Some(&b' ') | Some(&b'\t'))
+}
+
+// Where the chunks start: `start`, then the first object boundary at
+// least `chunk_len` bytes after the previous chunk start.
+fn chunk_starts(buf: &[u8], start: usize, chunk_len: usize) -> Vec<usize> {
+    let mut starts = vec![start];
+    let mut pos = start;
+    while let Some(nl) = buf[pos..].iter().position(|&b| b == b'\n') {
+        pos += nl + 1;
+        let last = starts[starts.len() - 1];
+        if pos - last >= chunk_len && starts_object(&buf[pos..]) {
+            starts.push(pos);
+        }
+    }
+    starts
+}
+
+fn parse_chunk(chunk: &[u8], offset: usize) -> Result<Vec<Object>, Error> {
+    let attrs = AttrIter::resume(chunk, offset as Offset);
+    ObjectIter::from(RawObjectIter::from(attrs)).collect()
+}
+
+fn parse_objects_with(buf: &[u8], chunk_len: usize) -> Result<Vec<Object>, Error> {
+    let start = match begindata(buf) {
+        IResult::Done(rest, ()) => buf.len() - rest.len(),
+        // Let the usual parser say what's wrong, if anything.
+        _ => return ObjectIter::new(buf).collect(),
+    };
+    let mut starts = chunk_starts(buf, start, chunk_len);
+    starts.push(buf.len());
+    let chunks: Vec<_> = starts.par_windows(2)
+        .map(|w| parse_chunk(&buf[w[0]..w[1]], w[0]))
+        .collect();
+    let mut objs = Vec::new();
+    for chunk in chunks {
+        objs.extend(chunk?);
+    }
+    Ok(objs)
+}
+
+/// The same as collecting `ObjectIter::new(buf)`, but faster for big
+/// inputs on multicore machines.
+pub fn parse_objects(buf: &[u8]) -> Result<Vec<Object>, Error> {
+    let chunk_len = (buf.len() / (rayon::current_num_threads() * 4)).max(MIN_CHUNK);
+    parse_objects_with(buf, chunk_len)
+}
+
+/// `CertData::from_iter` over `parse_objects`.
+pub fn parse_cert_data(buf: &[u8]) -> Result<CertData, Error> {
+    CertData::from_iter(parse_objects(buf)?.into_iter().map(Ok::<_, Error>))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{chunk_starts, parse_cert_data, parse_objects_with};
+    use reader::ObjectIter;
+    use structured::Object;
+    use Error;
+
+    const FIXTURE: &[u8] = include_bytes!("../tests/data/certdata.txt");
+
+    fn labels(objs: &[Object]) -> Vec<&str> {
+        objs.iter().map(|obj| match *obj {
+            Object::Certificate(ref c) => &c.label[..],
+            Object::Trust(ref t) => &t.label[..],
+        }).collect()
+    }
+
+    #[test]
+    fn test_same_objects() {
+        let sequential: Vec<Object> = ObjectIter::new(FIXTURE).collect::<Result<_, _>>().unwrap();
+        let count = sequential.len();
+        for &chunk_len in &[1, 4096, 1 << 20] {
+            let parallel = parse_objects_with(FIXTURE, chunk_len).unwrap();
+            assert_eq!(labels(&parallel), labels(&sequential));
+        }
+        assert!(chunk_starts(FIXTURE, 0, 4096).len() > 100);
+        assert_eq!(parse_cert_data(FIXTURE).unwrap().certs().len(), 143);
+
+        // An attribute that only looks like `CKA_CLASS` isn't a boundary.
+        let text = String::from_utf8(FIXTURE.to_vec()).unwrap()
+            .replace("CKA_TOKEN CK_BBOOL CK_TRUE\n",
+                     "CKA_TOKEN CK_BBOOL CK_TRUE\nCKA_CLASS_FOO CK_BBOOL CK_TRUE\n");
+        let sequential: Vec<Object> = ObjectIter::new(text.as_bytes())
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(sequential.len(), count);
+        for &chunk_len in &[1, 4096] {
+            assert_eq!(parse_objects_with(text.as_bytes(), chunk_len).unwrap(), sequential);
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        // A syntax error late in the file, and a structural one before it.
+        let text = String::from_utf8(FIXTURE.to_vec()).unwrap();
+        let late = text.rfind("CKA_TRUST_CODE_SIGNING CK_TRUST").unwrap();
+        let broken = format!("{}CKA_TRUST_CODE_SIGNING ~{}", &text[..late], &text[late + 22..]);
+        let sequential = ObjectIter::new(broken.as_bytes()).collect::<Result<Vec<_>, _>>();
+        let expected = match sequential {
+            Err(Error::ParseError(err)) => err.byte_offset,
+            other => panic!("{:?}", other.map(|v| v.len())),
+        };
+        assert_eq!(expected, late as u64 + 23);
+        match parse_objects_with(broken.as_bytes(), 4096) {
+            Err(Error::ParseError(err)) => assert_eq!(err.byte_offset, expected),
+            other => panic!("{:?}", other.map(|v| v.len())),
+        }
+
+        let early = text.find("CKA_CERTIFICATE_TYPE CK_CERTIFICATE_TYPE CKC_X_509").unwrap();
+        let broken = format!("{}{}", &text[..early], &broken[early + 51..]);
+        match parse_objects_with(broken.as_bytes(), 4096) {
+            Err(Error::StructureError(_)) => (),
+            other => panic!("{:?}", other.map(|v| v.len())),
+        }
+
+        // No BEGINDATA.
+        match parse_objects_with(b"# nothing here\n", 4096) {
+            Ok(objs) => assert!(objs.is_empty()),
+            Err(err) => panic!("{:?}", err),
+        }
+    }
+}
@@ -0,0 +1,151 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Base64 and PEM armor (RFC 7468); small enough that it's not worth
+// another crate dependency.
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn base64_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [chunk[0], *chunk.get(1).unwrap_or(&0), *chunk.get(2).unwrap_or(&0)];
+        let n = u32::from(b[0]) << 16 | u32::from(b[1]) << 8 | u32::from(b[2]);
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+fn base64_value(c: u8) -> Option<u32> {
+    match c {
+        b'A'..=b'Z' => Some(u32::from(c - b'A')),
+        b'a'..=b'z' => Some(u32::from(c - b'a') + 26),
+        b'0'..=b'9' => Some(u32::from(c - b'0') + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// Decodes base64, ignoring whitespace; returns `None` on anything
+/// else unexpected, including bad padding.
+pub fn base64_decode(text: &str) -> Option<Vec<u8>> {
+    let chars: Vec<u8> = text.bytes().filter(|c| !c.is_ascii_whitespace()).collect();
+    if !chars.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut out = Vec::with_capacity(chars.len() / 4 * 3);
+    for (i, quad) in chars.chunks(4).enumerate() {
+        let last = i == chars.len() / 4 - 1;
+        let pad = quad.iter().rev().take_while(|&&c| c == b'=').count();
+        if pad > 2 || (pad > 0 && !last) {
+            return None;
+        }
+        let mut n = 0;
+        for &c in &quad[..4 - pad] {
+            n = n << 6 | base64_value(c)?;
+        }
+        n <<= 6 * pad;
+        let bytes = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        out.extend_from_slice(&bytes[..3 - pad]);
+    }
+    Some(out)
+}
+
+/// PEM armor with the usual 64-column lines.
+pub fn encode(label: &str, data: &[u8]) -> String {
+    let b64 = base64_encode(data);
+    let mut out = format!("-----BEGIN {}-----\n", label);
+    for line in b64.as_bytes().chunks(64) {
+        // The alphabet is all ASCII, so this can't split a character.
+        out.push_str(&String::from_utf8_lossy(line));
+        out.push('\n');
+    }
+    out.push_str(&format!("-----END {}-----\n", label));
+    out
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Block {
+    pub label: String,
+    pub data: Vec<u8>,
+}
+
+/// Finds all the PEM blocks in some text, skipping anything outside
+/// them (comments, `openssl x509 -text` output, etc.); returns `None`
+/// if a block is unterminated or its contents aren't valid base64.
+pub fn decode_all(text: &str) -> Option<Vec<Block>> {
+    let mut blocks = Vec::new();
+    let mut current: Option<(String, String)> = None;
+    for line in text.lines() {
+        let line = line.trim();
+        current = match current {
+            None => {
+                if line.starts_with("-----BEGIN ") && line.ends_with("-----") {
+                    Some((line[11..line.len() - 5].to_owned(), String::new()))
+                } else {
+                    None
+                }
+            }
+            Some((label, mut body)) => {
+                if line == format!("-----END {}-----", label) {
+                    blocks.push(Block {
+                        label,
+                        data: base64_decode(&body)?,
+                    });
+                    None
+                } else {
+                    body.push_str(line);
+                    Some((label, body))
+                }
+            }
+        }
+    }
+    if current.is_some() {
+        return None;
+    }
+    Some(blocks)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{base64_encode, base64_decode, encode, decode_all, Block};
+
+    #[test]
+    fn test_base64() {
+        let cases: &[(&[u8], &str)] = &[(b"", ""), (b"f", "Zg=="), (b"fo", "Zm8="),
+                                        (b"foo", "Zm9v"), (b"foob", "Zm9vYg=="),
+                                        (b"fooba", "Zm9vYmE="), (b"foobar", "Zm9vYmFy"),
+                                        (b"\xff\xfe\x00", "//4A")];
+        for &(raw, enc) in cases {
+            assert_eq!(base64_encode(raw), enc);
+            assert_eq!(base64_decode(enc).as_ref().map(|v| &v[..]), Some(raw));
+        }
+        assert_eq!(base64_decode("Zm9v\nYmFy\n"), Some(b"foobar".to_vec()));
+        assert_eq!(base64_decode("Zm9"), None);
+        assert_eq!(base64_decode("Zg==Zm9v"), None);
+        assert_eq!(base64_decode("Z==="), None);
+        assert_eq!(base64_decode("Zm9*"), None);
+    }
+
+    #[test]
+    fn test_armor() {
+        let data: Vec<u8> = (0..100).collect();
+        let text = encode("CERTIFICATE", &data);
+        assert!(text.starts_with("-----BEGIN CERTIFICATE-----\n"));
+        assert!(text.ends_with("\n-----END CERTIFICATE-----\n"));
+        assert_eq!(text.lines().nth(1).unwrap().len(), 64);
+        let commented = format!("# Label: \"Thing\"\n{}\n{}", text, text);
+        assert_eq!(decode_all(&commented),
+                   Some(vec![Block { label: "CERTIFICATE".to_owned(), data: data.clone() },
+                             Block { label: "CERTIFICATE".to_owned(), data: data.clone() }]));
+        assert_eq!(decode_all(&text[..text.len() - 10]), None);
+    }
+}
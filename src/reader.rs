@@ -24,20 +24,24 @@ pub struct ParseError {
     pub what: ErrorKind,
 }
 
-fn nom_error_info<'a, E: Clone>(err: &'a Err<&'a [u8], E>) -> (&'a [u8], ErrorKind<E>) {
+// Errors without a position are blamed on the start of `buf`.
+fn nom_error_info<'a, E: Clone>(buf: &'a [u8], err: &'a Err<&'a [u8], E>)
+                                -> (&'a [u8], ErrorKind<E>) {
     match *err {
-        Err::Code(_) => unimplemented!(),
-        Err::Node(_, ref err_box) => nom_error_info(err_box),
+        Err::Code(ref ek) => (buf, ek.clone()),
+        Err::Node(_, ref err_box) => nom_error_info(buf, err_box),
         Err::Position(ref ek, loc) => (loc, ek.clone()),
-        Err::NodePosition(_, _, ref err_box) => nom_error_info(err_box),
+        Err::NodePosition(_, _, ref err_box) => nom_error_info(buf, err_box),
     }
 }
 
 // The code duplication makes me sad, and this ought to be properly tested....
-fn bufferize<I, O, E, F>(mut src: I, mut f: F) -> Result<Option<(usize, O)>, E>
+fn bufferize<I, O, E, F, B>(mut src: I, mut f: F, backtracked: B)
+                            -> Result<Option<(usize, O)>, E>
     where I: BufRead,
           E: From<io::Error>,
-          F: for<'a> FnMut(&'a [u8]) -> Result<Option<(usize, O)>, E>
+          F: for<'a> FnMut(&'a [u8]) -> Result<Option<(usize, O)>, E>,
+          B: FnOnce(usize, usize) -> E
 {
     let mut big_buf = Vec::new();
     // Non-lexical lifetimes would make this code cleaner.
@@ -69,6 +73,11 @@ fn bufferize<I, O, E, F>(mut src: I, mut f: F) -> Result<Option<(usize, O)>, E>
             buf
         });
         match try!(f(&big_buf)) {
+            // Succeeding on less than a previous attempt rejected would
+            // mean un-reading input, so give up instead.
+            Some((used, _)) if used < old_len => {
+                return Err(backtracked(used, big_buf.len() - used));
+            },
             Some((used, res)) => {
                 src.consume(used - old_len);
                 return Ok(Some((used, res)));
@@ -96,12 +105,21 @@ fn apply_nom<I, O, P>(mut parser: P, off: Offset, src: I)
 {
     if let Some((used, res)) = try!(bufferize(src, |buf| {
         match parser(buf) {
+            // Matching nothing would leave the iterators stuck; this is
+            // what `many0!` calls it.
+            IResult::Done(rest, _) if rest.len() == buf.len() => {
+                Err(Error::ParseError(ParseError{
+                    byte_offset: off,
+                    buf_left: rest.len(),
+                    what: ErrorKind::Many0,
+                }))
+            }
             IResult::Done(rest, res) => {
                 let (used, _) = slice_to_offsets(buf, rest);
                 Ok(Some((used, res)))
             }
             IResult::Error(err) => {
-                let (rest, what) = nom_error_info(&err);
+                let (rest, what) = nom_error_info(buf, &err);
                 // This is a hack to try to recognize spurious errors.
                 // Might cause false negatives.
                 if !rest.contains(&b'\n') {
@@ -119,6 +137,12 @@ fn apply_nom<I, O, P>(mut parser: P, off: Offset, src: I)
                 Ok(None)
             }
         }
+    }, |used, left| {
+        Error::ParseError(ParseError{
+            byte_offset: off + (used as Offset),
+            buf_left: left,
+            what: ErrorKind::Alt,
+        })
     })) {
         Ok(Some((off + (used as Offset), res)))
     } else {
@@ -140,6 +164,25 @@ impl<I: BufRead> AttrIter<I> {
             had_error: false
         }
     }
+
+    // For input that starts at `offset` in a file, past `BEGINDATA`;
+    // errors are reported at offsets in the whole file.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn resume(src: I, offset: Offset) -> Self {
+        assert!(offset != 0, "offset 0 would mean BEGINDATA is still to come");
+        AttrIter {
+            src,
+            offset,
+            had_error: false
+        }
+    }
+
+    /// How much of the input has been parsed: the byte offset just past
+    /// the last attribute returned (and past `BEGINDATA`, once that's
+    /// been seen).
+    pub fn offset(&self) -> Offset {
+        self.offset
+    }
 }
 impl<I: BufRead> Iterator for AttrIter<I> {
     type Item = Result<Attr, Error>;
@@ -157,7 +200,6 @@ impl<I: BufRead> Iterator for AttrIter<I> {
                     return None;
                 }
                 Ok(Some((offset, ()))) => {
-                    assert!(offset != 0);
                     self.offset = offset;
                 }
             }
@@ -169,7 +211,6 @@ impl<I: BufRead> Iterator for AttrIter<I> {
             }
             Ok(None) => None,
             Ok(Some((offset, attr))) => {
-                assert!(offset > self.offset);
                 self.offset = offset;
                 Some(Ok(attr))
             }
@@ -230,6 +271,16 @@ impl<I: BufRead> Iterator for RawObjectIter<I> {
     }
 }
 
+impl<I: BufRead> From<AttrIter<I>> for RawObjectIter<I> {
+    fn from(inner: AttrIter<I>) -> Self {
+        RawObjectIter {
+            inner,
+            acc: HashMap::new(),
+            done: false,
+        }
+    }
+}
+
 // Does this really belong in this module (vs. structured)?  Does it matter?
 pub struct ObjectIter<I: BufRead> {
     inner: RawObjectIter<I>,
@@ -271,3 +322,87 @@ impl<I: BufRead> Iterator for ObjectIter<I> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_nom, nom_error_info, AttrIter, ObjectIter};
+    use collect::CertData;
+    use nom::{Err, ErrorKind, IResult, Needed};
+    use std::fs;
+    use std::io::BufReader;
+    use Error;
+
+    #[test]
+    fn test_error_info() {
+        let buf = &b"CKA_CLASS ~"[..];
+        let err: Err<&[u8]> = Err::Code(ErrorKind::Tag);
+        assert_eq!(nom_error_info(buf, &err), (buf, ErrorKind::Tag));
+        let err: Err<&[u8]> = Err::Node(ErrorKind::Alt,
+                                        Box::new(Err::Position(ErrorKind::Char, &buf[10..])));
+        assert_eq!(nom_error_info(buf, &err), (&buf[10..], ErrorKind::Char));
+    }
+
+    #[test]
+    fn test_no_progress() {
+        fn nothing(i: &[u8]) -> IResult<&[u8], ()> {
+            IResult::Done(i, ())
+        }
+        match apply_nom(nothing, 100, &b"CKA_CLASS\n"[..]) {
+            Err(Error::ParseError(err)) => {
+                assert_eq!(err.byte_offset, 100);
+                assert_eq!(err.what, ErrorKind::Many0);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_backtrack() {
+        // Wants all 8 bytes before deciding to take only 2 of them,
+        // which is too late when the reads are 4 bytes at a time.
+        fn fickle(i: &[u8]) -> IResult<&[u8], ()> {
+            if i.len() < 8 {
+                IResult::Incomplete(Needed::Unknown)
+            } else {
+                IResult::Done(&i[2..], ())
+            }
+        }
+        let src = BufReader::with_capacity(4, &b"CKA_CLASS\n"[..]);
+        match apply_nom(fickle, 100, src) {
+            Err(Error::ParseError(err)) => {
+                assert_eq!((err.byte_offset, err.buf_left), (102, 6));
+                assert_eq!(err.what, ErrorKind::Alt);
+            }
+            other => panic!("{:?}", other),
+        }
+    }
+
+    // The fuzz targets' corpora (see fuzz/): the first byte of each
+    // input is the read buffer size less one, and the rest is the text.
+    // None of them should panic, however the input is split up.
+    #[test]
+    fn test_fuzz_corpus() {
+        let mut inputs = 0;
+        for dir in fs::read_dir("fuzz/corpus").unwrap() {
+            for file in fs::read_dir(dir.unwrap().path()).unwrap() {
+                let data = fs::read(file.unwrap().path()).unwrap();
+                let (&size, text) = match data.split_first() {
+                    Some(split) => split,
+                    None => continue,
+                };
+                for &cap in &[size as usize + 1, 1, text.len().max(1)] {
+                    let attrs = AttrIter::new(BufReader::with_capacity(cap, text));
+                    for attr in attrs {
+                        if attr.is_err() {
+                            break;
+                        }
+                    }
+                    let objs = ObjectIter::new(BufReader::with_capacity(cap, text));
+                    let _ = CertData::from_iter(objs);
+                }
+                inputs += 1;
+            }
+        }
+        assert!(inputs > 100);
+    }
+}
@@ -0,0 +1,194 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// A versioned JSON description of a whole certdata.txt, for dashboards
+// and the like, as specified by `schema/report-v1.schema.json`.  Unlike
+// the serde output, the shape of this is a promise: no field is added,
+// renamed or removed without bumping `REPORT_VERSION`.
+
+use collect::CertData;
+use export::{sort_certs, Order};
+use json::Json;
+use name;
+use structured::{Certificate, Trust, TrustLevel, Usage};
+use x509::PublicKey;
+use Error;
+
+pub const REPORT_FORMAT: &str = "nss-certdata-report";
+pub const REPORT_VERSION: i64 = 1;
+/// The JSON Schema that reports conform to.
+pub const REPORT_SCHEMA: &str = include_str!("../schema/report-v1.schema.json");
+
+const USAGES: &[(&str, Usage)] = &[("tls_server", Usage::TlsServer),
+                                   ("email", Usage::Email),
+                                   ("code_signing", Usage::CodeSigning)];
+
+/// Upper-case hex without separators.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02X}", b)).collect()
+}
+
+fn trust_level(level: TrustLevel) -> Json {
+    Json::str(match level {
+        TrustLevel::TrustedDelegator => "trusted",
+        TrustLevel::MustVerify => "must_verify",
+        TrustLevel::Distrust => "distrusted",
+    })
+}
+
+fn trust_json(trust: Option<&Trust>) -> Json {
+    Json::Object(USAGES.iter().map(|&(key, usage)| {
+        (key, trust_level(trust.map_or(TrustLevel::MustVerify, |t| t.trust_level(usage))))
+    }).collect())
+}
+
+// The contents of a DER INTEGER, in hex.
+pub(crate) fn serial_hex(serial: &[u8]) -> Result<String, Error> {
+    Ok(hex(::der::parse_one(serial, ::der::tag::INTEGER)?.value))
+}
+
+fn root_json(cert: &Certificate, trust: Option<&Trust>) -> Result<Json, Error> {
+    let info = cert.x509()?;
+    let key = PublicKey::parse(info.spki)?;
+    let time = |t: Option<::time::Time>| Json::opt(t, |t| Json::str(t.to_string()));
+    Ok(Json::Object(vec![
+        ("label", Json::str(&cert.label[..])),
+        ("sha256", Json::str(hex(&cert.sha256_fingerprint()))),
+        ("sha1", Json::str(hex(&cert.sha1_fingerprint()))),
+        ("subject", Json::str(name::to_rfc4514(&cert.subject)?)),
+        ("issuer", Json::str(name::to_rfc4514(&cert.issuer)?)),
+        ("serial", Json::str(serial_hex(&cert.serial)?)),
+        ("not_before", Json::str(info.not_before.to_string())),
+        ("not_after", Json::str(info.not_after.to_string())),
+        ("key", Json::Object(vec![
+            ("algorithm", Json::str(key.algorithm())),
+            ("bits", Json::opt(key.bits(), |b| Json::Int(b.into()))),
+            ("curve", Json::opt(key.curve_name(), Json::str)),
+        ])),
+        ("trust", trust_json(trust)),
+        ("distrust_after", Json::Object(vec![
+            ("tls_server", time(cert.server_distrust_after)),
+            ("email", time(cert.email_distrust_after)),
+        ])),
+        ("mozilla_ca_policy", Json::opt(cert.mozilla_ca_policy, Json::Bool)),
+    ]))
+}
+
+fn distrust_json(trust: &Trust) -> Result<Json, Error> {
+    let digest = |d: &Option<::structured::Blob>| Json::opt(d.as_ref(), |d| Json::str(hex(d)));
+    Ok(Json::Object(vec![
+        ("label", Json::str(&trust.label[..])),
+        ("issuer", Json::str(name::to_rfc4514(&trust.issuer)?)),
+        ("serial", Json::str(serial_hex(&trust.serial)?)),
+        ("sha1", digest(&trust.sha1)),
+        ("md5", digest(&trust.md5)),
+        ("trust", trust_json(Some(trust))),
+    ]))
+}
+
+impl CertData {
+    /// The report as a `Json` tree, for embedding in something bigger.
+    pub fn json_report(&self) -> Result<Json, Error> {
+        let mut roots = Vec::new();
+        for cert in sort_certs(self.certs().iter().collect(), Order::Label) {
+            roots.push(root_json(cert, self.trust_for_cert(cert))?);
+        }
+        let mut distrusts = Vec::new();
+        let mut orphans: Vec<_> = self.trusts().iter()
+            .filter(|t| !self.certs().iter().any(|c| c.issuer == t.issuer && c.serial == t.serial))
+            .filter(|t| USAGES.iter().any(|&(_, u)| t.trust_level(u) == TrustLevel::Distrust))
+            .collect();
+        orphans.sort_by(|a, b| (&a.label, &a.issuer, &a.serial).cmp(&(&b.label, &b.issuer, &b.serial)));
+        for trust in orphans {
+            distrusts.push(distrust_json(trust)?);
+        }
+        Ok(Json::Object(vec![
+            ("format", Json::str(REPORT_FORMAT)),
+            ("version", Json::Int(REPORT_VERSION)),
+            ("roots", Json::Array(roots)),
+            ("distrusts", Json::Array(distrusts)),
+        ]))
+    }
+
+    /// The report as pretty-printed JSON.
+    pub fn to_json_report(&self) -> Result<String, Error> {
+        Ok(self.json_report()?.to_pretty())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate jsonschema;
+    extern crate serde_json;
+
+    use self::serde_json::Value;
+    use super::REPORT_SCHEMA;
+    use collect::CertData;
+    use reader::ObjectIter;
+
+    fn report() -> Value {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        serde_json::from_str(&data.to_json_report().unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_schema() {
+        let schema: Value = serde_json::from_str(REPORT_SCHEMA).unwrap();
+        let validator = jsonschema::draft202012::new(&schema).unwrap();
+        let report = report();
+        validator.validate(&report).unwrap();
+
+        // And the schema does rule things out.
+        let mut bad = report.clone();
+        bad["roots"][0]["trust"]["email"] = Value::from("maybe");
+        assert!(!validator.is_valid(&bad));
+        let mut bad = report.clone();
+        bad["roots"][0].as_object_mut().unwrap().remove("key");
+        assert!(!validator.is_valid(&bad));
+        let mut bad = report.clone();
+        bad["distrusts"][0]["extra"] = Value::from(1);
+        assert!(!validator.is_valid(&bad));
+        let mut bad = report.clone();
+        bad["roots"][0]["sha1"] = Value::from("8a");
+        assert!(!validator.is_valid(&bad));
+        let mut bad = report.clone();
+        bad["roots"][0]["serial"] = Value::from("ABC");
+        assert!(!validator.is_valid(&bad));
+        let mut bad = report.clone();
+        bad["roots"][0]["not_after"] = Value::from("2030-12-31");
+        assert!(!validator.is_valid(&bad));
+        let mut bad = report;
+        bad["roots"][0]["distrust_after"]["email"] = Value::from("2030-12-31 09:37:37");
+        assert!(!validator.is_valid(&bad));
+    }
+
+    #[test]
+    fn test_contents() {
+        let report = report();
+        assert_eq!(report["version"], 1);
+        let roots = report["roots"].as_array().unwrap();
+        assert_eq!(roots.len(), 143);
+        let root = roots.iter().find(|r| r["label"] == "ACCVRAIZ1").unwrap();
+        assert_eq!(root["sha256"],
+                   "9A6EC012E1A7DA9DBE34194D478AD7C0DB1822FB071DF12981496ED104384113");
+        assert_eq!(root["subject"], "C=ES,O=ACCV,OU=PKIACCV,CN=ACCVRAIZ1");
+        assert_eq!(root["serial"], "5EC3B7A6437FA4E0");
+        assert_eq!(root["not_after"], "2030-12-31T09:37:37Z");
+        assert_eq!(root["key"]["algorithm"], "RSA");
+        assert_eq!(root["key"]["bits"], 4096);
+        assert_eq!(root["trust"]["tls_server"], "trusted");
+        assert_eq!(root["trust"]["code_signing"], "must_verify");
+
+        let tugra = roots.iter().find(|r| r["label"] == "E-Tugra Certification Authority").unwrap();
+        assert_eq!(tugra["distrust_after"]["tls_server"], "2023-02-01T00:00:00Z");
+
+        let distrusts = report["distrusts"].as_array().unwrap();
+        let labels: Vec<_> = distrusts.iter().map(|d| d["label"].as_str().unwrap()).collect();
+        assert_eq!(labels, vec!["Explicitly Distrust Example Fraudulent Server",
+                                "Explicitly Distrust Example Mis-issued Intermediate"]);
+        assert!(distrusts[0]["sha1"].is_null());
+        assert_eq!(distrusts[1]["trust"]["email"], "distrusted");
+    }
+}
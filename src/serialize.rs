@@ -0,0 +1,210 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Serde support, for the types that can't just derive it.  Binary data
+// is base64 in human-readable formats and bytes otherwise; times are
+// RFC 3339 strings or Unix seconds, likewise.
+
+use std::fmt;
+
+use serde::de::{self, Deserialize, Deserializer, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeStruct, Serializer};
+
+use collect::CertData;
+use pem::{base64_decode, base64_encode};
+use structured::{Blob, Certificate, Object, Trust};
+use time::Time;
+
+/// For `#[serde(with = "...")]` on `Vec<u8>` fields.
+pub mod bytes {
+    use super::BytesVisitor;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(bytes: &[u8], ser: S) -> Result<S::Ok, S::Error> {
+        if ser.is_human_readable() {
+            ser.serialize_str(&super::base64_encode(bytes))
+        } else {
+            ser.serialize_bytes(bytes)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(de: D) -> Result<Vec<u8>, D::Error> {
+        if de.is_human_readable() {
+            de.deserialize_str(BytesVisitor)
+        } else {
+            de.deserialize_byte_buf(BytesVisitor)
+        }
+    }
+}
+
+struct BytesVisitor;
+
+impl<'de> Visitor<'de> for BytesVisitor {
+    type Value = Vec<u8>;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("bytes or a base64 string")
+    }
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Vec<u8>, E> {
+        base64_decode(s).ok_or_else(|| E::invalid_value(de::Unexpected::Str(s), &self))
+    }
+    fn visit_bytes<E: de::Error>(self, b: &[u8]) -> Result<Vec<u8>, E> {
+        Ok(b.to_vec())
+    }
+    fn visit_byte_buf<E: de::Error>(self, b: Vec<u8>) -> Result<Vec<u8>, E> {
+        Ok(b)
+    }
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Vec<u8>, A::Error> {
+        let mut out = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(b) = seq.next_element()? {
+            out.push(b);
+        }
+        Ok(out)
+    }
+}
+
+impl Serialize for Blob {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        bytes::serialize(self, ser)
+    }
+}
+
+impl<'de> Deserialize<'de> for Blob {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Blob, D::Error> {
+        bytes::deserialize(de).map(Blob::from)
+    }
+}
+
+impl Serialize for Time {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        if ser.is_human_readable() {
+            ser.collect_str(self)
+        } else {
+            ser.serialize_i64(self.unix())
+        }
+    }
+}
+
+struct TimeVisitor;
+
+impl<'de> Visitor<'de> for TimeVisitor {
+    type Value = Time;
+
+    fn expecting(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str("an RFC 3339 UTC time or Unix seconds")
+    }
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Time, E> {
+        s.parse().map_err(|_| E::invalid_value(de::Unexpected::Str(s), &self))
+    }
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Time, E> {
+        Ok(Time::from_unix(n))
+    }
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Time, E> {
+        if n > i64::MAX as u64 {
+            return Err(E::invalid_value(de::Unexpected::Unsigned(n), &self));
+        }
+        Ok(Time::from_unix(n as i64))
+    }
+}
+
+impl<'de> Deserialize<'de> for Time {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<Time, D::Error> {
+        if de.is_human_readable() {
+            de.deserialize_any(TimeVisitor)
+        } else {
+            de.deserialize_i64(TimeVisitor)
+        }
+    }
+}
+
+impl Serialize for CertData {
+    fn serialize<S: Serializer>(&self, ser: S) -> Result<S::Ok, S::Error> {
+        let mut st = ser.serialize_struct("CertData", 2)?;
+        st.serialize_field("certs", self.certs())?;
+        st.serialize_field("trusts", self.trusts())?;
+        st.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename = "CertData")]
+struct CertDataParts {
+    certs: Vec<Certificate>,
+    trusts: Vec<Trust>,
+}
+
+impl<'de> Deserialize<'de> for CertData {
+    fn deserialize<D: Deserializer<'de>>(de: D) -> Result<CertData, D::Error> {
+        // Going through `from_iter` puts things back in the order the
+        // lookups depend on, whatever order they were serialized in.
+        let parts = CertDataParts::deserialize(de)?;
+        let objs = parts.certs.into_iter().map(Object::Certificate)
+            .chain(parts.trusts.into_iter().map(Object::Trust))
+            .map(Ok::<_, D::Error>);
+        CertData::from_iter(objs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate serde_json;
+    extern crate serde_test;
+
+    use self::serde_test::{assert_tokens, Configure, Token};
+    use collect::CertData;
+    use reader::ObjectIter;
+    use structured::{Blob, TrustLevel, Usage};
+    use syntax::Value;
+    use time::Time;
+
+    #[test]
+    fn test_blob() {
+        let blob = Blob::from(b"foobar".to_vec());
+        assert_tokens(&blob.clone().readable(), &[Token::Str("Zm9vYmFy")]);
+        assert_tokens(&blob.compact(), &[Token::Bytes(b"foobar")]);
+        assert!(serde_json::from_str::<Blob>("\"Zm9*\"").is_err());
+    }
+
+    #[test]
+    fn test_time() {
+        let t = Time::from_unix(1675209600);
+        assert_tokens(&t.readable(), &[Token::Str("2023-02-01T00:00:00Z")]);
+        assert_tokens(&t.compact(), &[Token::I64(1675209600)]);
+    }
+
+    #[test]
+    fn test_enums() {
+        assert_tokens(&TrustLevel::TrustedDelegator,
+                      &[Token::UnitVariant { name: "TrustLevel", variant: "TrustedDelegator" }]);
+        assert_tokens(&Usage::Email, &[Token::UnitVariant { name: "Usage", variant: "Email" }]);
+        assert_eq!(serde_json::to_string(&Value::Binary(vec![0, 1, 2])).unwrap(),
+                   r#"{"Binary":"AAEC"}"#);
+        assert_eq!(serde_json::to_string(&Value::Token("CK_BBOOL".to_owned(),
+                                                       "CK_TRUE".to_owned())).unwrap(),
+                   r#"{"Token":["CK_BBOOL","CK_TRUE"]}"#);
+    }
+
+    #[test]
+    fn test_cert_data() {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let json = serde_json::to_value(&data).unwrap();
+        let cert = &json["certs"][0];
+        assert_eq!(cert["label"], data.certs()[0].label.as_str());
+        assert!(cert["cert"].as_str().unwrap().starts_with("MII"));
+
+        let back: CertData = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&back).unwrap(), json);
+
+        // Trust lookups still work if the records come back in another order.
+        let mut shuffled = json.clone();
+        shuffled["certs"].as_array_mut().unwrap().reverse();
+        shuffled["trusts"].as_array_mut().unwrap().reverse();
+        let back: CertData = serde_json::from_value(shuffled).unwrap();
+        for &usage in &[Usage::TlsServer, Usage::Email, Usage::CodeSigning] {
+            assert_eq!(back.trusted_certs(usage).len(), data.trusted_certs(usage).len());
+            assert_eq!(back.distrusts(usage).len(), data.distrusts(usage).len());
+        }
+    }
+}
@@ -0,0 +1,331 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// A binary form of `CertData` that loads without parsing certdata.txt:
+// the records are read in place from the buffer (which can be mmapped
+// or `include_bytes!`ed) into a `CertDataRef` that borrows from it.
+//
+// Layout, with integers big-endian:
+//
+//   magic      b"NSSCDSNP"
+//   version    u32, currently 1
+//   checksum   SHA-256 of everything after it
+//   counts     u32 certificates, u32 trust records
+//   certs      label, cert, issuer, serial, subject: bytes
+//              server and email distrust-after: optional time
+//              Mozilla CA policy: u8, 0 unset, 1 false, 2 true
+//   trusts     label, issuer, serial: bytes
+//              TLS server, email, code signing trust: u8 level
+//              md5, sha1: optional bytes
+//
+// where bytes are a u32 length and the contents, optional things are a
+// u8 0 or 1 followed by the thing if 1, times are i64 Unix times, and
+// trust levels are 0 for distrust, 1 for must-verify, 2 for trusted
+// delegator.  Trust records are in `CertData` order, so sorted by
+// issuer and serial, and the loader relies on that for lookups.
+
+use std::io::{self, Write};
+use std::str;
+
+use sha2::{Digest, Sha256};
+
+use borrowed::{CertDataRef, CertificateRef, TrustRef};
+use collect::CertData;
+use structured::TrustLevel;
+use time::Time;
+
+const MAGIC: &[u8; 8] = b"NSSCDSNP";
+pub const VERSION: u32 = 1;
+const HEADER_LEN: usize = 8 + 4 + 32;
+
+quick_error!{
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum SnapshotError {
+        BadMagic {
+            description("not a certdata snapshot")
+        }
+        UnsupportedVersion(version: u32) {
+            description("unsupported snapshot version")
+            display("unsupported snapshot version {}", version)
+        }
+        BadChecksum {
+            description("snapshot checksum mismatch")
+        }
+        Truncated {
+            description("truncated snapshot")
+        }
+        TrailingData {
+            description("trailing data after snapshot")
+        }
+        BadValue(what: &'static str) {
+            description("malformed snapshot")
+            display("malformed snapshot: bad {}", what)
+        }
+    }
+}
+
+pub type Result<T> = ::std::result::Result<T, SnapshotError>;
+
+/// Whether `buf` starts like a snapshot, as opposed to certdata.txt.
+pub fn is_snapshot(buf: &[u8]) -> bool {
+    buf.starts_with(MAGIC)
+}
+
+fn put_u32(out: &mut Vec<u8>, n: usize) {
+    out.extend_from_slice(&(n as u32).to_be_bytes());
+}
+
+fn put_bytes(out: &mut Vec<u8>, bytes: &[u8]) {
+    put_u32(out, bytes.len());
+    out.extend_from_slice(bytes);
+}
+
+fn put_time(out: &mut Vec<u8>, t: Option<Time>) {
+    match t {
+        None => out.push(0),
+        Some(t) => {
+            out.push(1);
+            out.extend_from_slice(&t.unix().to_be_bytes());
+        }
+    }
+}
+
+fn put_opt_bytes(out: &mut Vec<u8>, bytes: Option<&[u8]>) {
+    match bytes {
+        None => out.push(0),
+        Some(bytes) => {
+            out.push(1);
+            put_bytes(out, bytes);
+        }
+    }
+}
+
+fn level_code(level: TrustLevel) -> u8 {
+    match level {
+        TrustLevel::Distrust => 0,
+        TrustLevel::MustVerify => 1,
+        TrustLevel::TrustedDelegator => 2,
+    }
+}
+
+pub fn write_snapshot<W: Write>(out: &mut W, data: &CertData) -> io::Result<()> {
+    let mut body = Vec::new();
+    put_u32(&mut body, data.certs().len());
+    put_u32(&mut body, data.trusts().len());
+    for cert in data.certs() {
+        put_bytes(&mut body, cert.label.as_bytes());
+        put_bytes(&mut body, &cert.cert);
+        put_bytes(&mut body, &cert.issuer);
+        put_bytes(&mut body, &cert.serial);
+        put_bytes(&mut body, &cert.subject);
+        put_time(&mut body, cert.server_distrust_after);
+        put_time(&mut body, cert.email_distrust_after);
+        body.push(match cert.mozilla_ca_policy {
+            None => 0,
+            Some(false) => 1,
+            Some(true) => 2,
+        });
+    }
+    for trust in data.trusts() {
+        put_bytes(&mut body, trust.label.as_bytes());
+        put_bytes(&mut body, &trust.issuer);
+        put_bytes(&mut body, &trust.serial);
+        body.push(level_code(trust.tls_server_trust));
+        body.push(level_code(trust.email_trust));
+        body.push(level_code(trust.code_signing_trust));
+        put_opt_bytes(&mut body, trust.md5.as_ref().map(|b| &b[..]));
+        put_opt_bytes(&mut body, trust.sha1.as_ref().map(|b| &b[..]));
+    }
+
+    out.write_all(MAGIC)?;
+    out.write_all(&VERSION.to_be_bytes())?;
+    out.write_all(&Sha256::digest(&body))?;
+    out.write_all(&body)
+}
+
+struct Cursor<'a> {
+    buf: &'a [u8],
+}
+
+impl<'a> Cursor<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8]> {
+        if n > self.buf.len() {
+            return Err(SnapshotError::Truncated);
+        }
+        let (head, tail) = self.buf.split_at(n);
+        self.buf = tail;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<usize> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]) as usize)
+    }
+
+    fn bytes(&mut self) -> Result<&'a [u8]> {
+        let len = self.u32()?;
+        self.take(len)
+    }
+
+    fn str(&mut self) -> Result<&'a str> {
+        str::from_utf8(self.bytes()?).map_err(|_| SnapshotError::BadValue("label"))
+    }
+
+    fn flag(&mut self) -> Result<bool> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(SnapshotError::BadValue("flag")),
+        }
+    }
+
+    fn time(&mut self) -> Result<Option<Time>> {
+        if !self.flag()? {
+            return Ok(None);
+        }
+        let b = self.take(8)?;
+        let mut be = [0; 8];
+        be.copy_from_slice(b);
+        Ok(Some(Time::from_unix(i64::from_be_bytes(be))))
+    }
+
+    fn opt_bytes(&mut self) -> Result<Option<&'a [u8]>> {
+        if self.flag()? {
+            Ok(Some(self.bytes()?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn level(&mut self) -> Result<TrustLevel> {
+        match self.u8()? {
+            0 => Ok(TrustLevel::Distrust),
+            1 => Ok(TrustLevel::MustVerify),
+            2 => Ok(TrustLevel::TrustedDelegator),
+            _ => Err(SnapshotError::BadValue("trust level")),
+        }
+    }
+}
+
+/// Checks a snapshot and returns its records, borrowed from `buf`.
+pub fn load_snapshot(buf: &[u8]) -> Result<CertDataRef<'_>> {
+    if !is_snapshot(buf) {
+        return Err(SnapshotError::BadMagic);
+    }
+    if buf.len() < HEADER_LEN {
+        return Err(SnapshotError::Truncated);
+    }
+    let version = u32::from_be_bytes([buf[8], buf[9], buf[10], buf[11]]);
+    if version != VERSION {
+        return Err(SnapshotError::UnsupportedVersion(version));
+    }
+    let body = &buf[HEADER_LEN..];
+    if Sha256::digest(body)[..] != buf[12..HEADER_LEN] {
+        return Err(SnapshotError::BadChecksum);
+    }
+
+    let mut rd = Cursor { buf: body };
+    let (ncerts, ntrusts) = (rd.u32()?, rd.u32()?);
+    // Don't trust the counts for allocation; each record is at least
+    // this many bytes.
+    let mut certs = Vec::with_capacity(ncerts.min(body.len() / 23));
+    for _ in 0..ncerts {
+        certs.push(CertificateRef {
+            label: rd.str()?,
+            cert: rd.bytes()?,
+            issuer: rd.bytes()?,
+            serial: rd.bytes()?,
+            subject: rd.bytes()?,
+            server_distrust_after: rd.time()?,
+            email_distrust_after: rd.time()?,
+            mozilla_ca_policy: match rd.u8()? {
+                0 => None,
+                1 => Some(false),
+                2 => Some(true),
+                _ => return Err(SnapshotError::BadValue("Mozilla CA policy")),
+            },
+        });
+    }
+    let mut trusts = Vec::with_capacity(ntrusts.min(body.len() / 17));
+    for _ in 0..ntrusts {
+        trusts.push(TrustRef {
+            label: rd.str()?,
+            issuer: rd.bytes()?,
+            serial: rd.bytes()?,
+            tls_server_trust: rd.level()?,
+            email_trust: rd.level()?,
+            code_signing_trust: rd.level()?,
+            md5: rd.opt_bytes()?,
+            sha1: rd.opt_bytes()?,
+        });
+    }
+    if !rd.buf.is_empty() {
+        return Err(SnapshotError::TrailingData);
+    }
+    if !trusts.windows(2).all(|w| (w[0].issuer, w[0].serial) <= (w[1].issuer, w[1].serial)) {
+        return Err(SnapshotError::BadValue("trust order"));
+    }
+    Ok(CertDataRef::from_sorted(certs, trusts))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_snapshot, load_snapshot, write_snapshot, SnapshotError, HEADER_LEN};
+    use borrowed::{CertificateRef, TrustRef};
+    use collect::CertData;
+    use fixture;
+    use structured::Usage;
+
+    #[test]
+    fn test_round_trip() {
+        let data = fixture();
+        let mut buf = Vec::new();
+        write_snapshot(&mut buf, &data).unwrap();
+        assert!(is_snapshot(&buf));
+
+        let loaded = load_snapshot(&buf).unwrap();
+        // Borrowed straight from the buffer.
+        let range = buf.as_ptr_range();
+        assert!(range.contains(&loaded.certs()[0].cert.as_ptr()));
+
+        let again = loaded.to_cert_data();
+        fn refs(d: &CertData) -> (Vec<CertificateRef<'_>>, Vec<TrustRef<'_>>) {
+            (d.certs().iter().map(CertificateRef::from).collect(),
+             d.trusts().iter().map(TrustRef::from).collect())
+        }
+        let (certs, trusts) = refs(&data);
+        assert_eq!(loaded.certs(), &certs[..]);
+        assert_eq!(loaded.trusts(), &trusts[..]);
+        assert_eq!(refs(&again), (certs, trusts));
+
+        for cert in data.certs() {
+            assert_eq!(loaded.trust_for(&cert.issuer, &cert.serial).map(|t| t.label),
+                       data.trust_for_cert(cert).map(|t| &t.label[..]));
+        }
+        assert_eq!(loaded.trusted_certs(Usage::Email).len(),
+                   data.trusted_certs(Usage::Email).len());
+    }
+
+    #[test]
+    fn test_corrupt() {
+        let mut buf = Vec::new();
+        write_snapshot(&mut buf, &fixture()).unwrap();
+
+        assert_eq!(load_snapshot(b"CKA_CLASS").unwrap_err(), SnapshotError::BadMagic);
+        assert_eq!(load_snapshot(&buf[..20]).unwrap_err(), SnapshotError::Truncated);
+        let mut bad = buf.clone();
+        bad[11] = 2;
+        assert_eq!(load_snapshot(&bad).unwrap_err(), SnapshotError::UnsupportedVersion(2));
+        let mut bad = buf.clone();
+        bad[HEADER_LEN + 100] ^= 1;
+        assert_eq!(load_snapshot(&bad).unwrap_err(), SnapshotError::BadChecksum);
+        let mut bad = buf.clone();
+        bad.push(0);
+        assert_eq!(load_snapshot(&bad).unwrap_err(), SnapshotError::BadChecksum);
+    }
+}
@@ -6,10 +6,19 @@ use std::fmt;
 use std::ops::Deref;
 use std::result;
 
+use md5::Md5;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+
+use constraints::{self, NameConstraints};
+use der;
 use reader::RawObject;
 use syntax::Value;
+use time::Time;
+use x509::CertInfo;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Object {
     Trust(Trust),
     Certificate(Certificate),
@@ -29,6 +38,12 @@ impl Deref for Blob {
     }
 }
 
+impl From<Vec<u8>> for Blob {
+    fn from(v: Vec<u8>) -> Blob {
+        Blob(v)
+    }
+}
+
 impl fmt::Debug for Blob {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
         // This ignores the provided "alternate" flag; printing one
@@ -39,7 +54,8 @@ impl fmt::Debug for Blob {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Trust {
     // TODO: factor out these three fields, for list-of-distrusts use cases?
     pub label: String,
@@ -58,6 +74,7 @@ pub struct Trust {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum TrustLevel {
     Distrust,
     MustVerify,
@@ -76,6 +93,7 @@ impl TrustLevel {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Usage {
     TlsServer,
     Email,
@@ -92,44 +110,133 @@ impl Trust {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct Certificate {
     pub label: String,
     pub cert: Asn1,
     pub issuer: Asn1,
     pub serial: Asn1,
     pub subject: Asn1,
+    // These are `CK_BBOOL CK_FALSE` when not set (and absent in
+    // older files), which is the same thing as far as we're concerned.
+    pub server_distrust_after: Option<Time>,
+    pub email_distrust_after: Option<Time>,
+    // `None` in files from before this attribute was added.
+    pub mozilla_ca_policy: Option<bool>,
+}
+
+impl Certificate {
+    /// Builds a `Certificate` from just the DER, e.g. for roots that
+    /// came from somewhere other than `certdata.txt`.
+    pub fn from_der(label: String, cert: Vec<u8>) -> der::Result<Certificate> {
+        let (issuer, serial, subject) = {
+            let info = CertInfo::parse(&cert)?;
+            (info.issuer.to_vec(), info.serial.to_vec(), info.subject.to_vec())
+        };
+        Ok(Certificate {
+            label,
+            cert: Blob(cert),
+            issuer: Blob(issuer),
+            serial: Blob(serial),
+            subject: Blob(subject),
+            server_distrust_after: None,
+            email_distrust_after: None,
+            mozilla_ca_policy: None,
+        })
+    }
+
+    /// Certificates issued (per their notBefore) after this time
+    /// shouldn't be trusted by way of this one, for this usage.
+    pub fn distrust_after(&self, usage: Usage) -> Option<Time> {
+        match usage {
+            Usage::TlsServer => self.server_distrust_after,
+            Usage::Email => self.email_distrust_after,
+            Usage::CodeSigning => None,
+        }
+    }
+
+    pub fn sha1_fingerprint(&self) -> [u8; 20] {
+        Sha1::digest(&self.cert[..]).into()
+    }
+    pub fn sha256_fingerprint(&self) -> [u8; 32] {
+        Sha256::digest(&self.cert[..]).into()
+    }
+    pub fn md5_fingerprint(&self) -> [u8; 16] {
+        Md5::digest(&self.cert[..]).into()
+    }
+
+    pub fn x509<'a>(&'a self) -> der::Result<CertInfo<'a>> {
+        CertInfo::parse(&self.cert)
+    }
+
+    /// The certificate's own nameConstraints, if any.
+    pub fn name_constraints<'a>(&'a self) -> der::Result<Option<NameConstraints<'a>>> {
+        self.x509()?.name_constraints()
+    }
+
+    /// Both its own nameConstraints and any NSS applies to it by subject
+    /// (see `constraints::builtin`); a name must satisfy all of them.
+    pub fn all_name_constraints<'a>(&'a self) -> der::Result<Vec<NameConstraints<'a>>> {
+        Ok(self.name_constraints()?.into_iter().chain(constraints::builtin(&self.subject))
+           .collect())
+    }
+
+    /// Whether `host` (a DNS name or IP address) is within all of this
+    /// certificate's name constraints; true if it has none.
+    pub fn permits_host(&self, host: &str) -> der::Result<bool> {
+        Ok(self.all_name_constraints()?.iter().all(|nc| nc.permits_host(host)))
+    }
+
+    /// Whether `addr` is within all of this certificate's name
+    /// constraints; true if it has none.
+    pub fn permits_email(&self, addr: &str) -> der::Result<bool> {
+        Ok(self.all_name_constraints()?.iter().all(|nc| nc.permits_email(addr)))
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
-// TODO: will impl'ing Display make this print more usefully?
 pub struct TypeError {
     pub got: String,
     pub expected: &'static str,
     pub key: &'static str,
 }
 
+impl fmt::Display for TypeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} is {}, not {}", self.key, self.got, self.expected)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
-// TODO: will impl'ing Display make this print more usefully?
 pub struct ValueError {
     pub got: String,
     pub attr_type: &'static str,
     pub key: &'static str,
 }
 
+impl fmt::Display for ValueError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} has unexpected {} {}", self.key, self.attr_type, self.got)
+    }
+}
+
 quick_error!{
     #[derive(Debug, Clone, PartialEq, Eq)]
     pub enum StructureError {
         MissingKey(key: &'static str) {
             description("missing key")
+            display("missing {}", key)
             from()
         }
         TypeError(err: TypeError) {
             description("unexpected attribute type")
+            display("{}", err)
             from()
         }
         ValueError(err: ValueError) {
             description("unexpected attribute value")
+            display("{}", err)
             from()
         }
     }
@@ -187,6 +294,34 @@ fn take_tok<R, F>(obj: &mut RawObject, key: &'static str, exp_ty: &'static str,
     }
 }
 
+fn bbool_from_str(s: &str) -> Option<bool> {
+    match s {
+        "CK_TRUE" => Some(true),
+        "CK_FALSE" => Some(false),
+        _ => None,
+    }
+}
+
+fn take_date(obj: &mut RawObject, key: &'static str) -> Result<Option<Time>> {
+    match obj.remove(key) {
+        None => Ok(None),
+        Some(Value::Token(ref ty, ref val)) if ty == "CK_BBOOL" && val == "CK_FALSE" => Ok(None),
+        Some(Value::Binary(val)) => match Time::from_utc_time(&val) {
+            Some(t) => Ok(Some(t)),
+            None => Err(ValueError {
+                got: String::from_utf8_lossy(&val).into_owned(),
+                attr_type: "MULTILINE_OCTAL",
+                key: key,
+            }.into()),
+        },
+        Some(val) => Err(TypeError {
+            got: val.into_type(),
+            expected: "MULTILINE_OCTAL",
+            key: key,
+        }.into()),
+    }
+}
+
 fn optionalize<T>(r: Result<T>) -> Result<Option<T>> {
     match r {
         Ok(thing) => Ok(Some(thing)),
@@ -207,6 +342,10 @@ impl Certificate {
             issuer: try!(take_bin(obj, "CKA_ISSUER")),
             serial: try!(take_bin(obj, "CKA_SERIAL_NUMBER")),
             subject: try!(take_bin(obj, "CKA_SUBJECT")),
+            server_distrust_after: try!(take_date(obj, "CKA_NSS_SERVER_DISTRUST_AFTER")),
+            email_distrust_after: try!(take_date(obj, "CKA_NSS_EMAIL_DISTRUST_AFTER")),
+            mozilla_ca_policy: try!(optionalize(take_tok(obj, "CKA_NSS_MOZILLA_CA_POLICY",
+                                                         "CK_BBOOL", bbool_from_str))),
         })
     }
 }
@@ -2,7 +2,7 @@
  * License, v. 2.0. If a copy of the MPL was not distributed with this
  * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
 
-use nom::{space, not_line_ending, alphanumeric, ErrorKind};
+use nom::{space, not_line_ending, alphanumeric, ErrorKind, IResult, Needed};
 
 named!(comment<()>,
        chain!(tag!("#") ~
@@ -49,9 +49,47 @@ named!(quoted_string<String>,
                            String::from_utf8),
                   tag!("\"")));
 
-named!(multiline_octal<Vec<u8> >,
+// The lines of octal escapes after `MULTILINE_OCTAL`, up to the `END`,
+// decoded with the combinators alone.  Public, like `multiline_octal`,
+// so that benches/parse.rs can compare the two.
+named!(pub slow_multiline_octal<Vec<u8> >,
        many0!(preceded!(leading_junk, octal_esc)));
 
+// `\ooo` at the start of `esc`, decoded.
+fn octal_byte(esc: &[u8]) -> Option<u8> {
+    match *esc {
+        [b'\\', a @ b'0'..=b'3', b @ b'0'..=b'7', c @ b'0'..=b'7', ..] =>
+            Some((a - b'0') << 6 | (b - b'0') << 3 | (c - b'0')),
+        _ => None,
+    }
+}
+
+// The same as `slow_multiline_octal`, but the octal values are most of
+// certdata.txt, so the usual layout (escapes, with only a newline
+// between lines) is decoded directly and the combinators only see what
+// comes after.
+pub fn multiline_octal(input: &[u8]) -> IResult<&[u8], Vec<u8>> {
+    let mut bits = Vec::new();
+    let mut rest = input;
+    loop {
+        let esc = if rest.first() == Some(&b'\n') { &rest[1..] } else { rest };
+        match octal_byte(esc) {
+            Some(b) => bits.push(b),
+            None => break,
+        }
+        rest = &esc[4..];
+    }
+    let consumed = input.len() - rest.len();
+    match slow_multiline_octal(rest) {
+        IResult::Done(rest, more) => {
+            bits.extend(more);
+            IResult::Done(rest, bits)
+        }
+        IResult::Incomplete(Needed::Size(n)) => IResult::Incomplete(Needed::Size(consumed + n)),
+        other => other,
+    }
+}
+
 named!(type_and_value<Value>,
        alt!(preceded!(tag!("MULTILINE_OCTAL"),
                       error!(ErrorKind::Alt,
@@ -95,10 +133,12 @@ pub type Type = Token;
 pub type Attr = (Token, Value);
 
 #[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum Value {
     Token(Type, Token),
     String(String),
-    Binary(Vec<u8>), // Type is always MULTILINE_OCTAL
+    Binary(#[cfg_attr(feature = "serde", serde(with = "::serialize::bytes"))]
+           Vec<u8>), // Type is always MULTILINE_OCTAL
 }
 
 impl Value {
@@ -120,7 +160,8 @@ impl Value {
 #[cfg(test)]
 mod tests {
     use super::{comment, endl, token, quad_digit, octal_digit, hex_digit, octal_esc, hex_esc,
-                quoted_string, multiline_octal, type_and_value, attribute, Value};
+                quoted_string, multiline_octal, slow_multiline_octal, type_and_value, attribute,
+                Value};
     use nom::IResult::*;
     use nom::{Needed, ErrorKind, Err};
 
@@ -276,6 +317,18 @@ mod tests {
         assert_eq!(multiline_octal(b"\\101\\033\n   END"), Done(&b"\n   END"[..], vec![65, 27]));
     }
 
+    #[test]
+    fn test_multiline_octal_fast_path() {
+        // Every prefix, so every place the fast path can stop.
+        let text = b"\\101\\033\n\\377\\000\r\n\\012 \\001\n\n\\002 # x\n\t\\003\n\\456\nEND\n";
+        for end in 0..text.len() + 1 {
+            assert_eq!(multiline_octal(&text[..end]), slow_multiline_octal(&text[..end]),
+                       "{:?}", String::from_utf8_lossy(&text[..end]));
+        }
+        assert_eq!(multiline_octal(text),
+                   Done(&b"\n\\456\nEND\n"[..], vec![65, 27, 255, 0, 10, 1, 2, 3]));
+    }
+
     #[test]
     fn test_token_value() {
         assert_eq!(type_and_value(b"CK_BBOOL CK_TRUE\n"),
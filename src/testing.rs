@@ -0,0 +1,497 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Random but well-formed certificate and trust objects, and
+// certdata.txt text for them, for testing the layers above the syntax.
+// Everything comes from the seed, so a failure can be reproduced from
+// that alone.
+//
+// The certificates are minimal: v3 with no extensions and a garbage
+// signature, but `CertInfo::parse` accepts them, and the issuer, serial
+// number and subject in the object are the ones in the DER.  Names come
+// from a small set, so that certificates share issuers and subjects.
+
+use std::collections::HashSet;
+
+use md5::Md5;
+use sha1::Sha1;
+use sha2::Digest;
+
+use der::{self, tag};
+use format::write_attr;
+use structured::{Certificate, Object, Trust, TrustLevel};
+use syntax::{Attr, Value};
+use time::Time;
+use x509::oid;
+
+const COMMON_NAME: &[u8] = b"\x55\x04\x03";
+const ORGANIZATION: &[u8] = b"\x55\x04\x0a";
+
+fn rdn(attr_type: &[u8], value: &str) -> Vec<u8> {
+    let atv = [der::encode(tag::OID, attr_type),
+               der::encode(tag::UTF8_STRING, value.as_bytes())].concat();
+    der::encode(tag::SET, &der::encode(tag::SEQUENCE, &atv))
+}
+
+fn utc_time(t: Time) -> Vec<u8> {
+    der::encode(tag::UTC_TIME, t.to_utc_time().unwrap().as_bytes())
+}
+
+fn cert_der(issuer: &[u8], serial: &[u8], subject: &[u8], not_before: Time, not_after: Time,
+            key: &[u8], signature: &[u8]) -> Vec<u8> {
+    let alg = der::encode(tag::SEQUENCE, &der::encode(tag::OID, oid::ED25519));
+    let validity = [utc_time(not_before), utc_time(not_after)].concat();
+    let spki = [alg.clone(), der::encode(tag::BIT_STRING, &[&[0], key].concat())].concat();
+    let tbs = [der::encode(tag::context(0), &der::encode(tag::INTEGER, &[2])),
+               serial.to_vec(),
+               alg.clone(),
+               issuer.to_vec(),
+               der::encode(tag::SEQUENCE, &validity),
+               subject.to_vec(),
+               der::encode(tag::SEQUENCE, &spki)].concat();
+    let cert = [der::encode(tag::SEQUENCE, &tbs),
+                alg,
+                der::encode(tag::BIT_STRING, &[&[0], signature].concat())].concat();
+    der::encode(tag::SEQUENCE, &cert)
+}
+
+fn token(key: &str, attr_type: &str, value: &str) -> Attr {
+    (key.to_owned(), Value::Token(attr_type.to_owned(), value.to_owned()))
+}
+
+fn bbool(key: &str, value: bool) -> Attr {
+    token(key, "CK_BBOOL", if value { "CK_TRUE" } else { "CK_FALSE" })
+}
+
+fn string(key: &str, value: &str) -> Attr {
+    (key.to_owned(), Value::String(value.to_owned()))
+}
+
+fn binary(key: &str, value: &[u8]) -> Attr {
+    (key.to_owned(), Value::Binary(value.to_vec()))
+}
+
+fn level_token(level: TrustLevel) -> &'static str {
+    match level {
+        TrustLevel::Distrust => "CKT_NSS_NOT_TRUSTED",
+        TrustLevel::MustVerify => "CKT_NSS_MUST_VERIFY_TRUST",
+        TrustLevel::TrustedDelegator => "CKT_NSS_TRUSTED_DELEGATOR",
+    }
+}
+
+// The attributes every object in certdata.txt starts with.
+fn common_attrs(class: &str, label: &str) -> Vec<Attr> {
+    vec![token("CKA_CLASS", "CK_OBJECT_CLASS", class),
+         bbool("CKA_TOKEN", true),
+         bbool("CKA_PRIVATE", false),
+         bbool("CKA_MODIFIABLE", false),
+         string("CKA_LABEL", label)]
+}
+
+pub struct Generator {
+    state: u64,
+    // Issuer and serial number pairs handed out so far.
+    used: HashSet<(Vec<u8>, Vec<u8>)>,
+}
+
+impl Generator {
+    pub fn new(seed: u64) -> Generator {
+        Generator {
+            state: seed,
+            used: HashSet::new(),
+        }
+    }
+
+    // SplitMix64.
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// A number less than `n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+
+    fn chance(&mut self, percent: u64) -> bool {
+        self.below(100) < percent
+    }
+
+    fn bytes(&mut self, len: usize) -> Vec<u8> {
+        (0..len).map(|_| self.next() as u8).collect()
+    }
+
+    fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+
+    /// A time to the second, in the range certdata.txt can express.
+    pub fn time(&mut self) -> Time {
+        let start = Time::from_ymd_hms(1950, 1, 1, 0, 0, 0).unwrap().unix();
+        let end = Time::from_ymd_hms(2050, 1, 1, 0, 0, 0).unwrap().unix();
+        Time::from_unix(start + self.below((end - start) as u64) as i64)
+    }
+
+    fn label(&mut self) -> String {
+        const PIECES: &[&str] = &["Root", "CA", " ", "2", "é", "Főtanúsítvány", "Ω", "\"",
+                                  "\\", "#", "\t"];
+        let len = self.below(6);
+        (0..len).map(|_| PIECES[self.below(PIECES.len() as u64) as usize]).collect()
+    }
+
+    fn name(&mut self) -> Vec<u8> {
+        let mut rdns = Vec::new();
+        if self.chance(50) {
+            rdns.extend(rdn(ORGANIZATION, "Example"));
+        }
+        rdns.extend(rdn(COMMON_NAME, &format!("Test Root {}", self.below(6))));
+        der::encode(tag::SEQUENCE, &rdns)
+    }
+
+    fn level(&mut self) -> TrustLevel {
+        match self.below(3) {
+            0 => TrustLevel::Distrust,
+            1 => TrustLevel::MustVerify,
+            _ => TrustLevel::TrustedDelegator,
+        }
+    }
+
+    /// A certificate with an issuer and serial number that no earlier
+    /// one from this generator has.
+    pub fn certificate(&mut self) -> Certificate {
+        let issuer = self.name();
+        let subject = if self.chance(70) { issuer.clone() } else { self.name() };
+        let serial = loop {
+            let len = 1 + self.below(16) as usize;
+            let mut value = self.bytes(len);
+            // Positive, and minimally encoded.
+            value[0] = 1 + value[0] % 0x7f;
+            let serial = der::encode(tag::INTEGER, &value);
+            if self.used.insert((issuer.clone(), serial.clone())) {
+                break serial;
+            }
+        };
+        let (a, b) = (self.time(), self.time());
+        let (not_before, not_after) = if a <= b { (a, b) } else { (b, a) };
+        let (key, signature) = (self.bytes(32), self.bytes(64));
+        let cert = cert_der(&issuer, &serial, &subject, not_before, not_after, &key, &signature);
+        let mut distrust_after = || if self.chance(30) { Some(self.time()) } else { None };
+        let (server_distrust_after, email_distrust_after) = (distrust_after(), distrust_after());
+        Certificate {
+            label: self.label(),
+            cert: cert.into(),
+            issuer: issuer.into(),
+            serial: serial.into(),
+            subject: subject.into(),
+            server_distrust_after,
+            email_distrust_after,
+            mozilla_ca_policy: match self.below(3) {
+                0 => None,
+                1 => Some(false),
+                _ => Some(true),
+            },
+        }
+    }
+
+    /// A trust object for `cert`, with random trust levels.
+    pub fn trust_for(&mut self, cert: &Certificate) -> Trust {
+        let md5 = Md5::digest(&cert.cert[..]).to_vec();
+        let sha1 = Sha1::digest(&cert.cert[..]).to_vec();
+        Trust {
+            label: cert.label.clone(),
+            issuer: cert.issuer.clone(),
+            serial: cert.serial.clone(),
+            tls_server_trust: self.level(),
+            email_trust: self.level(),
+            code_signing_trust: self.level(),
+            md5: if self.chance(80) { Some(md5.into()) } else { None },
+            sha1: if self.chance(80) { Some(sha1.into()) } else { None },
+        }
+    }
+
+    /// A trust object distrusting `cert` for everything.
+    pub fn distrust(&mut self, cert: &Certificate) -> Trust {
+        Trust {
+            tls_server_trust: TrustLevel::Distrust,
+            email_trust: TrustLevel::Distrust,
+            code_signing_trust: TrustLevel::Distrust,
+            ..self.trust_for(cert)
+        }
+    }
+
+    /// Objects for `n` certificates, in random order: most certificates
+    /// with a trust object, some without, and some left out and only
+    /// distrusted.
+    pub fn objects(&mut self, n: usize) -> Vec<Object> {
+        let mut objs = Vec::new();
+        for _ in 0..n {
+            let cert = self.certificate();
+            if self.chance(10) {
+                objs.push(Object::Trust(self.distrust(&cert)));
+                continue;
+            }
+            if self.chance(90) {
+                objs.push(Object::Trust(self.trust_for(&cert)));
+            }
+            objs.push(Object::Certificate(cert));
+        }
+        self.shuffle(&mut objs);
+        objs
+    }
+
+    fn cert_attrs(&mut self, cert: &Certificate) -> Vec<Attr> {
+        let mut attrs = common_attrs("CKO_CERTIFICATE", &cert.label);
+        attrs.push(token("CKA_CERTIFICATE_TYPE", "CK_CERTIFICATE_TYPE", "CKC_X_509"));
+        attrs.push(binary("CKA_SUBJECT", &cert.subject));
+        attrs.push(string("CKA_ID", "0"));
+        attrs.push(binary("CKA_ISSUER", &cert.issuer));
+        attrs.push(binary("CKA_SERIAL_NUMBER", &cert.serial));
+        attrs.push(binary("CKA_VALUE", &cert.cert));
+        if let Some(policy) = cert.mozilla_ca_policy {
+            attrs.push(bbool("CKA_NSS_MOZILLA_CA_POLICY", policy));
+        }
+        for &(key, after) in &[("CKA_NSS_SERVER_DISTRUST_AFTER", cert.server_distrust_after),
+                               ("CKA_NSS_EMAIL_DISTRUST_AFTER", cert.email_distrust_after)] {
+            match after {
+                Some(t) => attrs.push(binary(key, t.to_utc_time().unwrap().as_bytes())),
+                // Older files leave these out.
+                None if self.chance(50) => attrs.push(bbool(key, false)),
+                None => (),
+            }
+        }
+        attrs
+    }
+
+    fn trust_attrs(&mut self, trust: &Trust) -> Vec<Attr> {
+        let mut attrs = common_attrs("CKO_NSS_TRUST", &trust.label);
+        if let Some(ref sha1) = trust.sha1 {
+            attrs.push(binary("CKA_CERT_SHA1_HASH", sha1));
+        }
+        if let Some(ref md5) = trust.md5 {
+            attrs.push(binary("CKA_CERT_MD5_HASH", md5));
+        }
+        attrs.push(binary("CKA_ISSUER", &trust.issuer));
+        attrs.push(binary("CKA_SERIAL_NUMBER", &trust.serial));
+        attrs.push(token("CKA_TRUST_SERVER_AUTH", "CK_TRUST", level_token(trust.tls_server_trust)));
+        attrs.push(token("CKA_TRUST_EMAIL_PROTECTION", "CK_TRUST",
+                         level_token(trust.email_trust)));
+        attrs.push(token("CKA_TRUST_CODE_SIGNING", "CK_TRUST",
+                         level_token(trust.code_signing_trust)));
+        attrs.push(bbool("CKA_TRUST_STEP_UP_APPROVED", false));
+        attrs
+    }
+
+    fn write_object(&mut self, out: &mut String, mut attrs: Vec<Attr>) {
+        // Except that CKA_CLASS has to come first, since it's what
+        // starts a new object.
+        self.shuffle(&mut attrs[1..]);
+        match self.below(4) {
+            0 => out.push('\n'),
+            1 => out.push_str("#\n# Comment\n#\n"),
+            2 => out.push_str("\n\n \t# Indented comment\n"),
+            _ => (),
+        }
+        for attr in &attrs {
+            write_attr(out, attr);
+        }
+    }
+
+    /// certdata.txt text for `objs`, in that order.  The layout varies:
+    /// the order of attributes, comments and blank lines, whether unset
+    /// distrust-after dates are written out, and objects of other
+    /// classes, which `ObjectIter` skips.
+    pub fn render(&mut self, objs: &[Object]) -> String {
+        let mut out = String::from("# Generated for testing.\n\nBEGINDATA\n");
+        for obj in objs {
+            if self.chance(5) {
+                let attrs = common_attrs("CKO_NSS_BUILTIN_ROOT_LIST", "Mozilla Builtin Roots");
+                self.write_object(&mut out, attrs);
+            }
+            let attrs = match *obj {
+                Object::Certificate(ref cert) => self.cert_attrs(cert),
+                Object::Trust(ref trust) => self.trust_attrs(trust),
+            };
+            self.write_object(&mut out, attrs);
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Generator;
+    use collect::{CertData, TrustStatus};
+    use reader::ObjectIter;
+    use std::io::BufReader;
+    use structured::{Certificate, Object, Trust, Usage};
+    use structured::TrustLevel::*;
+    use time::Time;
+    use Error;
+
+    const USAGES: [Usage; 3] = [Usage::TlsServer, Usage::Email, Usage::CodeSigning];
+
+    #[test]
+    fn test_generator() {
+        let mut gen = Generator::new(1);
+        for _ in 0..50 {
+            let cert = gen.certificate();
+            let again = Certificate::from_der(cert.label.clone(), cert.cert.to_vec()).unwrap();
+            assert_eq!((&again.issuer, &again.serial, &again.subject),
+                       (&cert.issuer, &cert.serial, &cert.subject));
+            let info = cert.x509().unwrap();
+            assert!(info.not_before <= info.not_after);
+        }
+        let text = |seed| {
+            let mut gen = Generator::new(seed);
+            let objs = gen.objects(5);
+            gen.render(&objs)
+        };
+        assert_eq!(text(7), text(7));
+        assert!(text(7) != text(8));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        for seed in 0..200 {
+            let mut gen = Generator::new(seed);
+            let n = gen.below(20) as usize;
+            let objs = gen.objects(n);
+            let text = gen.render(&objs);
+            let parsed: Vec<Object> = ObjectIter::new(text.as_bytes())
+                .collect::<Result<_, _>>()
+                .unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+            assert_eq!(parsed, objs, "seed {}", seed);
+
+            // And read a few bytes at a time.
+            let src = BufReader::with_capacity(1 + gen.below(64) as usize, text.as_bytes());
+            let parsed: Vec<Object> = ObjectIter::new(src)
+                .collect::<Result<_, _>>()
+                .unwrap_or_else(|err| panic!("seed {}: {}", seed, err));
+            assert_eq!(parsed, objs, "seed {}", seed);
+        }
+    }
+
+    // What `CertData` should say, worked out the slow way.
+    struct Reference {
+        certs: Vec<Certificate>,
+        trusts: Vec<Trust>,
+    }
+
+    impl Reference {
+        fn new(objs: &[Object]) -> Reference {
+            let mut reference = Reference { certs: Vec::new(), trusts: Vec::new() };
+            for obj in objs {
+                match *obj {
+                    Object::Certificate(ref cert) => reference.certs.push(cert.clone()),
+                    Object::Trust(ref trust) => reference.trusts.push(trust.clone()),
+                }
+            }
+            reference
+        }
+
+        fn trust_for(&self, cert: &Certificate) -> Option<&Trust> {
+            self.trusts.iter().find(|t| t.issuer == cert.issuer && t.serial == cert.serial)
+        }
+
+        // Every reason `cert` isn't simply trusted, in no particular order.
+        fn reasons(&self, cert: &Certificate, usage: Usage, date: Time) -> Vec<TrustStatus> {
+            let info = cert.x509().unwrap();
+            let mut reasons = Vec::new();
+            if date > info.not_after {
+                reasons.push(TrustStatus::Expired(info.not_after));
+            }
+            if let Some(after) = cert.distrust_after(usage).filter(|&after| date > after) {
+                reasons.push(TrustStatus::DistrustedAfter(after));
+            }
+            if date < info.not_before {
+                reasons.push(TrustStatus::NotYetValid(info.not_before));
+            }
+            match self.trust_for(cert).map(|t| t.trust_level(usage)) {
+                Some(Distrust) => reasons.push(TrustStatus::Distrusted),
+                None | Some(MustVerify) => reasons.push(TrustStatus::NoTrust),
+                Some(TrustedDelegator) => (),
+            }
+            reasons
+        }
+
+        // The reason that wins: the trust record, then validity, then
+        // the distrust-after date.
+        fn status(&self, cert: &Certificate, usage: Usage, date: Time) -> TrustStatus {
+            fn precedence(status: &TrustStatus) -> u8 {
+                match *status {
+                    TrustStatus::Distrusted => 0,
+                    TrustStatus::NoTrust => 1,
+                    TrustStatus::NotYetValid(_) => 2,
+                    TrustStatus::Expired(_) => 3,
+                    TrustStatus::DistrustedAfter(_) => 4,
+                    TrustStatus::Trusted => 5,
+                }
+            }
+            self.reasons(cert, usage, date).into_iter().min_by_key(precedence)
+                .unwrap_or(TrustStatus::Trusted)
+        }
+    }
+
+    fn sorted_ders<'a, I: IntoIterator<Item = &'a Certificate>>(certs: I) -> Vec<&'a [u8]> {
+        let mut ders: Vec<_> = certs.into_iter().map(|c| &c.cert[..]).collect();
+        ders.sort();
+        ders
+    }
+
+    #[test]
+    fn test_queries() {
+        for seed in 0..100 {
+            let mut gen = Generator::new(seed);
+            let objs = gen.objects(30);
+            let data = CertData::from_iter(objs.iter().cloned().map(Ok::<_, Error>)).unwrap();
+            let reference = Reference::new(&objs);
+
+            assert_eq!(sorted_ders(data.certs()), sorted_ders(&reference.certs));
+            assert_eq!(data.trusts().len(), reference.trusts.len());
+            for trust in &reference.trusts {
+                assert_eq!(data.trust_for(&trust.issuer, &trust.serial), Some(trust));
+            }
+            let stranger = gen.certificate();
+            assert_eq!(data.trust_for_cert(&stranger), None);
+
+            for cert in &reference.certs {
+                assert_eq!(data.trust_for_cert(cert), reference.trust_for(cert));
+                for &usage in &USAGES {
+                    let distrusted = reference.trust_for(cert)
+                        .filter(|t| t.trust_level(usage) == Distrust);
+                    assert_eq!(data.is_distrusted(&cert.cert, usage).unwrap(), distrusted);
+                    let date = gen.time();
+                    assert_eq!(data.status_as_of(cert, usage, date).unwrap(),
+                               reference.status(cert, usage, date), "seed {}", seed);
+                }
+            }
+
+            for &usage in &USAGES {
+                let trusted = reference.certs.iter().filter(|c| {
+                    reference.trust_for(c).map(|t| t.trust_level(usage)) == Some(TrustedDelegator)
+                });
+                assert_eq!(sorted_ders(data.trusted_certs(usage)), sorted_ders(trusted));
+
+                let date = gen.time();
+                let trusted = reference.certs.iter().filter(|c| {
+                    reference.status(c, usage, date) == TrustStatus::Trusted
+                });
+                assert_eq!(sorted_ders(data.trusted_certs_as_of(usage, date)),
+                           sorted_ders(trusted));
+
+                let distrusts = reference.trusts.iter()
+                    .filter(|t| t.trust_level(usage) == Distrust)
+                    .count();
+                assert_eq!(data.distrusts(usage).len(), distrusts);
+                assert!(data.distrusts(usage).iter().all(|t| t.trust_level(usage) == Distrust));
+            }
+        }
+    }
+}
@@ -0,0 +1,233 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Just enough calendar arithmetic to deal with X.509 validity periods
+// and NSS's distrust-after dates, without pulling in a date crate.
+
+use std::fmt;
+use std::result;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A point in time, in whole seconds (UTC, no leap seconds).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Time(i64);
+
+// Days since 1970-01-01 of a proleptic Gregorian date; this is
+// Howard Hinnant's `days_from_civil`.
+fn days_from_civil(y: i64, m: u32, d: u32) -> i64 {
+    let y = if m <= 2 { y - 1 } else { y };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let mp = (i64::from(m) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(d) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+// ...and its inverse, `civil_from_days`.
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = yoe + era * 400 + if m <= 2 { 1 } else { 0 };
+    (y, m, d)
+}
+
+fn is_leap(y: i64) -> bool {
+    y % 4 == 0 && (y % 100 != 0 || y % 400 == 0)
+}
+
+fn days_in_month(y: i64, m: u32) -> u32 {
+    match m {
+        2 => if is_leap(y) { 29 } else { 28 },
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+fn digits(s: &[u8]) -> Option<u32> {
+    if s.is_empty() || !s.iter().all(u8::is_ascii_digit) {
+        return None;
+    }
+    Some(s.iter().fold(0, |acc, &b| acc * 10 + u32::from(b - b'0')))
+}
+
+impl Time {
+    pub const fn from_unix(secs: i64) -> Time {
+        Time(secs)
+    }
+    pub fn unix(&self) -> i64 {
+        self.0
+    }
+
+    pub fn now() -> Time {
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(d) => Time(d.as_secs() as i64),
+            Err(e) => Time(-(e.duration().as_secs() as i64)),
+        }
+    }
+
+    /// Returns `None` if any field is out of range.
+    pub fn from_ymd_hms(year: i64, month: u32, day: u32, hour: u32, min: u32, sec: u32)
+                        -> Option<Time> {
+        if !(1..=12).contains(&month) || day < 1 || day > days_in_month(year, month) ||
+            hour > 23 || min > 59 || sec > 59 {
+            return None;
+        }
+        let days = days_from_civil(year, month, day);
+        Some(Time(days * 86400 + i64::from(hour * 3600 + min * 60 + sec)))
+    }
+
+    /// (year, month, day, hour, minute, second)
+    pub fn to_ymd_hms(&self) -> (i64, u32, u32, u32, u32, u32) {
+        let days = self.0.div_euclid(86400);
+        let secs = self.0.rem_euclid(86400) as u32;
+        let (y, m, d) = civil_from_days(days);
+        (y, m, d, secs / 3600, secs / 60 % 60, secs % 60)
+    }
+
+    /// Parses the DER form of an ASN.1 UTCTime (`YYMMDDHHMMSSZ`),
+    /// which is also what `certdata.txt` uses for distrust-after dates.
+    pub fn from_utc_time(s: &[u8]) -> Option<Time> {
+        if s.len() != 13 || s[12] != b'Z' {
+            return None;
+        }
+        let yy = digits(&s[0..2])? as i64;
+        let year = if yy >= 50 { 1900 + yy } else { 2000 + yy };
+        Self::from_fields(year, &s[2..12])
+    }
+
+    /// Parses the DER form of an ASN.1 GeneralizedTime (`YYYYMMDDHHMMSSZ`).
+    pub fn from_generalized_time(s: &[u8]) -> Option<Time> {
+        if s.len() != 15 || s[14] != b'Z' {
+            return None;
+        }
+        let year = digits(&s[0..4])? as i64;
+        Self::from_fields(year, &s[4..14])
+    }
+
+    fn from_fields(year: i64, s: &[u8]) -> Option<Time> {
+        Time::from_ymd_hms(year,
+                           digits(&s[0..2])?,
+                           digits(&s[2..4])?,
+                           digits(&s[4..6])?,
+                           digits(&s[6..8])?,
+                           digits(&s[8..10])?)
+    }
+
+    /// The inverse of `from_utc_time`; `None` outside of 1950-2049.
+    pub fn to_utc_time(&self) -> Option<String> {
+        let (y, mo, d, h, mi, s) = self.to_ymd_hms();
+        if !(1950..2050).contains(&y) {
+            return None;
+        }
+        Some(format!("{:02}{:02}{:02}{:02}{:02}{:02}Z", y % 100, mo, d, h, mi, s))
+    }
+
+    /// The date part only, as `YYYY-MM-DD`.
+    pub fn date_string(&self) -> String {
+        let (y, m, d, _, _, _) = self.to_ymd_hms();
+        format!("{:04}-{:02}-{:02}", y, m, d)
+    }
+}
+
+/// RFC 3339, always in UTC: `2038-01-19T03:14:07Z`.
+impl fmt::Display for Time {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        let (y, mo, d, h, mi, s) = self.to_ymd_hms();
+        write!(fmt, "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z", y, mo, d, h, mi, s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimeParseError;
+
+impl fmt::Display for TimeParseError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> result::Result<(), fmt::Error> {
+        fmt.write_str("expected YYYY-MM-DD or YYYY-MM-DDTHH:MM:SSZ")
+    }
+}
+
+/// Accepts `YYYY-MM-DD` (meaning midnight UTC) or the `Display` form.
+impl FromStr for Time {
+    type Err = TimeParseError;
+    fn from_str(s: &str) -> result::Result<Time, TimeParseError> {
+        let b = s.as_bytes();
+        let sep_ok = |i: usize, c: u8| b.get(i) == Some(&c);
+        if b.len() < 10 || !sep_ok(4, b'-') || !sep_ok(7, b'-') {
+            return Err(TimeParseError);
+        }
+        let (h, mi, sec) = if b.len() == 10 {
+            (Some(0), Some(0), Some(0))
+        } else if b.len() == 20 && (b[10] == b'T' || b[10] == b' ') &&
+            sep_ok(13, b':') && sep_ok(16, b':') && sep_ok(19, b'Z') {
+            (digits(&b[11..13]), digits(&b[14..16]), digits(&b[17..19]))
+        } else {
+            return Err(TimeParseError);
+        };
+        match (digits(&b[0..4]), digits(&b[5..7]), digits(&b[8..10]), h, mi, sec) {
+            (Some(y), Some(mo), Some(d), Some(h), Some(mi), Some(sec)) =>
+                Time::from_ymd_hms(i64::from(y), mo, d, h, mi, sec).ok_or(TimeParseError),
+            _ => Err(TimeParseError),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Time;
+
+    #[test]
+    fn test_epoch() {
+        assert_eq!(Time::from_ymd_hms(1970, 1, 1, 0, 0, 0), Some(Time::from_unix(0)));
+        assert_eq!(Time::from_unix(0).to_string(), "1970-01-01T00:00:00Z");
+        assert_eq!(Time::from_unix(2147483647).to_string(), "2038-01-19T03:14:07Z");
+        assert_eq!(Time::from_unix(-1).to_string(), "1969-12-31T23:59:59Z");
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert!(Time::from_ymd_hms(2000, 2, 29, 0, 0, 0).is_some());
+        assert!(Time::from_ymd_hms(1900, 2, 29, 0, 0, 0).is_none());
+        assert!(Time::from_ymd_hms(2023, 13, 1, 0, 0, 0).is_none());
+        assert!(Time::from_ymd_hms(2023, 4, 31, 0, 0, 0).is_none());
+        assert!(Time::from_ymd_hms(2023, 4, 30, 24, 0, 0).is_none());
+    }
+
+    #[test]
+    fn test_asn1() {
+        assert_eq!(Time::from_utc_time(b"200401000000Z").unwrap().to_string(),
+                   "2020-04-01T00:00:00Z");
+        assert_eq!(Time::from_utc_time(b"991231235959Z").unwrap().to_string(),
+                   "1999-12-31T23:59:59Z");
+        assert_eq!(Time::from_utc_time(b"500101000000Z").unwrap().to_string(),
+                   "1950-01-01T00:00:00Z");
+        assert_eq!(Time::from_generalized_time(b"20491231235959Z").unwrap().to_string(),
+                   "2049-12-31T23:59:59Z");
+        assert_eq!(Time::from_utc_time(b"2004010000Z"), None);
+        assert_eq!(Time::from_utc_time(b"200401000000+0100"), None);
+        assert_eq!(Time::from_utc_time(b"2004010000-0Z"), None);
+        assert_eq!(Time::from_generalized_time(b"200401000000Z"), None);
+
+        let t = Time::from_utc_time(b"491231235959Z").unwrap();
+        assert_eq!(t.to_utc_time().unwrap(), "491231235959Z");
+        assert_eq!(Time::from_ymd_hms(2050, 1, 1, 0, 0, 0).unwrap().to_utc_time(), None);
+    }
+
+    #[test]
+    fn test_from_str() {
+        assert_eq!("2023-05-01".parse(), Ok(Time::from_ymd_hms(2023, 5, 1, 0, 0, 0).unwrap()));
+        assert_eq!("2023-05-01T12:34:56Z".parse(),
+                   Ok(Time::from_ymd_hms(2023, 5, 1, 12, 34, 56).unwrap()));
+        assert!("2023-5-01".parse::<Time>().is_err());
+        assert!("2023-05-01T12:34:56".parse::<Time>().is_err());
+        assert!("2023-02-30".parse::<Time>().is_err());
+    }
+}
@@ -0,0 +1,242 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// The history of the store over a series of certdata.txt revisions
+// (e.g. one per NSS release): the `diff` between each one and the one
+// before, filed under the certificate it's about.  Each entry is tagged
+// with the version where the change first showed up.
+
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::Path;
+
+use collect::CertData;
+use diff::{diff, Change};
+use reader::ObjectIter;
+use structured::{TrustLevel, Usage};
+use Error;
+
+/// Everything that happened to one certificate, in order.
+#[derive(Debug, Clone)]
+pub struct RootHistory {
+    pub sha256: [u8; 32],
+    /// The most recent label.
+    pub label: String,
+    /// Version tags and what changed in them.
+    pub changes: Vec<(String, Change)>,
+}
+
+impl RootHistory {
+    /// The version it was first added in.
+    pub fn first_appeared(&self) -> Option<&str> {
+        self.changes.iter()
+            .find(|&(_, change)| matches!(*change, Change::Added { .. }))
+            .map(|(version, _)| &version[..])
+    }
+
+    /// The version it was last removed in, if it's not in the latest one.
+    pub fn removed(&self) -> Option<&str> {
+        let last = self.changes.iter().rev().find(|&(_, change)| {
+            matches!(*change, Change::Added { .. } | Change::Removed { .. })
+        });
+        match last {
+            Some((version, Change::Removed { .. })) => Some(version),
+            _ => None,
+        }
+    }
+
+    /// The first version where it was a trusted delegator for `usage`.
+    pub fn first_trusted(&self, usage: Usage) -> Option<&str> {
+        self.changes.iter().find(|&(_, change)| match *change {
+            Change::Trust { usage: u, new: TrustLevel::TrustedDelegator, .. } => u == usage,
+            _ => false,
+        }).map(|(version, _)| &version[..])
+    }
+}
+
+pub struct Timeline {
+    versions: Vec<String>,
+    roots: BTreeMap<[u8; 32], RootHistory>,
+    distrusts: Vec<(String, Change)>,
+    last: CertData,
+}
+
+impl Default for Timeline {
+    fn default() -> Self {
+        Timeline {
+            versions: Vec::new(),
+            roots: BTreeMap::new(),
+            distrusts: Vec::new(),
+            last: CertData::from_iter(Vec::<Result<_, Error>>::new()).unwrap(),
+        }
+    }
+}
+
+fn subject(change: &Change) -> Option<([u8; 32], &str)> {
+    match *change {
+        Change::Added { ref label, sha256 } |
+        Change::Removed { ref label, sha256 } |
+        Change::Trust { ref label, sha256, .. } |
+        Change::DistrustAfter { ref label, sha256, .. } |
+        Change::MozillaCaPolicy { ref label, sha256, .. } => Some((sha256, label)),
+        Change::Relabeled { ref new, sha256, .. } => Some((sha256, new)),
+        Change::DistrustAdded { .. } | Change::DistrustRemoved { .. } => None,
+    }
+}
+
+impl Timeline {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads each file in turn; `files` should be oldest first.
+    pub fn from_files<I, S, P>(files: I) -> Result<Timeline, Error>
+        where I: IntoIterator<Item = (S, P)>,
+              S: Into<String>,
+              P: AsRef<Path>
+    {
+        let mut timeline = Timeline::new();
+        for (version, path) in files {
+            let objs = ObjectIter::new(BufReader::new(File::open(path)?));
+            timeline.push(version, CertData::from_iter(objs)?);
+        }
+        Ok(timeline)
+    }
+
+    /// Adds the next revision.
+    pub fn push<S: Into<String>>(&mut self, version: S, data: CertData) {
+        let version = version.into();
+        for change in diff(&self.last, &data) {
+            match subject(&change) {
+                Some((sha256, label)) => {
+                    let root = self.roots.entry(sha256).or_insert_with(|| RootHistory {
+                        sha256,
+                        label: String::new(),
+                        changes: Vec::new(),
+                    });
+                    root.label = label.to_owned();
+                    root.changes.push((version.clone(), change));
+                }
+                None => self.distrusts.push((version.clone(), change)),
+            }
+        }
+        self.versions.push(version);
+        self.last = data;
+    }
+
+    pub fn versions(&self) -> &[String] {
+        &self.versions
+    }
+
+    /// The latest revision.
+    pub fn latest(&self) -> &CertData {
+        &self.last
+    }
+
+    /// Every certificate that was ever in the store, by fingerprint.
+    pub fn roots(&self) -> impl Iterator<Item = &RootHistory> {
+        self.roots.values()
+    }
+
+    pub fn root(&self, sha256: &[u8; 32]) -> Option<&RootHistory> {
+        self.roots.get(sha256)
+    }
+
+    /// Certificates that have had `label` at some point.
+    pub fn find_label<'a>(&'a self, label: &'a str) -> impl Iterator<Item = &'a RootHistory> {
+        self.roots.values().filter(move |root| {
+            root.label == label || root.changes.iter().any(|(_, change)| match *change {
+                Change::Added { label: ref l, .. } |
+                Change::Relabeled { old: ref l, .. } => l == label,
+                _ => false,
+            })
+        })
+    }
+
+    /// Explicit distrusts of certificates not in the file, being added
+    /// and removed.
+    pub fn distrusts(&self) -> &[(String, Change)] {
+        &self.distrusts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Timeline;
+    use collect::CertData;
+    use diff::Change;
+    use reader::ObjectIter;
+    use structured::{Object, TrustLevel, Usage};
+    use Error;
+
+    fn fixture_objects() -> Vec<Object> {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        ObjectIter::new(fixture).collect::<Result<_, _>>().unwrap()
+    }
+
+    fn collect(objs: Vec<Object>) -> CertData {
+        CertData::from_iter(objs.into_iter().map(Ok::<_, Error>)).unwrap()
+    }
+
+    #[test]
+    fn test_timeline() {
+        // v1: no ACCVRAIZ1, and Amazon Root CA 1 isn't trusted for email.
+        let mut v1 = fixture_objects();
+        v1.retain(|obj| match *obj {
+            Object::Certificate(ref c) => c.label != "ACCVRAIZ1",
+            Object::Trust(ref t) => t.label != "ACCVRAIZ1",
+        });
+        for obj in &mut v1 {
+            if let Object::Trust(ref mut t) = *obj {
+                if t.label == "Amazon Root CA 1" {
+                    t.email_trust = TrustLevel::MustVerify;
+                }
+            }
+        }
+        // v3: no Certigna.
+        let mut v3 = fixture_objects();
+        v3.retain(|obj| match *obj {
+            Object::Certificate(ref c) => c.label != "Certigna",
+            Object::Trust(ref t) => t.label != "Certigna",
+        });
+
+        let mut timeline = Timeline::new();
+        timeline.push("v1", collect(v1));
+        timeline.push("v2", collect(fixture_objects()));
+        timeline.push("v3", collect(v3));
+        assert_eq!(timeline.versions(), ["v1", "v2", "v3"]);
+
+        let accv = timeline.find_label("ACCVRAIZ1").next().unwrap();
+        assert_eq!(accv.first_appeared(), Some("v2"));
+        assert_eq!(accv.first_trusted(Usage::TlsServer), Some("v2"));
+        assert_eq!(accv.removed(), None);
+
+        let amazon = timeline.find_label("Amazon Root CA 1").next().unwrap();
+        assert_eq!(amazon.first_appeared(), Some("v1"));
+        assert_eq!(amazon.first_trusted(Usage::TlsServer), Some("v1"));
+        assert_eq!(amazon.first_trusted(Usage::Email), Some("v2"));
+        assert_eq!(amazon.first_trusted(Usage::CodeSigning), None);
+
+        let certigna = timeline.find_label("Certigna").next().unwrap();
+        assert_eq!(certigna.removed(), Some("v3"));
+        assert_eq!(timeline.root(&certigna.sha256).unwrap().label, "Certigna");
+        assert!(!timeline.latest().certs().iter().any(|c| c.label == "Certigna"));
+
+        assert_eq!(timeline.roots().count(), timeline.latest().certs().len() + 1);
+        match timeline.distrusts()[0] {
+            (ref version, Change::DistrustAdded { .. }) => assert_eq!(version, "v1"),
+            ref other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_from_files() {
+        let files = vec![("3.90", "tests/data/certdata.txt"), ("3.91", "tests/data/certdata.txt")];
+        let timeline = Timeline::from_files(files).unwrap();
+        assert_eq!(timeline.versions(), ["3.90", "3.91"]);
+        assert!(timeline.roots().all(|root| root.first_appeared() == Some("3.90")));
+        assert!(Timeline::from_files(vec![("x", "tests/data/nonexistent")]).is_err());
+    }
+}
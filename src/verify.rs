@@ -0,0 +1,592 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Chain building and path validation against a `CertData`, with NSS's
+// trust semantics: a path ends at a certificate that's a trusted
+// delegator for the usage, and an explicit distrust of any certificate
+// on the path (including the leaf) rejects it no matter how good the
+// signatures are.  Distrust-after dates on the root are compared with
+// the leaf's notBefore.
+//
+// This is a depth-first search over every issuer candidate, so a
+// cross-signed intermediate that leads to a distrusted root doesn't
+// hide another path that leads to a trusted one.  Not checked: name
+// constraints, key usage, extended key usage, policies, revocation.
+
+use std::convert::TryFrom;
+
+use ed25519_dalek;
+use p256;
+use p384;
+use p256::ecdsa::signature::hazmat::PrehashVerifier;
+use rsa::{BigUint, Pkcs1v15Sign, Pss, RsaPublicKey};
+use sha2::{Digest, Sha256, Sha384, Sha512};
+
+use collect::CertData;
+use der::{self, tag, DerError};
+use name;
+use structured::{Certificate, TrustLevel, Usage};
+use time::Time;
+use x509::{oid, CertInfo, PublicKey};
+
+quick_error!{
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub enum VerifyError {
+        BadCertificate(err: DerError) {
+            from()
+            description("malformed certificate")
+            display("malformed certificate: {}", err)
+        }
+        UnknownIssuer(subject: String) {
+            description("no path to a trusted root")
+            display("no trusted issuer found for {}", subject)
+        }
+        BadSignature(subject: String) {
+            description("bad signature")
+            display("bad signature on {}", subject)
+        }
+        UnsupportedAlgorithm(subject: String, oid: String) {
+            description("unsupported signature algorithm")
+            display("unsupported signature algorithm {} on {}", oid, subject)
+        }
+        Expired(subject: String, not_after: Time) {
+            description("certificate expired")
+            display("{} expired on {}", subject, not_after.date_string())
+        }
+        NotYetValid(subject: String, not_before: Time) {
+            description("certificate not yet valid")
+            display("{} is not valid until {}", subject, not_before.date_string())
+        }
+        NotCa(subject: String) {
+            description("issuer is not a CA")
+            display("{} is not a CA", subject)
+        }
+        PathTooLong(subject: String) {
+            description("path length constraint violated")
+            display("path length constraint of {} violated", subject)
+        }
+        Distrusted(subject: String, label: String) {
+            description("certificate explicitly distrusted")
+            display("{} is explicitly distrusted by {:?}", subject, label)
+        }
+        DistrustedAfter(label: String, after: Time) {
+            description("root distrusted for certificates issued after a date")
+            display("{:?} is distrusted for certificates issued after {}",
+                    label, after.date_string())
+        }
+    }
+}
+
+pub type Result<T> = ::std::result::Result<T, VerifyError>;
+
+#[derive(Debug, Clone)]
+pub struct VerifyOptions {
+    pub usage: Usage,
+    /// For validity periods.
+    pub time: Time,
+    /// The most certificates a path may have, counting the leaf and root.
+    pub max_path_len: usize,
+}
+
+impl Default for VerifyOptions {
+    fn default() -> Self {
+        VerifyOptions {
+            usage: Usage::TlsServer,
+            time: Time::now(),
+            max_path_len: 8,
+        }
+    }
+}
+
+/// A path from the leaf to a trusted root.
+#[derive(Debug, Clone)]
+pub struct VerifiedPath<'a> {
+    /// DER certificates, starting with the leaf and ending with the root.
+    pub chain: Vec<&'a [u8]>,
+    pub root: &'a Certificate,
+}
+
+struct Node<'a> {
+    der: &'a [u8],
+    info: CertInfo<'a>,
+    /// Set for certificates from the `CertData`.
+    stored: Option<&'a Certificate>,
+}
+
+impl<'a> Node<'a> {
+    fn new(der: &'a [u8], stored: Option<&'a Certificate>) -> Result<Self> {
+        Ok(Node { der, info: CertInfo::parse(der)?, stored })
+    }
+}
+
+// Which of two failures to report.
+fn rank(best: VerifyError, err: VerifyError) -> VerifyError {
+    match best {
+        VerifyError::UnknownIssuer(_) => err,
+        _ => best,
+    }
+}
+
+fn describe(name: &[u8]) -> String {
+    name::to_rfc4514(name).unwrap_or_else(|_| "?".to_owned())
+}
+
+struct Verifier<'a, 'o> {
+    data: &'a CertData,
+    opts: &'o VerifyOptions,
+    candidates: Vec<Node<'a>>,
+}
+
+/// Finds a path from `leaf` to a root that `data` trusts for
+/// `opts.usage`, using `intermediates` and the certificates in `data`.
+///
+/// When there's no good path, the error is from the most promising bad
+/// one: anything other than `UnknownIssuer` takes precedence.
+pub fn verify<'a>(data: &'a CertData,
+                  leaf: &'a [u8],
+                  intermediates: &[&'a [u8]],
+                  opts: &VerifyOptions)
+                  -> Result<VerifiedPath<'a>> {
+    // A stored certificate that doesn't parse can't be on any path, so
+    // it's no reason to fail.
+    let mut candidates: Vec<_> = data.certs().iter()
+        .filter_map(|cert| Node::new(&cert.cert, Some(cert)).ok())
+        .collect();
+    for &der in intermediates {
+        if !candidates.iter().any(|c| c.der == der) {
+            candidates.push(Node::new(der, None)?);
+        }
+    }
+    let verifier = Verifier { data, opts, candidates };
+
+    let leaf = Node::new(leaf, None)?;
+    verifier.check_cert(&leaf.info)?;
+    let mut path = vec![&leaf];
+    let root = verifier.extend(&mut path)?;
+    Ok(VerifiedPath {
+        chain: path.iter().map(|node| node.der).collect(),
+        root,
+    })
+}
+
+impl<'a, 'o> Verifier<'a, 'o> {
+    // On success, `path` is left ending with the root; on failure, it's
+    // left as it was.
+    fn extend<'p>(&'p self, path: &mut Vec<&'p Node<'a>>) -> Result<&'a Certificate> {
+        let child = path[path.len() - 1];
+        let mut best = VerifyError::UnknownIssuer(describe(child.info.issuer));
+        if path.len() >= self.opts.max_path_len {
+            return Err(best);
+        }
+        let aki = child.info.authority_key_id()?;
+        for cand in &self.candidates {
+            if cand.info.subject != child.info.issuer || path.iter().any(|n| n.der == cand.der) {
+                continue;
+            }
+            if let (Some(aki), Some(ski)) = (aki, cand.info.subject_key_id()?) {
+                if aki != ski {
+                    continue;
+                }
+            }
+            if let Err(err) = self.check_issuer(path, cand) {
+                best = rank(best, err);
+                continue;
+            }
+            path.push(cand);
+            let result = match cand.stored {
+                Some(root) if self.is_anchor(root) =>
+                    self.check_distrust_after(root, &path[0].info).map(|()| root),
+                _ => self.extend(path),
+            };
+            match result {
+                Ok(root) => return Ok(root),
+                Err(err) => {
+                    path.pop();
+                    best = rank(best, err);
+                }
+            }
+        }
+        Err(best)
+    }
+
+    fn is_anchor(&self, cert: &Certificate) -> bool {
+        self.data.trust_for_cert(cert)
+            .is_some_and(|t| t.trust_level(self.opts.usage) == TrustLevel::TrustedDelegator)
+    }
+
+    // The checks that apply to every certificate on the path.
+    fn check_cert(&self, info: &CertInfo) -> Result<()> {
+        if self.opts.time < info.not_before {
+            return Err(VerifyError::NotYetValid(describe(info.subject), info.not_before));
+        }
+        if self.opts.time > info.not_after {
+            return Err(VerifyError::Expired(describe(info.subject), info.not_after));
+        }
+        if let Some(trust) = self.data.trust_for(info.issuer, info.serial) {
+            if trust.trust_level(self.opts.usage) == TrustLevel::Distrust {
+                return Err(VerifyError::Distrusted(describe(info.subject), trust.label.clone()));
+            }
+        }
+        Ok(())
+    }
+
+    fn check_issuer(&self, path: &[&Node], issuer: &Node) -> Result<()> {
+        let child = &path[path.len() - 1].info;
+        check_signature(child, &issuer.info)?;
+        self.check_cert(&issuer.info)?;
+        match issuer.info.basic_constraints()? {
+            Some((true, path_len)) => {
+                // The certificates between this one and the leaf.
+                if path_len.is_some_and(|n| (path.len() - 1) as u64 > n) {
+                    return Err(VerifyError::PathTooLong(describe(issuer.info.subject)));
+                }
+            }
+            // v1 roots predate basicConstraints; NSS trusts them anyway.
+            None if issuer.stored.is_some() && issuer.info.version == 0 => (),
+            _ => return Err(VerifyError::NotCa(describe(issuer.info.subject))),
+        }
+        Ok(())
+    }
+
+    fn check_distrust_after(&self, root: &Certificate, leaf: &CertInfo) -> Result<()> {
+        match root.distrust_after(self.opts.usage) {
+            Some(after) if leaf.not_before > after => {
+                Err(VerifyError::DistrustedAfter(root.label.clone(), after))
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Hash {
+    Sha256,
+    Sha384,
+    Sha512,
+}
+
+impl Hash {
+    fn from_oid(oid: &[u8]) -> Option<Hash> {
+        match oid {
+            oid::SHA256 => Some(Hash::Sha256),
+            oid::SHA384 => Some(Hash::Sha384),
+            oid::SHA512 => Some(Hash::Sha512),
+            _ => None,
+        }
+    }
+
+    fn digest(self, msg: &[u8]) -> Vec<u8> {
+        match self {
+            Hash::Sha256 => Sha256::digest(msg).to_vec(),
+            Hash::Sha384 => Sha384::digest(msg).to_vec(),
+            Hash::Sha512 => Sha512::digest(msg).to_vec(),
+        }
+    }
+}
+
+enum Scheme {
+    Pkcs1(Hash),
+    Pss(Hash, usize),
+    Ecdsa(Hash),
+    Ed25519,
+}
+
+// The hash algorithm in an AlgorithmIdentifier, whose parameters are
+// NULL or absent.
+fn hash_alg(alg: &[u8]) -> der::Result<Option<Hash>> {
+    let mut rd = der::parse_one(alg, tag::SEQUENCE)?.reader();
+    let hash = Hash::from_oid(rd.read(tag::OID)?.value);
+    rd.read_optional(tag::NULL)?;
+    rd.finish()?;
+    Ok(hash)
+}
+
+// RSASSA-PSS-params, if they use the same SHA-2 hash for the message
+// and MGF1, which is all anyone does in practice.
+fn pss_params(params: &[u8]) -> der::Result<Option<Scheme>> {
+    let mut rd = der::parse_one(params, tag::SEQUENCE)?.reader();
+    let hash = match rd.read_optional(tag::context(0))? {
+        None => return Ok(None), // SHA-1
+        Some(tlv) => hash_alg(tlv.value)?,
+    };
+    let mgf_hash = match rd.read_optional(tag::context(1))? {
+        None => return Ok(None),
+        Some(tlv) => {
+            let mut mgf = der::parse_one(tlv.value, tag::SEQUENCE)?.reader();
+            if mgf.read(tag::OID)?.value != oid::MGF1 {
+                return Ok(None);
+            }
+            hash_alg(mgf.read(tag::SEQUENCE)?.raw)?
+        }
+    };
+    let salt = match rd.read_optional(tag::context(2))? {
+        None => 20,
+        Some(tlv) => der::small_uint(der::parse_one(tlv.value, tag::INTEGER)?.value)? as usize,
+    };
+    Ok(match (hash, mgf_hash) {
+        (Some(h), Some(m)) if h == m => Some(Scheme::Pss(h, salt)),
+        _ => None,
+    })
+}
+
+fn scheme(alg: &[u8]) -> der::Result<Option<Scheme>> {
+    let mut rd = der::parse_one(alg, tag::SEQUENCE)?.reader();
+    Ok(match rd.read(tag::OID)?.value {
+        oid::SHA256_WITH_RSA => Some(Scheme::Pkcs1(Hash::Sha256)),
+        oid::SHA384_WITH_RSA => Some(Scheme::Pkcs1(Hash::Sha384)),
+        oid::SHA512_WITH_RSA => Some(Scheme::Pkcs1(Hash::Sha512)),
+        oid::RSASSA_PSS => pss_params(rd.read(tag::SEQUENCE)?.raw)?,
+        oid::ECDSA_WITH_SHA256 => Some(Scheme::Ecdsa(Hash::Sha256)),
+        oid::ECDSA_WITH_SHA384 => Some(Scheme::Ecdsa(Hash::Sha384)),
+        oid::ECDSA_WITH_SHA512 => Some(Scheme::Ecdsa(Hash::Sha512)),
+        oid::ED25519 => Some(Scheme::Ed25519),
+        _ => None,
+    })
+}
+
+// An ECDSA-Sig-Value as the fixed-width r || s that the curve crates
+// want, or `None` if either half doesn't fit.
+fn ecdsa_fixed(sig: &[u8], width: usize) -> der::Result<Option<Vec<u8>>> {
+    let mut rd = der::parse_one(sig, tag::SEQUENCE)?.reader();
+    let r = der::unsigned_int(rd.read(tag::INTEGER)?.value)?;
+    let s = der::unsigned_int(rd.read(tag::INTEGER)?.value)?;
+    rd.finish()?;
+    if r.len() > width || s.len() > width {
+        return Ok(None);
+    }
+    let mut out = vec![0; width * 2];
+    out[width - r.len()..width].copy_from_slice(r);
+    out[width * 2 - s.len()..].copy_from_slice(s);
+    Ok(Some(out))
+}
+
+fn rsa_verify(modulus: &[u8], exponent: &[u8], scheme: &Scheme, msg: &[u8], sig: &[u8])
+              -> bool {
+    let key = match RsaPublicKey::new_with_max_size(BigUint::from_bytes_be(modulus),
+                                                    BigUint::from_bytes_be(exponent),
+                                                    8192) {
+        Ok(key) => key,
+        Err(_) => return false,
+    };
+    let result = match *scheme {
+        Scheme::Pkcs1(hash) => {
+            let padding = match hash {
+                Hash::Sha256 => Pkcs1v15Sign::new::<Sha256>(),
+                Hash::Sha384 => Pkcs1v15Sign::new::<Sha384>(),
+                Hash::Sha512 => Pkcs1v15Sign::new::<Sha512>(),
+            };
+            key.verify(padding, &hash.digest(msg), sig)
+        }
+        Scheme::Pss(hash, salt) => {
+            let padding = match hash {
+                Hash::Sha256 => Pss::new_with_salt::<Sha256>(salt),
+                Hash::Sha384 => Pss::new_with_salt::<Sha384>(salt),
+                Hash::Sha512 => Pss::new_with_salt::<Sha512>(salt),
+            };
+            key.verify(padding, &hash.digest(msg), sig)
+        }
+        _ => return false,
+    };
+    result.is_ok()
+}
+
+fn ecdsa_verify(curve: &[u8], point: &[u8], hash: Hash, msg: &[u8], sig: &[u8])
+                -> der::Result<bool> {
+    let prehash = hash.digest(msg);
+    Ok(match curve {
+        oid::CURVE_P256 => {
+            let sig = ecdsa_fixed(sig, 32)?
+                .and_then(|rs| p256::ecdsa::Signature::from_slice(&rs).ok());
+            let key = p256::ecdsa::VerifyingKey::from_sec1_bytes(point).ok();
+            match (key, sig) {
+                (Some(key), Some(sig)) => key.verify_prehash(&prehash, &sig).is_ok(),
+                _ => false,
+            }
+        }
+        oid::CURVE_P384 => {
+            let sig = ecdsa_fixed(sig, 48)?
+                .and_then(|rs| p384::ecdsa::Signature::from_slice(&rs).ok());
+            let key = p384::ecdsa::VerifyingKey::from_sec1_bytes(point).ok();
+            match (key, sig) {
+                (Some(key), Some(sig)) => key.verify_prehash(&prehash, &sig).is_ok(),
+                _ => false,
+            }
+        }
+        _ => false,
+    })
+}
+
+fn ed25519_verify(key: &[u8], msg: &[u8], sig: &[u8]) -> bool {
+    let key = match <&[u8; 32]>::try_from(key) {
+        Ok(bytes) => ed25519_dalek::VerifyingKey::from_bytes(bytes),
+        Err(_) => return false,
+    };
+    let sig = ed25519_dalek::Signature::from_slice(sig);
+    match (key, sig) {
+        (Ok(key), Ok(sig)) => key.verify_strict(msg, &sig).is_ok(),
+        _ => false,
+    }
+}
+
+fn check_signature(child: &CertInfo, issuer: &CertInfo) -> Result<()> {
+    let scheme = match scheme(child.signature_algorithm)? {
+        Some(scheme) => scheme,
+        None => {
+            let oid = der::oid_to_string(child.signature_oid()?)?;
+            return Err(VerifyError::UnsupportedAlgorithm(describe(child.subject), oid));
+        }
+    };
+    let (msg, sig) = (child.tbs, child.signature);
+    let ok = match (&scheme, PublicKey::parse(issuer.spki)?) {
+        (&Scheme::Pkcs1(_), PublicKey::Rsa { modulus, exponent }) |
+        (&Scheme::Pss(..), PublicKey::Rsa { modulus, exponent }) =>
+            rsa_verify(modulus, exponent, &scheme, msg, sig),
+        (&Scheme::Ecdsa(hash), PublicKey::Ec { curve, point }) =>
+            ecdsa_verify(curve, point, hash, msg, sig)?,
+        (&Scheme::Ed25519, PublicKey::Ed25519(key)) => ed25519_verify(key, msg, sig),
+        _ => false,
+    };
+    if ok {
+        Ok(())
+    } else {
+        Err(VerifyError::BadSignature(describe(child.subject)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{verify, VerifyError, VerifyOptions};
+    use collect::CertData;
+    use structured::{Certificate, Object, Trust, TrustLevel, Usage};
+    use time::Time;
+    use x509::CertInfo;
+    use Error;
+
+    const ROOT: &[u8] = include_bytes!("../tests/data/verify/root.der");
+    const INT_P384: &[u8] = include_bytes!("../tests/data/verify/int-p384.der");
+    const INT_P256: &[u8] = include_bytes!("../tests/data/verify/int-p256.der");
+    const LEAF: &[u8] = include_bytes!("../tests/data/verify/leaf.der");
+    const INT_ED25519: &[u8] = include_bytes!("../tests/data/verify/int-ed25519.der");
+    const LEAF_ED25519: &[u8] = include_bytes!("../tests/data/verify/leaf-ed25519.der");
+    const LEAF_OF_LEAF: &[u8] = include_bytes!("../tests/data/verify/leaf-of-leaf.der");
+    const SUB_INT: &[u8] = include_bytes!("../tests/data/verify/sub-int.der");
+    const DEEP_LEAF: &[u8] = include_bytes!("../tests/data/verify/deep-leaf.der");
+
+    fn trust(label: &str, der: &[u8], level: TrustLevel) -> Trust {
+        let info = CertInfo::parse(der).unwrap();
+        Trust {
+            label: label.to_owned(),
+            issuer: info.issuer.to_vec().into(),
+            serial: info.serial.to_vec().into(),
+            tls_server_trust: level,
+            email_trust: level,
+            code_signing_trust: TrustLevel::MustVerify,
+            md5: None,
+            sha1: None,
+        }
+    }
+
+    fn store(extra: Vec<Object>) -> CertData {
+        let root = Certificate::from_der("Test Root".to_owned(), ROOT.to_vec()).unwrap();
+        let objs = vec![Object::Certificate(root),
+                        Object::Trust(trust("Test Root", ROOT, TrustLevel::TrustedDelegator))];
+        CertData::from_iter(objs.into_iter().chain(extra).map(Ok::<_, Error>)).unwrap()
+    }
+
+    fn opts() -> VerifyOptions {
+        VerifyOptions { time: Time::from_ymd_hms(2025, 6, 1, 0, 0, 0).unwrap(),
+                        ..VerifyOptions::default() }
+    }
+
+    #[test]
+    fn test_good_paths() {
+        let data = store(vec![]);
+        let path = verify(&data, LEAF, &[INT_P256, INT_ED25519, INT_P384], &opts()).unwrap();
+        assert_eq!(path.chain, vec![LEAF, INT_P256, INT_P384, ROOT]);
+        assert_eq!(path.root.label, "Test Root");
+
+        let path = verify(&data, LEAF_ED25519, &[INT_P256, INT_ED25519], &opts()).unwrap();
+        assert_eq!(path.chain, vec![LEAF_ED25519, INT_ED25519, ROOT]);
+    }
+
+    #[test]
+    fn test_bad_paths() {
+        let data = store(vec![]);
+        let ints = &[INT_P384, INT_P256, SUB_INT, LEAF];
+        let err = |leaf, ints: &[&[u8]], opts| verify(&data, leaf, ints, &opts).unwrap_err();
+
+        match err(LEAF, &[INT_P256], opts()) {
+            VerifyError::UnknownIssuer(ref name) => assert!(name.contains("P-384"), "{}", name),
+            other => panic!("{:?}", other),
+        }
+        match err(LEAF_OF_LEAF, ints, opts()) {
+            VerifyError::NotCa(ref name) => assert!(name.contains("leaf"), "{}", name),
+            other => panic!("{:?}", other),
+        }
+        match err(DEEP_LEAF, ints, opts()) {
+            VerifyError::PathTooLong(ref name) => assert!(name.contains("P-256"), "{}", name),
+            other => panic!("{:?}", other),
+        }
+        let late = VerifyOptions { time: Time::from_ymd_hms(2050, 1, 1, 0, 0, 0).unwrap(),
+                                   ..opts() };
+        match err(LEAF, ints, late) {
+            VerifyError::Expired(..) => (),
+            other => panic!("{:?}", other),
+        }
+
+        let mut tampered = LEAF.to_vec();
+        let info = CertInfo::parse(LEAF).unwrap();
+        let offset = info.serial.as_ptr() as usize - LEAF.as_ptr() as usize;
+        tampered[offset + info.serial.len() - 1] ^= 1;
+        match err(&tampered, ints, opts()) {
+            VerifyError::BadSignature(_) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_distrust() {
+        let data = store(vec![Object::Trust(trust("Bad Intermediate", INT_P256,
+                                                  TrustLevel::Distrust))]);
+        match verify(&data, LEAF, &[INT_P384, INT_P256], &opts()).unwrap_err() {
+            VerifyError::Distrusted(_, ref label) => assert_eq!(label, "Bad Intermediate"),
+            other => panic!("{:?}", other),
+        }
+        // Only for the usages it says.
+        let code = VerifyOptions { usage: Usage::CodeSigning, ..opts() };
+        match verify(&data, LEAF, &[INT_P384, INT_P256], &code).unwrap_err() {
+            VerifyError::UnknownIssuer(_) => (),
+            other => panic!("{:?}", other),
+        }
+
+        let mut root = Certificate::from_der("Test Root".to_owned(), ROOT.to_vec()).unwrap();
+        root.server_distrust_after = Time::from_ymd_hms(2023, 1, 1, 0, 0, 0);
+        let objs = vec![Object::Certificate(root),
+                        Object::Trust(trust("Test Root", ROOT, TrustLevel::TrustedDelegator))];
+        let data = CertData::from_iter(objs.into_iter().map(Ok::<_, Error>)).unwrap();
+        match verify(&data, LEAF, &[INT_P384, INT_P256], &opts()).unwrap_err() {
+            VerifyError::DistrustedAfter(ref label, _) => assert_eq!(label, "Test Root"),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_junk_in_store() {
+        let mut junk = Certificate::from_der("Junk".to_owned(), ROOT.to_vec()).unwrap();
+        junk.cert = b"\x30\x03\x02\x01\x00".to_vec().into();
+        let data = store(vec![Object::Certificate(junk)]);
+        let path = verify(&data, LEAF, &[INT_P384, INT_P256], &opts()).unwrap();
+        assert_eq!(path.root.label, "Test Root");
+
+        // The caller's own certificates still have to parse.
+        match verify(&data, b"\x30\x00", &[INT_P384, INT_P256], &opts()).unwrap_err() {
+            VerifyError::BadCertificate(_) => (),
+            other => panic!("{:?}", other),
+        }
+        match verify(&data, LEAF, &[b"\x30\x00", INT_P384, INT_P256], &opts()).unwrap_err() {
+            VerifyError::BadCertificate(_) => (),
+            other => panic!("{:?}", other),
+        }
+    }
+}
@@ -0,0 +1,389 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Splits a DER certificate into its fields.  Names, keys and most
+// extensions are left as raw DER for the caller to interpret.
+
+use constraints::NameConstraints;
+use der::{self, tag, DerError, Tlv};
+use time::Time;
+
+/// Contents of some OBJECT IDENTIFIERs that come up.
+pub mod oid {
+    /// id-kp-serverAuth (1.3.6.1.5.5.7.3.1)
+    pub const KP_SERVER_AUTH: &[u8] = b"\x2b\x06\x01\x05\x05\x07\x03\x01";
+    /// id-kp-codeSigning (1.3.6.1.5.5.7.3.3)
+    pub const KP_CODE_SIGNING: &[u8] = b"\x2b\x06\x01\x05\x05\x07\x03\x03";
+    /// id-kp-emailProtection (1.3.6.1.5.5.7.3.4)
+    pub const KP_EMAIL_PROTECTION: &[u8] = b"\x2b\x06\x01\x05\x05\x07\x03\x04";
+    /// id-ce-subjectKeyIdentifier (2.5.29.14)
+    pub const SUBJECT_KEY_IDENTIFIER: &[u8] = b"\x55\x1d\x0e";
+    /// id-ce-basicConstraints (2.5.29.19)
+    pub const BASIC_CONSTRAINTS: &[u8] = b"\x55\x1d\x13";
+    /// id-ce-authorityKeyIdentifier (2.5.29.35)
+    pub const AUTHORITY_KEY_IDENTIFIER: &[u8] = b"\x55\x1d\x23";
+    /// id-ce-nameConstraints (2.5.29.30)
+    pub const NAME_CONSTRAINTS: &[u8] = b"\x55\x1d\x1e";
+    /// id-ce-extKeyUsage (2.5.29.37)
+    pub const EXT_KEY_USAGE: &[u8] = b"\x55\x1d\x25";
+    /// anyExtendedKeyUsage (2.5.29.37.0)
+    pub const ANY_EXT_KEY_USAGE: &[u8] = b"\x55\x1d\x25\x00";
+    /// rsaEncryption (1.2.840.113549.1.1.1)
+    pub const RSA_ENCRYPTION: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01";
+    /// id-ecPublicKey (1.2.840.10045.2.1)
+    pub const EC_PUBLIC_KEY: &[u8] = b"\x2a\x86\x48\xce\x3d\x02\x01";
+    /// id-Ed25519 (1.3.101.112)
+    pub const ED25519: &[u8] = b"\x2b\x65\x70";
+    /// id-Ed448 (1.3.101.113)
+    pub const ED448: &[u8] = b"\x2b\x65\x71";
+    /// prime256v1 / P-256 (1.2.840.10045.3.1.7)
+    pub const CURVE_P256: &[u8] = b"\x2a\x86\x48\xce\x3d\x03\x01\x07";
+    /// secp384r1 / P-384 (1.3.132.0.34)
+    pub const CURVE_P384: &[u8] = b"\x2b\x81\x04\x00\x22";
+    /// secp521r1 / P-521 (1.3.132.0.35)
+    pub const CURVE_P521: &[u8] = b"\x2b\x81\x04\x00\x23";
+    /// md2WithRSAEncryption (1.2.840.113549.1.1.2)
+    pub const MD2_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x02";
+    /// md5WithRSAEncryption (1.2.840.113549.1.1.4)
+    pub const MD5_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x04";
+    /// sha1WithRSAEncryption (1.2.840.113549.1.1.5)
+    pub const SHA1_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x05";
+    /// RSASSA-PSS (1.2.840.113549.1.1.10)
+    pub const RSASSA_PSS: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0a";
+    /// sha256WithRSAEncryption (1.2.840.113549.1.1.11)
+    pub const SHA256_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b";
+    /// sha384WithRSAEncryption (1.2.840.113549.1.1.12)
+    pub const SHA384_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0c";
+    /// sha512WithRSAEncryption (1.2.840.113549.1.1.13)
+    pub const SHA512_WITH_RSA: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0d";
+    /// id-mgf1 (1.2.840.113549.1.1.8)
+    pub const MGF1: &[u8] = b"\x2a\x86\x48\x86\xf7\x0d\x01\x01\x08";
+    /// ecdsa-with-SHA1 (1.2.840.10045.4.1)
+    pub const ECDSA_WITH_SHA1: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x01";
+    /// ecdsa-with-SHA256 (1.2.840.10045.4.3.2)
+    pub const ECDSA_WITH_SHA256: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x02";
+    /// ecdsa-with-SHA384 (1.2.840.10045.4.3.3)
+    pub const ECDSA_WITH_SHA384: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x03";
+    /// ecdsa-with-SHA512 (1.2.840.10045.4.3.4)
+    pub const ECDSA_WITH_SHA512: &[u8] = b"\x2a\x86\x48\xce\x3d\x04\x03\x04";
+    /// id-sha256 (2.16.840.1.101.3.4.2.1)
+    pub const SHA256: &[u8] = b"\x60\x86\x48\x01\x65\x03\x04\x02\x01";
+    /// id-sha384 (2.16.840.1.101.3.4.2.2)
+    pub const SHA384: &[u8] = b"\x60\x86\x48\x01\x65\x03\x04\x02\x02";
+    /// id-sha512 (2.16.840.1.101.3.4.2.3)
+    pub const SHA512: &[u8] = b"\x60\x86\x48\x01\x65\x03\x04\x02\x03";
+    /// id-dsa-with-sha1 (1.2.840.10040.4.3)
+    pub const DSA_WITH_SHA1: &[u8] = b"\x2a\x86\x48\xce\x38\x04\x03";
+    /// OpenSSL's "rejected purposes" pseudo-extension, as used by
+    /// p11-kit (1.3.6.1.4.1.3319.6.10.16); same syntax as extKeyUsage.
+    pub const OPENSSL_REJECT: &[u8] = b"\x2b\x06\x01\x04\x01\x99\x77\x06\x0a\x10";
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Extension<'a> {
+    /// Contents of the OBJECT IDENTIFIER.
+    pub oid: &'a [u8],
+    pub critical: bool,
+    /// Contents of the OCTET STRING wrapping the extension value.
+    pub value: &'a [u8],
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CertInfo<'a> {
+    /// The entire `tbsCertificate`, which is what the signature covers.
+    pub tbs: &'a [u8],
+    /// As encoded: 0 for v1, 2 for v3.
+    pub version: u64,
+    /// The DER-encoded INTEGER, as in `CKA_SERIAL_NUMBER`.
+    pub serial: &'a [u8],
+    /// The DER-encoded AlgorithmIdentifier of the signature.
+    pub signature_algorithm: &'a [u8],
+    /// The DER-encoded Name, as in `CKA_ISSUER`.
+    pub issuer: &'a [u8],
+    pub not_before: Time,
+    pub not_after: Time,
+    /// The DER-encoded Name, as in `CKA_SUBJECT`.
+    pub subject: &'a [u8],
+    /// The DER-encoded SubjectPublicKeyInfo.
+    pub spki: &'a [u8],
+    pub extensions: Vec<Extension<'a>>,
+    pub signature: &'a [u8],
+}
+
+fn parse_time(tlv: Tlv) -> der::Result<Time> {
+    let t = match tlv.tag {
+        tag::UTC_TIME => Time::from_utc_time(tlv.value),
+        tag::GENERALIZED_TIME => Time::from_generalized_time(tlv.value),
+        got => return Err(DerError::UnexpectedTag(tag::UTC_TIME, got)),
+    };
+    t.ok_or(DerError::BadValue("time"))
+}
+
+fn parse_extensions(tlv: Tlv) -> der::Result<Vec<Extension>> {
+    let mut exts = Vec::new();
+    let mut outer = tlv.reader();
+    let mut seq = outer.read(tag::SEQUENCE)?.reader();
+    outer.finish()?;
+    while !seq.is_empty() {
+        let mut ext = seq.read(tag::SEQUENCE)?.reader();
+        let oid = ext.read(tag::OID)?.value;
+        let critical = match ext.read_optional(tag::BOOLEAN)? {
+            None => false,
+            Some(b) => b.value != [0],
+        };
+        let value = ext.read(tag::OCTET_STRING)?.value;
+        ext.finish()?;
+        exts.push(Extension {
+            oid,
+            critical,
+            value,
+        });
+    }
+    Ok(exts)
+}
+
+impl<'a> CertInfo<'a> {
+    pub fn parse(der: &'a [u8]) -> der::Result<CertInfo<'a>> {
+        let mut cert = der::parse_one(der, tag::SEQUENCE)?.reader();
+        let tbs_tlv = cert.read(tag::SEQUENCE)?;
+        let _outer_alg = cert.read(tag::SEQUENCE)?;
+        let signature = der::bit_string_bytes(cert.read(tag::BIT_STRING)?.value)?;
+        cert.finish()?;
+
+        let mut tbs = tbs_tlv.reader();
+        let version = match tbs.read_optional(tag::context(0))? {
+            None => 0,
+            Some(v) => {
+                let mut rd = v.reader();
+                let n = der::small_uint(rd.read(tag::INTEGER)?.value)?;
+                rd.finish()?;
+                n
+            }
+        };
+        let serial = tbs.read(tag::INTEGER)?.raw;
+        let signature_algorithm = tbs.read(tag::SEQUENCE)?.raw;
+        let issuer = tbs.read(tag::SEQUENCE)?.raw;
+        let mut validity = tbs.read(tag::SEQUENCE)?.reader();
+        let not_before = parse_time(validity.read_any()?)?;
+        let not_after = parse_time(validity.read_any()?)?;
+        validity.finish()?;
+        let subject = tbs.read(tag::SEQUENCE)?.raw;
+        let spki = tbs.read(tag::SEQUENCE)?.raw;
+        let _issuer_uid = tbs.read_optional(tag::context_primitive(1))?;
+        let _subject_uid = tbs.read_optional(tag::context_primitive(2))?;
+        let extensions = match tbs.read_optional(tag::context(3))? {
+            None => Vec::new(),
+            Some(tlv) => parse_extensions(tlv)?,
+        };
+        tbs.finish()?;
+
+        Ok(CertInfo {
+            tbs: tbs_tlv.raw,
+            version,
+            serial,
+            signature_algorithm,
+            issuer,
+            not_before,
+            not_after,
+            subject,
+            spki,
+            extensions,
+            signature,
+        })
+    }
+
+    pub fn extension(&self, oid: &[u8]) -> Option<&Extension<'a>> {
+        self.extensions.iter().find(|ext| ext.oid == oid)
+    }
+
+    /// The contents of the signature algorithm's OBJECT IDENTIFIER.
+    pub fn signature_oid(&self) -> der::Result<&'a [u8]> {
+        let mut alg = der::parse_one(self.signature_algorithm, tag::SEQUENCE)?.reader();
+        Ok(alg.read(tag::OID)?.value)
+    }
+
+    /// basicConstraints, if present, as `cA` and `pathLenConstraint`.
+    pub fn basic_constraints(&self) -> der::Result<Option<(bool, Option<u64>)>> {
+        let ext = match self.extension(oid::BASIC_CONSTRAINTS) {
+            None => return Ok(None),
+            Some(ext) => ext,
+        };
+        let mut seq = der::parse_one(ext.value, tag::SEQUENCE)?.reader();
+        let ca = match seq.read_optional(tag::BOOLEAN)? {
+            None => false,
+            Some(b) => b.value != [0],
+        };
+        let path_len = match seq.read_optional(tag::INTEGER)? {
+            None => None,
+            Some(n) => Some(der::small_uint(n.value)?),
+        };
+        seq.finish()?;
+        Ok(Some((ca, path_len)))
+    }
+
+    /// The nameConstraints, if present.
+    pub fn name_constraints(&self) -> der::Result<Option<NameConstraints<'a>>> {
+        match self.extension(oid::NAME_CONSTRAINTS) {
+            None => Ok(None),
+            Some(ext) => Ok(Some(NameConstraints::parse(ext.value)?)),
+        }
+    }
+
+    /// The subjectKeyIdentifier, if present.
+    pub fn subject_key_id(&self) -> der::Result<Option<&'a [u8]>> {
+        match self.extension(oid::SUBJECT_KEY_IDENTIFIER) {
+            None => Ok(None),
+            Some(ext) => Ok(Some(der::parse_one(ext.value, tag::OCTET_STRING)?.value)),
+        }
+    }
+
+    /// The keyIdentifier in the authorityKeyIdentifier, if present.
+    pub fn authority_key_id(&self) -> der::Result<Option<&'a [u8]>> {
+        let ext = match self.extension(oid::AUTHORITY_KEY_IDENTIFIER) {
+            None => return Ok(None),
+            Some(ext) => ext,
+        };
+        let mut seq = der::parse_one(ext.value, tag::SEQUENCE)?.reader();
+        Ok(seq.read_optional(tag::context_primitive(0))?.map(|id| id.value))
+    }
+}
+
+/// A SubjectPublicKeyInfo, split up as far as this crate cares about.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PublicKey<'a> {
+    /// Big-endian magnitudes, without leading zeros.
+    Rsa { modulus: &'a [u8], exponent: &'a [u8] },
+    /// The curve's OID contents and the encoded point.
+    Ec { curve: &'a [u8], point: &'a [u8] },
+    Ed25519(&'a [u8]),
+    Ed448(&'a [u8]),
+    /// Contents of the algorithm OID, for anything else.
+    Other(&'a [u8]),
+}
+
+impl<'a> PublicKey<'a> {
+    /// Parses a DER SubjectPublicKeyInfo, like `CertInfo::spki`.
+    pub fn parse(spki: &'a [u8]) -> der::Result<PublicKey<'a>> {
+        let mut rd = der::parse_one(spki, tag::SEQUENCE)?.reader();
+        let mut alg = rd.read(tag::SEQUENCE)?.reader();
+        let key = der::bit_string_bytes(rd.read(tag::BIT_STRING)?.value)?;
+        rd.finish()?;
+        let alg_oid = alg.read(tag::OID)?.value;
+        Ok(match alg_oid {
+            oid::RSA_ENCRYPTION => {
+                let mut rsa = der::parse_one(key, tag::SEQUENCE)?.reader();
+                let modulus = der::unsigned_int(rsa.read(tag::INTEGER)?.value)?;
+                let exponent = der::unsigned_int(rsa.read(tag::INTEGER)?.value)?;
+                rsa.finish()?;
+                PublicKey::Rsa { modulus, exponent }
+            }
+            oid::EC_PUBLIC_KEY => {
+                // Only namedCurve parameters; nobody uses the others.
+                let curve = alg.read(tag::OID)?.value;
+                PublicKey::Ec { curve, point: key }
+            }
+            oid::ED25519 => PublicKey::Ed25519(key),
+            oid::ED448 => PublicKey::Ed448(key),
+            other => PublicKey::Other(other),
+        })
+    }
+
+    /// A short name for the algorithm: "RSA", "EC", "Ed25519", "Ed448",
+    /// or the dotted OID.
+    pub fn algorithm(&self) -> String {
+        match *self {
+            PublicKey::Rsa { .. } => "RSA".to_owned(),
+            PublicKey::Ec { .. } => "EC".to_owned(),
+            PublicKey::Ed25519(_) => "Ed25519".to_owned(),
+            PublicKey::Ed448(_) => "Ed448".to_owned(),
+            PublicKey::Other(oid) => der::oid_to_string(oid).unwrap_or_else(|_| "?".to_owned()),
+        }
+    }
+
+    /// The NIST name of an EC key's curve, if it's one of the usual ones.
+    pub fn curve_name(&self) -> Option<&'static str> {
+        match *self {
+            PublicKey::Ec { curve: oid::CURVE_P256, .. } => Some("P-256"),
+            PublicKey::Ec { curve: oid::CURVE_P384, .. } => Some("P-384"),
+            PublicKey::Ec { curve: oid::CURVE_P521, .. } => Some("P-521"),
+            _ => None,
+        }
+    }
+
+    /// The key size in bits, as usually quoted: the modulus length for
+    /// RSA, the field size for EC.
+    pub fn bits(&self) -> Option<u32> {
+        match *self {
+            PublicKey::Rsa { modulus, .. } => modulus.first().map(|&top| {
+                modulus.len() as u32 * 8 - top.leading_zeros()
+            }),
+            PublicKey::Ec { .. } => match self.curve_name() {
+                Some("P-256") => Some(256),
+                Some("P-384") => Some(384),
+                Some("P-521") => Some(521),
+                _ => None,
+            },
+            PublicKey::Ed25519(_) => Some(256),
+            PublicKey::Ed448(_) => Some(448),
+            PublicKey::Other(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{oid, CertInfo, PublicKey};
+    use collect::CertData;
+    use reader::ObjectIter;
+    use time::Time;
+
+    #[test]
+    fn test_parse() {
+        let fixture = &include_bytes!("../tests/data/certdata.txt")[..];
+        let data = CertData::from_iter(ObjectIter::new(fixture)).unwrap();
+        let cert = data.certs().iter().find(|c| c.label == "ACCVRAIZ1").unwrap();
+        let info = CertInfo::parse(&cert.cert).unwrap();
+        assert_eq!(info.version, 2);
+        assert_eq!(info.serial, &cert.serial[..]);
+        assert_eq!(info.subject, &cert.subject[..]);
+        assert_eq!(info.not_before, Time::from_ymd_hms(2011, 5, 5, 9, 37, 37).unwrap());
+        assert!(info.extension(b"\x55\x1d\x13").unwrap().critical);
+        assert_eq!(info.signature_oid().unwrap(), oid::SHA1_WITH_RSA);
+        assert_eq!(info.basic_constraints().unwrap(), Some((true, None)));
+        let ski = info.subject_key_id().unwrap().unwrap();
+        assert_eq!(ski[..4], [0xd2, 0x87, 0xb4, 0xe3]);
+        assert_eq!(info.authority_key_id().unwrap(), Some(ski));
+
+        let key = PublicKey::parse(info.spki).unwrap();
+        assert_eq!((key.algorithm(), key.bits()), ("RSA".to_owned(), Some(4096)));
+        match key {
+            PublicKey::Rsa { exponent, .. } => assert_eq!(exponent, b"\x01\x00\x01"),
+            _ => unreachable!(),
+        }
+        for &(label, curve, bits) in &[("Amazon Root CA 3", "P-256", 256),
+                                          ("Amazon Root CA 4", "P-384", 384)] {
+            let cert = data.certs().iter().find(|c| c.label == label).unwrap();
+            let key = PublicKey::parse(cert.x509().unwrap().spki).unwrap();
+            assert_eq!((key.curve_name(), key.bits()), (Some(curve), Some(bits)));
+        }
+    }
+
+    #[test]
+    fn test_other_keys() {
+        // SEQUENCE { SEQUENCE { id-Ed25519 }, BIT STRING (32 bytes) }
+        let spki = [&b"\x30\x2a\x30\x05\x06\x03\x2b\x65\x70\x03\x21\x00"[..], &[7; 32]].concat();
+        let key = PublicKey::parse(&spki).unwrap();
+        assert_eq!(key, PublicKey::Ed25519(&[7; 32]));
+        assert_eq!(key.algorithm(), "Ed25519");
+        // ...and id-Ed448, BIT STRING (57 bytes)
+        let spki = [&b"\x30\x43\x30\x05\x06\x03\x2b\x65\x71\x03\x3a\x00"[..], &[7; 57]].concat();
+        let key = PublicKey::parse(&spki).unwrap();
+        assert_eq!(key, PublicKey::Ed448(&[7; 57]));
+        assert_eq!((key.algorithm(), key.bits()), ("Ed448".to_owned(), Some(448)));
+        let spki = b"\x30\x0c\x30\x07\x06\x05\x2b\x0e\x03\x02\x0c\x03\x01\x00";
+        let key = PublicKey::parse(spki).unwrap();
+        assert_eq!(key, PublicKey::Other(b"\x2b\x0e\x03\x02\x0c"));
+        assert_eq!((key.algorithm(), key.bits()), ("1.3.14.3.2.12".to_owned(), None));
+    }
+}
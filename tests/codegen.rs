@@ -0,0 +1,82 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at https://mozilla.org/MPL/2.0/. */
+
+// Compiles what `codegen` writes (tests/data/generated.rs, which its
+// own tests keep up to date) and checks it against the parsed fixture.
+
+extern crate nss_certdata_parser;
+
+use nss_certdata_parser::codegen::AnchorParts;
+use nss_certdata_parser::{CertData, ObjectIter, Time, Usage};
+
+// The shape of webpki's `TrustAnchor`.
+pub struct TrustAnchor<'a> {
+    pub subject: &'a [u8],
+    pub spki: &'a [u8],
+    pub name_constraints: Option<&'a [u8]>,
+}
+
+mod generated {
+    use super::TrustAnchor;
+
+    include!("data/generated.rs");
+}
+
+fn fixture() -> CertData {
+    CertData::from_iter(ObjectIter::new(&include_bytes!("data/certdata.txt")[..])).unwrap()
+}
+
+#[test]
+fn test_trust_anchors() {
+    let data = fixture();
+    let labels: Vec<_> = generated::TLS_SERVER_ROOTS.iter().map(|anchor| {
+        let cert = data.certs().iter().find(|cert| {
+            let info = cert.x509().unwrap();
+            let parts = AnchorParts::from_cert_info(&info).unwrap();
+            (parts.subject, parts.spki, parts.name_constraints)
+                == (anchor.subject, anchor.spki, anchor.name_constraints)
+        }).unwrap();
+        assert!(data.trusted_certs(Usage::TlsServer).contains(&cert));
+        &cert.label[..]
+    }).collect();
+    assert_eq!(labels, vec!["ACCVRAIZ1", "E-Tugra Certification Authority",
+                            "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1"]);
+    // NSS constrains TUBITAK's root to Turkish public-sector domains.
+    assert!(generated::TLS_SERVER_ROOTS[2].name_constraints.is_some());
+}
+
+#[test]
+fn test_der() {
+    let data = fixture();
+    let accv = data.certs().iter().find(|cert| cert.label == "ACCVRAIZ1").unwrap();
+    assert_eq!(generated::EMAIL_ROOTS, &[&accv.cert[..]]);
+}
+
+#[test]
+fn test_distrusts() {
+    let data = fixture();
+    let distrusts = generated::TLS_SERVER_DISTRUSTS;
+    assert_eq!(distrusts.len(), 3);
+    assert!(distrusts.windows(2).all(|w| w[0] < w[1]));
+    for &(issuer, serial) in distrusts {
+        let trust = data.trust_for(issuer, serial).unwrap();
+        assert!(trust.label.starts_with("Explicitly Distrust Example "));
+    }
+}
+
+#[test]
+fn test_cert_data_ref() {
+    let data = fixture();
+    let compiled = generated::CERTDATA.to_cert_data();
+    assert_eq!(compiled.certs().len(), 4);
+    assert_eq!(compiled.trusts().len(), 6);
+    for cert in compiled.certs() {
+        let original = data.certs().iter().find(|c| c.label == cert.label).unwrap();
+        assert_eq!(cert, original);
+        assert_eq!(compiled.trust_for_cert(cert), data.trust_for_cert(original));
+    }
+    let tugra = generated::CERTDATA.certs().iter()
+        .find(|cert| cert.label == "E-Tugra Certification Authority").unwrap();
+    assert_eq!(tugra.distrust_after(Usage::TlsServer), Time::from_ymd_hms(2023, 2, 1, 0, 0, 0));
+}
@@ -0,0 +1,836 @@
+// Generated from certdata.txt by codegen::tests::test_generated.
+pub static TLS_SERVER_ROOTS: &[TrustAnchor<'static>] = &[
+    // "ACCVRAIZ1"
+    // SHA-256: 9A:6E:C0:12:E1:A7:DA:9D:BE:34:19:4D:47:8A:D7:C0:DB:18:22:FB:07:1D:F1:29:81:49:6E:D1:04:38:41:13
+    TrustAnchor {
+        subject: b"\x31\x12\x30\x10\x06\x03\x55\x04\x03\x0c\x09\x41\x43\x43\x56\x52\
+                   \x41\x49\x5a\x31\x31\x10\x30\x0e\x06\x03\x55\x04\x0b\x0c\x07\x50\
+                   \x4b\x49\x41\x43\x43\x56\x31\x0d\x30\x0b\x06\x03\x55\x04\x0a\x0c\
+                   \x04\x41\x43\x43\x56\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\
+                   \x45\x53",
+        spki: b"\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01\x05\x00\x03\
+                \x82\x02\x0f\x00\x30\x82\x02\x0a\x02\x82\x02\x01\x00\x9b\xa9\xab\
+                \xbf\x61\x4a\x97\xaf\x2f\x97\x66\x9a\x74\x5f\xd0\xd9\x96\xfd\xcf\
+                \xe2\xe4\x66\xef\x1f\x1f\x47\x33\xc2\x44\xa3\xdf\x9a\xde\x1f\xb5\
+                \x54\xdd\x15\x7c\x69\x35\x11\x6f\xbb\xc8\x0c\x8e\x6a\x18\x1e\xd8\
+                \x8f\xd9\x16\xbc\x10\x48\x36\x5c\xf0\x63\xb3\x90\x5a\x5c\x24\x37\
+                \xd7\xa3\xd6\xcb\x09\x71\xb9\xf1\x01\x72\x84\xb0\x7d\xdb\x4d\x80\
+                \xcd\xfc\xd3\x6f\xc9\xf8\xda\xb6\x0e\x82\xd2\x45\x85\xa8\x1b\x68\
+                \xa8\x3d\xe8\xf4\x44\x6c\xbd\xa1\xc2\xcb\x03\xbe\x8c\x3e\x13\x00\
+                \x84\xdf\x4a\x48\xc0\xe3\x22\x0a\xe8\xe9\x37\xa7\x18\x4c\xb1\x09\
+                \x0d\x23\x56\x7f\x04\x4d\xd9\x17\x84\x18\xa5\xc8\xda\x40\x94\x73\
+                \xeb\xce\x0e\x57\x3c\x03\x81\x3a\x9d\x0a\xa1\x57\x43\x69\xac\x57\
+                \x6d\x79\x90\x78\xe5\xb5\xb4\x3b\xd8\xbc\x4c\x8d\x28\xa1\xa7\xa3\
+                \xa7\xba\x02\x4e\x25\xd1\x2a\xae\xed\xae\x03\x22\xb8\x6b\x20\x0f\
+                \x30\x28\x54\x95\x7f\xe0\xee\xce\x0a\x66\x9d\xd1\x40\x2d\x6e\x22\
+                \xaf\x9d\x1a\xc1\x05\x19\xd2\x6f\xc0\xf2\x9f\xf8\x7b\xb3\x02\x42\
+                \xfb\x50\xa9\x1d\x2d\x93\x0f\x23\xab\xc6\xc1\x0f\x92\xff\xd0\xa2\
+                \x15\xf5\x53\x09\x71\x1c\xff\x45\x13\x84\xe6\x26\x5e\xf8\xe0\x88\
+                \x1c\x0a\xfc\x16\xb6\xa8\x73\x06\xb8\xf0\x63\x84\x02\xa0\xc6\x5a\
+                \xec\xe7\x74\xdf\x70\xae\xa3\x83\x25\xea\xd6\xc7\x97\x87\x93\xa7\
+                \xc6\x8a\x8a\x33\x97\x60\x37\x10\x3e\x97\x3e\x6e\x29\x15\xd6\xa1\
+                \x0f\xd1\x88\x2c\x12\x9f\x6f\xaa\xa4\xc6\x42\xeb\x41\xa2\xe3\x95\
+                \x43\xd3\x01\x85\x6d\x8e\xbb\x3b\xf3\x23\x36\xc7\xfe\x3b\xe0\xa1\
+                \x25\x07\x48\xab\xc9\x89\x74\xff\x08\x8f\x80\xbf\xc0\x96\x65\xf3\
+                \xee\xec\x4b\x68\xbd\x9d\x88\xc3\x31\xb3\x40\xf1\xe8\xcf\xf6\x38\
+                \xbb\x9c\xe4\xd1\x7f\xd4\xe5\x58\x9b\x7c\xfa\xd4\xf3\x0e\x9b\x75\
+                \x91\xe4\xba\x52\x2e\x19\x7e\xd1\xf5\xcd\x5a\x19\xfc\xba\x06\xf6\
+                \xfb\x52\xa8\x4b\x99\x04\xdd\xf8\xf9\xb4\x8b\x50\xa3\x4e\x62\x89\
+                \xf0\x87\x24\xfa\x83\x42\xc1\x87\xfa\xd5\x2d\x29\x2a\x5a\x71\x7a\
+                \x64\x6a\xd7\x27\x60\x63\x0d\xdb\xce\x49\xf5\x8d\x1f\x90\x89\x32\
+                \x17\xf8\x73\x43\xb8\xd2\x5a\x93\x86\x61\xd6\xe1\x75\x0a\xea\x79\
+                \x66\x76\x88\x4f\x71\xeb\x04\x25\xd6\x0a\x5a\x7a\x93\xe5\xb9\x4b\
+                \x17\x40\x0f\xb1\xb6\xb9\xf5\xde\x4f\xdc\xe0\xb3\xac\x3b\x11\x70\
+                \x60\x84\x4a\x43\x6e\x99\x20\xc0\x29\x71\x0a\xc0\x65\x02\x03\x01\
+                \x00\x01",
+        name_constraints: None,
+    },
+    // "E-Tugra Certification Authority"
+    // SHA-256: B0:BF:D5:2B:B0:D7:D9:BD:92:BF:5D:4D:C1:3D:A2:55:C0:2C:54:2F:37:83:65:EA:89:39:11:F5:5E:55:F2:3C
+    TrustAnchor {
+        subject: b"\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\x31\x0f\x30\
+                   \x0d\x06\x03\x55\x04\x07\x0c\x06\x41\x6e\x6b\x61\x72\x61\x31\x40\
+                   \x30\x3e\x06\x03\x55\x04\x0a\x0c\x37\x45\x2d\x54\x75\xc4\x9f\x72\
+                   \x61\x20\x45\x42\x47\x20\x42\x69\x6c\x69\xc5\x9f\x69\x6d\x20\x54\
+                   \x65\x6b\x6e\x6f\x6c\x6f\x6a\x69\x6c\x65\x72\x69\x20\x76\x65\x20\
+                   \x48\x69\x7a\x6d\x65\x74\x6c\x65\x72\x69\x20\x41\x2e\xc5\x9e\x2e\
+                   \x31\x26\x30\x24\x06\x03\x55\x04\x0b\x0c\x1d\x45\x2d\x54\x75\x67\
+                   \x72\x61\x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\
+                   \x20\x4d\x65\x72\x6b\x65\x7a\x69\x31\x28\x30\x26\x06\x03\x55\x04\
+                   \x03\x0c\x1f\x45\x2d\x54\x75\x67\x72\x61\x20\x43\x65\x72\x74\x69\
+                   \x66\x69\x63\x61\x74\x69\x6f\x6e\x20\x41\x75\x74\x68\x6f\x72\x69\
+                   \x74\x79",
+        spki: b"\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01\x05\x00\x03\
+                \x82\x02\x0f\x00\x30\x82\x02\x0a\x02\x82\x02\x01\x00\xe2\xf5\x3f\
+                \x93\x05\x51\x1e\x85\x62\x54\x5e\x7a\x0b\xf5\x18\x07\x83\xae\x7e\
+                \xaf\x7c\xf7\xd4\x8a\x6b\xa5\x63\x43\x39\xb9\x4b\xf7\xc3\xc6\x64\
+                \x89\x3d\x94\x2e\x54\x80\x52\x39\x39\x07\x4b\x4b\xdd\x85\x07\x76\
+                \x87\xcc\xbf\x2f\x95\x4c\xcc\x7d\xa7\x3d\xbc\x47\x0f\x98\x70\xf8\
+                \x8c\x85\x1e\x74\x8e\x92\x6d\x1b\x40\xd1\x99\x0d\xbb\x75\x6e\xc8\
+                \xa9\x6b\x9a\xc0\x84\x31\xaf\xca\x43\xcb\xeb\x2b\x34\xe8\x8f\x97\
+                \x6b\x01\x9b\xd5\x0e\x4a\x08\xaa\x5b\x92\x74\x85\x43\xd3\x80\xae\
+                \xa1\x88\x5b\xae\xb3\xea\x5e\xcb\x16\x9a\x77\x44\xc8\xa1\xf6\x54\
+                \x68\xce\xde\x8f\x97\x2b\xba\x5b\x40\x02\x0c\x64\x17\xc0\xb5\x93\
+                \xcd\xe1\xf1\x13\x66\xce\x0c\x79\xef\xd1\x91\x28\xab\x5f\xa0\x12\
+                \x52\x30\x73\x19\x8e\x8f\xe1\x8c\x07\xa2\xc3\xbb\x4a\xf0\xea\x1f\
+                \x15\xa8\xee\x25\xcc\xa4\x46\xf8\x1b\x22\xef\xb3\x0e\x43\xba\x2c\
+                \x24\xb8\xc5\x2c\x5c\xd4\x1c\xf8\x5d\x64\xbd\xc3\x93\x5e\x28\xa7\
+                \x3f\x27\xf1\x8e\x1e\xd3\x2a\x50\x05\xa3\x55\xd9\xcb\xe7\x39\x53\
+                \xc0\x98\x9e\x8c\x54\x62\x8b\x26\xb0\xf7\x7d\x8d\x7c\xe4\xc6\x9e\
+                \x66\x42\x55\x82\x47\xe7\xb2\x58\x8d\x66\xf7\x07\x7c\x2e\x36\xe6\
+                \x50\x1c\x3f\xdb\x43\x24\xc5\xbf\x86\x47\x79\xb3\x79\x1c\xf7\x5a\
+                \xf4\x13\xec\x6c\xf8\x3f\xe2\x59\x1f\x95\xee\x42\x3e\xb9\xad\xa8\
+                \x32\x85\x49\x97\x46\xfe\x4b\x31\x8f\x5a\xcb\xad\x74\x47\x1f\xe9\
+                \x91\xb7\xdf\x28\x04\x22\xa0\xd4\x0f\x5d\xe2\x79\x4f\xea\x6c\x85\
+                \x86\xbd\xa8\xa6\xce\xe4\xfa\xc3\xe1\xb3\xae\xde\x3c\x51\xee\xcb\
+                \x13\x7c\x01\x7f\x84\x0e\x5d\x51\x94\x9e\x13\x0c\xb6\x2e\xa5\x4c\
+                \xf9\x39\x70\x36\x6f\x96\xca\x2e\x0c\x44\x55\xc5\xca\xfa\x5d\x02\
+                \xa3\xdf\xd6\x64\x8c\x5a\xb3\x01\x0a\xa9\xb5\x0a\x47\x17\xff\xef\
+                \x91\x40\x2a\x8e\xa1\x46\x3a\x31\x98\xe5\x11\xfc\xcc\xbb\x49\x56\
+                \x8a\xfc\xb9\xd0\x61\x9a\x6f\x65\x6c\xe6\xc3\xcb\x3e\x75\x49\xfe\
+                \x8f\xa7\xe2\x89\xc5\x67\xd7\x9d\x46\x13\x4e\x31\x76\x3b\x24\xb3\
+                \x9e\x11\x65\x86\xab\x7f\xef\x1d\xd4\xf8\xbc\xe7\xac\x5a\x5c\xb7\
+                \x5a\x47\x5c\x55\xce\x55\xb4\x22\x71\x5b\x5b\x0b\xf0\xcf\xdc\xa0\
+                \x61\x64\xea\xa9\xd7\x68\x0a\x63\xa7\xe0\x0d\x3f\xa0\xaf\xd3\xaa\
+                \xd2\x7e\xef\x51\xa0\xe6\x51\x2b\x55\x92\x15\x17\x53\xcb\xb7\x66\
+                \x0e\x66\x4c\xf8\xf9\x75\x4c\x90\xe7\x12\x70\xc7\x45\x02\x03\x01\
+                \x00\x01",
+        name_constraints: None,
+    },
+    // "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1"
+    // SHA-256: 46:ED:C3:68:90:46:D5:3A:45:3F:B3:10:4A:B8:0D:CA:EC:65:8B:26:60:EA:16:29:DD:7E:86:79:90:64:87:16
+    TrustAnchor {
+        subject: b"\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\x31\x18\x30\
+                   \x16\x06\x03\x55\x04\x07\x13\x0f\x47\x65\x62\x7a\x65\x20\x2d\x20\
+                   \x4b\x6f\x63\x61\x65\x6c\x69\x31\x42\x30\x40\x06\x03\x55\x04\x0a\
+                   \x13\x39\x54\x75\x72\x6b\x69\x79\x65\x20\x42\x69\x6c\x69\x6d\x73\
+                   \x65\x6c\x20\x76\x65\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\x6a\x69\x6b\
+                   \x20\x41\x72\x61\x73\x74\x69\x72\x6d\x61\x20\x4b\x75\x72\x75\x6d\
+                   \x75\x20\x2d\x20\x54\x55\x42\x49\x54\x41\x4b\x31\x2d\x30\x2b\x06\
+                   \x03\x55\x04\x0b\x13\x24\x4b\x61\x6d\x75\x20\x53\x65\x72\x74\x69\
+                   \x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\x65\x72\x6b\x65\x7a\x69\
+                   \x20\x2d\x20\x4b\x61\x6d\x75\x20\x53\x4d\x31\x36\x30\x34\x06\x03\
+                   \x55\x04\x03\x13\x2d\x54\x55\x42\x49\x54\x41\x4b\x20\x4b\x61\x6d\
+                   \x75\x20\x53\x4d\x20\x53\x53\x4c\x20\x4b\x6f\x6b\x20\x53\x65\x72\
+                   \x74\x69\x66\x69\x6b\x61\x73\x69\x20\x2d\x20\x53\x75\x72\x75\x6d\
+                   \x20\x31",
+        spki: b"\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01\x05\x00\x03\
+                \x82\x01\x0f\x00\x30\x82\x01\x0a\x02\x82\x01\x01\x00\xaf\x75\x30\
+                \x33\xaa\xbb\x6b\xd3\x99\x2c\x12\x37\x84\xd9\x8d\x7b\x97\x80\xd3\
+                \x6e\xe7\xff\x9b\x50\x95\x3e\x90\x95\x56\x42\xd7\x19\x7c\x26\x84\
+                \x8d\x92\xfa\x01\x1d\x3a\x0f\xe2\x64\x38\xb7\x8c\xbc\xe8\x88\xf9\
+                \x8b\x24\xab\x2e\xa3\xf5\x37\xe4\x40\x8e\x18\x25\x79\x83\x75\x1f\
+                \x3b\xff\x6c\xa8\xc5\xc6\x56\xf8\xb4\xed\x8a\x44\xa3\xab\x6c\x4c\
+                \xfc\x1d\xd0\xdc\xef\x68\xbd\xcf\xe4\xaa\xce\xf0\x55\xf7\xa2\x34\
+                \xd4\x83\x6b\x37\x7c\x1c\xc2\xfe\xb5\x03\xec\x57\xce\xbc\xb4\xb5\
+                \xc5\xed\x00\x0f\x53\x37\x2a\x4d\xf4\x4f\x0c\x83\xfb\x86\xcf\xcb\
+                \xfe\x8c\x4e\xbd\x87\xf9\xa7\x8b\x21\x57\x9c\x7a\xdf\x03\x67\x89\
+                \x2c\x9d\x97\x61\xa7\x10\xb8\x55\x90\x7f\x0e\x2d\x27\x38\x74\xdf\
+                \xe7\xfd\xda\x4e\x12\xe3\x4d\x15\x22\x02\xc8\xe0\xe0\xfc\x0f\xad\
+                \x8a\xd7\xc9\x54\x50\xcc\x3b\x0f\xca\x16\x80\x84\xd0\x51\x56\xc3\
+                \x8e\x56\x7f\x89\x22\x33\x2f\xe6\x85\x0a\xbd\xa5\xa8\x1b\x36\xde\
+                \xd3\xdc\x2c\x6d\x3b\xc7\x13\xbd\x59\x23\x2c\xe6\xe5\xa4\xf7\xd8\
+                \x0b\xed\xea\x90\x40\x44\xa8\x95\xbb\x93\xd5\xd0\x80\x34\xb6\x46\
+                \x78\x0e\x1f\x00\x93\x46\xe1\xee\xe9\xf9\xec\x4f\x17\x02\x03\x01\
+                \x00\x01",
+        name_constraints: Some(b"\xa0\x63\x30\x09\x82\x07\x2e\x67\x6f\x76\x2e\x74\x72\x30\x09\x82\
+                                 \x07\x2e\x6b\x31\x32\x2e\x74\x72\x30\x09\x82\x07\x2e\x70\x6f\x6c\
+                                 \x2e\x74\x72\x30\x09\x82\x07\x2e\x6d\x69\x6c\x2e\x74\x72\x30\x09\
+                                 \x82\x07\x2e\x74\x73\x6b\x2e\x74\x72\x30\x09\x82\x07\x2e\x6b\x65\
+                                 \x70\x2e\x74\x72\x30\x09\x82\x07\x2e\x62\x65\x6c\x2e\x74\x72\x30\
+                                 \x09\x82\x07\x2e\x65\x64\x75\x2e\x74\x72\x30\x09\x82\x07\x2e\x6f\
+                                 \x72\x67\x2e\x74\x72"),
+    },
+];
+pub static EMAIL_ROOTS: &[&[u8]] = &[
+    // "ACCVRAIZ1"
+    b"\x30\x82\x07\xd3\x30\x82\x05\xbb\xa0\x03\x02\x01\x02\x02\x08\x5e\
+      \xc3\xb7\xa6\x43\x7f\xa4\xe0\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\
+      \x0d\x01\x01\x05\x05\x00\x30\x42\x31\x12\x30\x10\x06\x03\x55\x04\
+      \x03\x0c\x09\x41\x43\x43\x56\x52\x41\x49\x5a\x31\x31\x10\x30\x0e\
+      \x06\x03\x55\x04\x0b\x0c\x07\x50\x4b\x49\x41\x43\x43\x56\x31\x0d\
+      \x30\x0b\x06\x03\x55\x04\x0a\x0c\x04\x41\x43\x43\x56\x31\x0b\x30\
+      \x09\x06\x03\x55\x04\x06\x13\x02\x45\x53\x30\x1e\x17\x0d\x31\x31\
+      \x30\x35\x30\x35\x30\x39\x33\x37\x33\x37\x5a\x17\x0d\x33\x30\x31\
+      \x32\x33\x31\x30\x39\x33\x37\x33\x37\x5a\x30\x42\x31\x12\x30\x10\
+      \x06\x03\x55\x04\x03\x0c\x09\x41\x43\x43\x56\x52\x41\x49\x5a\x31\
+      \x31\x10\x30\x0e\x06\x03\x55\x04\x0b\x0c\x07\x50\x4b\x49\x41\x43\
+      \x43\x56\x31\x0d\x30\x0b\x06\x03\x55\x04\x0a\x0c\x04\x41\x43\x43\
+      \x56\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x45\x53\x30\x82\
+      \x02\x22\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01\x05\
+      \x00\x03\x82\x02\x0f\x00\x30\x82\x02\x0a\x02\x82\x02\x01\x00\x9b\
+      \xa9\xab\xbf\x61\x4a\x97\xaf\x2f\x97\x66\x9a\x74\x5f\xd0\xd9\x96\
+      \xfd\xcf\xe2\xe4\x66\xef\x1f\x1f\x47\x33\xc2\x44\xa3\xdf\x9a\xde\
+      \x1f\xb5\x54\xdd\x15\x7c\x69\x35\x11\x6f\xbb\xc8\x0c\x8e\x6a\x18\
+      \x1e\xd8\x8f\xd9\x16\xbc\x10\x48\x36\x5c\xf0\x63\xb3\x90\x5a\x5c\
+      \x24\x37\xd7\xa3\xd6\xcb\x09\x71\xb9\xf1\x01\x72\x84\xb0\x7d\xdb\
+      \x4d\x80\xcd\xfc\xd3\x6f\xc9\xf8\xda\xb6\x0e\x82\xd2\x45\x85\xa8\
+      \x1b\x68\xa8\x3d\xe8\xf4\x44\x6c\xbd\xa1\xc2\xcb\x03\xbe\x8c\x3e\
+      \x13\x00\x84\xdf\x4a\x48\xc0\xe3\x22\x0a\xe8\xe9\x37\xa7\x18\x4c\
+      \xb1\x09\x0d\x23\x56\x7f\x04\x4d\xd9\x17\x84\x18\xa5\xc8\xda\x40\
+      \x94\x73\xeb\xce\x0e\x57\x3c\x03\x81\x3a\x9d\x0a\xa1\x57\x43\x69\
+      \xac\x57\x6d\x79\x90\x78\xe5\xb5\xb4\x3b\xd8\xbc\x4c\x8d\x28\xa1\
+      \xa7\xa3\xa7\xba\x02\x4e\x25\xd1\x2a\xae\xed\xae\x03\x22\xb8\x6b\
+      \x20\x0f\x30\x28\x54\x95\x7f\xe0\xee\xce\x0a\x66\x9d\xd1\x40\x2d\
+      \x6e\x22\xaf\x9d\x1a\xc1\x05\x19\xd2\x6f\xc0\xf2\x9f\xf8\x7b\xb3\
+      \x02\x42\xfb\x50\xa9\x1d\x2d\x93\x0f\x23\xab\xc6\xc1\x0f\x92\xff\
+      \xd0\xa2\x15\xf5\x53\x09\x71\x1c\xff\x45\x13\x84\xe6\x26\x5e\xf8\
+      \xe0\x88\x1c\x0a\xfc\x16\xb6\xa8\x73\x06\xb8\xf0\x63\x84\x02\xa0\
+      \xc6\x5a\xec\xe7\x74\xdf\x70\xae\xa3\x83\x25\xea\xd6\xc7\x97\x87\
+      \x93\xa7\xc6\x8a\x8a\x33\x97\x60\x37\x10\x3e\x97\x3e\x6e\x29\x15\
+      \xd6\xa1\x0f\xd1\x88\x2c\x12\x9f\x6f\xaa\xa4\xc6\x42\xeb\x41\xa2\
+      \xe3\x95\x43\xd3\x01\x85\x6d\x8e\xbb\x3b\xf3\x23\x36\xc7\xfe\x3b\
+      \xe0\xa1\x25\x07\x48\xab\xc9\x89\x74\xff\x08\x8f\x80\xbf\xc0\x96\
+      \x65\xf3\xee\xec\x4b\x68\xbd\x9d\x88\xc3\x31\xb3\x40\xf1\xe8\xcf\
+      \xf6\x38\xbb\x9c\xe4\xd1\x7f\xd4\xe5\x58\x9b\x7c\xfa\xd4\xf3\x0e\
+      \x9b\x75\x91\xe4\xba\x52\x2e\x19\x7e\xd1\xf5\xcd\x5a\x19\xfc\xba\
+      \x06\xf6\xfb\x52\xa8\x4b\x99\x04\xdd\xf8\xf9\xb4\x8b\x50\xa3\x4e\
+      \x62\x89\xf0\x87\x24\xfa\x83\x42\xc1\x87\xfa\xd5\x2d\x29\x2a\x5a\
+      \x71\x7a\x64\x6a\xd7\x27\x60\x63\x0d\xdb\xce\x49\xf5\x8d\x1f\x90\
+      \x89\x32\x17\xf8\x73\x43\xb8\xd2\x5a\x93\x86\x61\xd6\xe1\x75\x0a\
+      \xea\x79\x66\x76\x88\x4f\x71\xeb\x04\x25\xd6\x0a\x5a\x7a\x93\xe5\
+      \xb9\x4b\x17\x40\x0f\xb1\xb6\xb9\xf5\xde\x4f\xdc\xe0\xb3\xac\x3b\
+      \x11\x70\x60\x84\x4a\x43\x6e\x99\x20\xc0\x29\x71\x0a\xc0\x65\x02\
+      \x03\x01\x00\x01\xa3\x82\x02\xcb\x30\x82\x02\xc7\x30\x7d\x06\x08\
+      \x2b\x06\x01\x05\x05\x07\x01\x01\x04\x71\x30\x6f\x30\x4c\x06\x08\
+      \x2b\x06\x01\x05\x05\x07\x30\x02\x86\x40\x68\x74\x74\x70\x3a\x2f\
+      \x2f\x77\x77\x77\x2e\x61\x63\x63\x76\x2e\x65\x73\x2f\x66\x69\x6c\
+      \x65\x61\x64\x6d\x69\x6e\x2f\x41\x72\x63\x68\x69\x76\x6f\x73\x2f\
+      \x63\x65\x72\x74\x69\x66\x69\x63\x61\x64\x6f\x73\x2f\x72\x61\x69\
+      \x7a\x61\x63\x63\x76\x31\x2e\x63\x72\x74\x30\x1f\x06\x08\x2b\x06\
+      \x01\x05\x05\x07\x30\x01\x86\x13\x68\x74\x74\x70\x3a\x2f\x2f\x6f\
+      \x63\x73\x70\x2e\x61\x63\x63\x76\x2e\x65\x73\x30\x1d\x06\x03\x55\
+      \x1d\x0e\x04\x16\x04\x14\xd2\x87\xb4\xe3\xdf\x37\x27\x93\x55\xf6\
+      \x56\xea\x81\xe5\x36\xcc\x8c\x1e\x3f\xbd\x30\x0f\x06\x03\x55\x1d\
+      \x13\x01\x01\xff\x04\x05\x30\x03\x01\x01\xff\x30\x1f\x06\x03\x55\
+      \x1d\x23\x04\x18\x30\x16\x80\x14\xd2\x87\xb4\xe3\xdf\x37\x27\x93\
+      \x55\xf6\x56\xea\x81\xe5\x36\xcc\x8c\x1e\x3f\xbd\x30\x82\x01\x73\
+      \x06\x03\x55\x1d\x20\x04\x82\x01\x6a\x30\x82\x01\x66\x30\x82\x01\
+      \x62\x06\x04\x55\x1d\x20\x00\x30\x82\x01\x58\x30\x82\x01\x22\x06\
+      \x08\x2b\x06\x01\x05\x05\x07\x02\x02\x30\x82\x01\x14\x1e\x82\x01\
+      \x10\x00\x41\x00\x75\x00\x74\x00\x6f\x00\x72\x00\x69\x00\x64\x00\
+      \x61\x00\x64\x00\x20\x00\x64\x00\x65\x00\x20\x00\x43\x00\x65\x00\
+      \x72\x00\x74\x00\x69\x00\x66\x00\x69\x00\x63\x00\x61\x00\x63\x00\
+      \x69\x00\xf3\x00\x6e\x00\x20\x00\x52\x00\x61\x00\xed\x00\x7a\x00\
+      \x20\x00\x64\x00\x65\x00\x20\x00\x6c\x00\x61\x00\x20\x00\x41\x00\
+      \x43\x00\x43\x00\x56\x00\x20\x00\x28\x00\x41\x00\x67\x00\x65\x00\
+      \x6e\x00\x63\x00\x69\x00\x61\x00\x20\x00\x64\x00\x65\x00\x20\x00\
+      \x54\x00\x65\x00\x63\x00\x6e\x00\x6f\x00\x6c\x00\x6f\x00\x67\x00\
+      \xed\x00\x61\x00\x20\x00\x79\x00\x20\x00\x43\x00\x65\x00\x72\x00\
+      \x74\x00\x69\x00\x66\x00\x69\x00\x63\x00\x61\x00\x63\x00\x69\x00\
+      \xf3\x00\x6e\x00\x20\x00\x45\x00\x6c\x00\x65\x00\x63\x00\x74\x00\
+      \x72\x00\xf3\x00\x6e\x00\x69\x00\x63\x00\x61\x00\x2c\x00\x20\x00\
+      \x43\x00\x49\x00\x46\x00\x20\x00\x51\x00\x34\x00\x36\x00\x30\x00\
+      \x31\x00\x31\x00\x35\x00\x36\x00\x45\x00\x29\x00\x2e\x00\x20\x00\
+      \x43\x00\x50\x00\x53\x00\x20\x00\x65\x00\x6e\x00\x20\x00\x68\x00\
+      \x74\x00\x74\x00\x70\x00\x3a\x00\x2f\x00\x2f\x00\x77\x00\x77\x00\
+      \x77\x00\x2e\x00\x61\x00\x63\x00\x63\x00\x76\x00\x2e\x00\x65\x00\
+      \x73\x30\x30\x06\x08\x2b\x06\x01\x05\x05\x07\x02\x01\x16\x24\x68\
+      \x74\x74\x70\x3a\x2f\x2f\x77\x77\x77\x2e\x61\x63\x63\x76\x2e\x65\
+      \x73\x2f\x6c\x65\x67\x69\x73\x6c\x61\x63\x69\x6f\x6e\x5f\x63\x2e\
+      \x68\x74\x6d\x30\x55\x06\x03\x55\x1d\x1f\x04\x4e\x30\x4c\x30\x4a\
+      \xa0\x48\xa0\x46\x86\x44\x68\x74\x74\x70\x3a\x2f\x2f\x77\x77\x77\
+      \x2e\x61\x63\x63\x76\x2e\x65\x73\x2f\x66\x69\x6c\x65\x61\x64\x6d\
+      \x69\x6e\x2f\x41\x72\x63\x68\x69\x76\x6f\x73\x2f\x63\x65\x72\x74\
+      \x69\x66\x69\x63\x61\x64\x6f\x73\x2f\x72\x61\x69\x7a\x61\x63\x63\
+      \x76\x31\x5f\x64\x65\x72\x2e\x63\x72\x6c\x30\x0e\x06\x03\x55\x1d\
+      \x0f\x01\x01\xff\x04\x04\x03\x02\x01\x06\x30\x17\x06\x03\x55\x1d\
+      \x11\x04\x10\x30\x0e\x81\x0c\x61\x63\x63\x76\x40\x61\x63\x63\x76\
+      \x2e\x65\x73\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x05\
+      \x05\x00\x03\x82\x02\x01\x00\x97\x31\x02\x9f\xe7\xfd\x43\x67\x48\
+      \x44\x14\xe4\x29\x87\xed\x4c\x28\x66\xd0\x8f\x35\xda\x4d\x61\xb7\
+      \x4a\x97\x4d\xb5\xdb\x90\xe0\x05\x2e\x0e\xc6\x79\xd0\xf2\x97\x69\
+      \x0f\xbd\x04\x47\xd9\xbe\xdb\xb5\x29\xda\x9b\xd9\xae\xa9\x99\xd5\
+      \xd3\x3c\x30\x93\xf5\x8d\xa1\xa8\xfc\x06\x8d\x44\xf4\xca\x16\x95\
+      \x7c\x33\xdc\x62\x8b\xa8\x37\xf8\x27\xd8\x09\x2d\x1b\xef\xc8\x14\
+      \x27\x20\xa9\x64\x44\xff\x2e\xd6\x75\xaa\x6c\x4d\x60\x40\x19\x49\
+      \x43\x54\x63\xda\xe2\xcc\xba\x66\xe5\x4f\x44\x7a\x5b\xd9\x6a\x81\
+      \x2b\x40\xd5\x7f\xf9\x01\x27\x58\x2c\xc8\xed\x48\x91\x7c\x3f\xa6\
+      \x00\xcf\xc4\x29\x73\x11\x36\xde\x86\x19\x3e\x9d\xee\x19\x8a\x1b\
+      \xd5\xb0\xed\x8e\x3d\x9c\x2a\xc0\x0d\xd8\x3d\x66\xe3\x3c\x0d\xbd\
+      \xd5\x94\x5c\xe2\xe2\xa7\x35\x1b\x04\x00\xf6\x3f\x5a\x8d\xea\x43\
+      \xbd\x5f\x89\x1d\xa9\xc1\xb0\xcc\x99\xe2\x4d\x00\x0a\xda\xc9\x27\
+      \x5b\xe7\x13\x90\x5c\xe4\xf5\x33\xa2\x55\x6d\xdc\xe0\x09\x4d\x2f\
+      \xb1\x26\x5b\x27\x75\x00\x09\xc4\x62\x77\x29\x08\x5f\x9e\x59\xac\
+      \xb6\x7e\xad\x9f\x54\x30\x22\x03\xc1\x1e\x71\x64\xfe\xf9\x38\x0a\
+      \x96\x18\xdd\x02\x14\xac\x23\xcb\x06\x1c\x1e\xa4\x7d\x8d\x0d\xde\
+      \x27\x41\xe8\xad\xda\x15\xb7\xb0\x23\xdd\x2b\xa8\xd3\xda\x25\x87\
+      \xed\xe8\x55\x44\x4d\x88\xf4\x36\x7e\x84\x9a\x78\xac\xf7\x0e\x56\
+      \x49\x0e\xd6\x33\x25\xd6\x84\x50\x42\x6c\x20\x12\x1d\x2a\xd5\xbe\
+      \xbc\xf2\x70\x81\xa4\x70\x60\xbe\x05\xb5\x9b\x9e\x04\x44\xbe\x61\
+      \x23\xac\xe9\xa5\x24\x8c\x11\x80\x94\x5a\xa2\xa2\xb9\x49\xd2\xc1\
+      \xdc\xd1\xa7\xed\x31\x11\x2c\x9e\x19\xa6\xee\xe1\x55\xe1\xc0\xea\
+      \xcf\x0d\x84\xe4\x17\xb7\xa2\x7c\xa5\xde\x55\x25\x06\xee\xcc\xc0\
+      \x87\x5c\x40\xda\xcc\x95\x3f\x55\xe0\x35\xc7\xb8\x84\xbe\xb4\x5d\
+      \xcd\x7a\x83\x01\x72\xee\x87\xe6\x5f\x1d\xae\xb5\x85\xc6\x26\xdf\
+      \xe6\xc1\x9a\xe9\x1e\x02\x47\x9f\x2a\xa8\x6d\xa9\x5b\xcf\xec\x45\
+      \x77\x7f\x98\x27\x9a\x32\x5d\x2a\xe3\x84\xee\xc5\x98\x66\x2f\x96\
+      \x20\x1d\xdd\xd8\xc3\x27\xd7\xb0\xf9\xfe\xd9\x7d\xcd\xd0\x9f\x8f\
+      \x0b\x14\x58\x51\x9f\x2f\x8b\xc3\x38\x2d\xde\xe8\x8f\xd6\x8d\x87\
+      \xa4\xf5\x56\x43\x16\x99\x2c\xf4\xa4\x56\xb4\x34\xb8\x61\x37\xc9\
+      \xc2\x58\x80\x1b\xa0\x97\xa1\xfc\x59\x8d\xe9\x11\xf6\xd1\x0f\x4b\
+      \x55\x34\x46\x2a\x8b\x86\x3b",
+];
+pub static TLS_SERVER_DISTRUSTS: &[(&[u8], &[u8])] = &[
+    // "Explicitly Distrust Example Compromised CA"
+    (b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+       \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+       \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+       \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+       \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+       \x64\x20\x43\x41",
+     b"\x02\x06\x4a\x3c\x00\xde\x00\x00"),
+    // "Explicitly Distrust Example Mis-issued Intermediate"
+    (b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+       \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+       \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+       \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+       \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+       \x64\x20\x43\x41",
+     b"\x02\x06\x4a\x3c\x00\xde\x00\x01"),
+    // "Explicitly Distrust Example Fraudulent Server"
+    (b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+       \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+       \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+       \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+       \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+       \x64\x20\x43\x41",
+     b"\x02\x06\x4a\x3c\x00\xde\x00\x02"),
+];
+pub static CERTDATA: ::nss_certdata_parser::CertDataRef<'static> = ::nss_certdata_parser::CertDataRef::new(&[
+    ::nss_certdata_parser::CertificateRef {
+        label: "ACCVRAIZ1",
+        cert: b"\x30\x82\x07\xd3\x30\x82\x05\xbb\xa0\x03\x02\x01\x02\x02\x08\x5e\
+                \xc3\xb7\xa6\x43\x7f\xa4\xe0\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\
+                \x0d\x01\x01\x05\x05\x00\x30\x42\x31\x12\x30\x10\x06\x03\x55\x04\
+                \x03\x0c\x09\x41\x43\x43\x56\x52\x41\x49\x5a\x31\x31\x10\x30\x0e\
+                \x06\x03\x55\x04\x0b\x0c\x07\x50\x4b\x49\x41\x43\x43\x56\x31\x0d\
+                \x30\x0b\x06\x03\x55\x04\x0a\x0c\x04\x41\x43\x43\x56\x31\x0b\x30\
+                \x09\x06\x03\x55\x04\x06\x13\x02\x45\x53\x30\x1e\x17\x0d\x31\x31\
+                \x30\x35\x30\x35\x30\x39\x33\x37\x33\x37\x5a\x17\x0d\x33\x30\x31\
+                \x32\x33\x31\x30\x39\x33\x37\x33\x37\x5a\x30\x42\x31\x12\x30\x10\
+                \x06\x03\x55\x04\x03\x0c\x09\x41\x43\x43\x56\x52\x41\x49\x5a\x31\
+                \x31\x10\x30\x0e\x06\x03\x55\x04\x0b\x0c\x07\x50\x4b\x49\x41\x43\
+                \x43\x56\x31\x0d\x30\x0b\x06\x03\x55\x04\x0a\x0c\x04\x41\x43\x43\
+                \x56\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x45\x53\x30\x82\
+                \x02\x22\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01\x05\
+                \x00\x03\x82\x02\x0f\x00\x30\x82\x02\x0a\x02\x82\x02\x01\x00\x9b\
+                \xa9\xab\xbf\x61\x4a\x97\xaf\x2f\x97\x66\x9a\x74\x5f\xd0\xd9\x96\
+                \xfd\xcf\xe2\xe4\x66\xef\x1f\x1f\x47\x33\xc2\x44\xa3\xdf\x9a\xde\
+                \x1f\xb5\x54\xdd\x15\x7c\x69\x35\x11\x6f\xbb\xc8\x0c\x8e\x6a\x18\
+                \x1e\xd8\x8f\xd9\x16\xbc\x10\x48\x36\x5c\xf0\x63\xb3\x90\x5a\x5c\
+                \x24\x37\xd7\xa3\xd6\xcb\x09\x71\xb9\xf1\x01\x72\x84\xb0\x7d\xdb\
+                \x4d\x80\xcd\xfc\xd3\x6f\xc9\xf8\xda\xb6\x0e\x82\xd2\x45\x85\xa8\
+                \x1b\x68\xa8\x3d\xe8\xf4\x44\x6c\xbd\xa1\xc2\xcb\x03\xbe\x8c\x3e\
+                \x13\x00\x84\xdf\x4a\x48\xc0\xe3\x22\x0a\xe8\xe9\x37\xa7\x18\x4c\
+                \xb1\x09\x0d\x23\x56\x7f\x04\x4d\xd9\x17\x84\x18\xa5\xc8\xda\x40\
+                \x94\x73\xeb\xce\x0e\x57\x3c\x03\x81\x3a\x9d\x0a\xa1\x57\x43\x69\
+                \xac\x57\x6d\x79\x90\x78\xe5\xb5\xb4\x3b\xd8\xbc\x4c\x8d\x28\xa1\
+                \xa7\xa3\xa7\xba\x02\x4e\x25\xd1\x2a\xae\xed\xae\x03\x22\xb8\x6b\
+                \x20\x0f\x30\x28\x54\x95\x7f\xe0\xee\xce\x0a\x66\x9d\xd1\x40\x2d\
+                \x6e\x22\xaf\x9d\x1a\xc1\x05\x19\xd2\x6f\xc0\xf2\x9f\xf8\x7b\xb3\
+                \x02\x42\xfb\x50\xa9\x1d\x2d\x93\x0f\x23\xab\xc6\xc1\x0f\x92\xff\
+                \xd0\xa2\x15\xf5\x53\x09\x71\x1c\xff\x45\x13\x84\xe6\x26\x5e\xf8\
+                \xe0\x88\x1c\x0a\xfc\x16\xb6\xa8\x73\x06\xb8\xf0\x63\x84\x02\xa0\
+                \xc6\x5a\xec\xe7\x74\xdf\x70\xae\xa3\x83\x25\xea\xd6\xc7\x97\x87\
+                \x93\xa7\xc6\x8a\x8a\x33\x97\x60\x37\x10\x3e\x97\x3e\x6e\x29\x15\
+                \xd6\xa1\x0f\xd1\x88\x2c\x12\x9f\x6f\xaa\xa4\xc6\x42\xeb\x41\xa2\
+                \xe3\x95\x43\xd3\x01\x85\x6d\x8e\xbb\x3b\xf3\x23\x36\xc7\xfe\x3b\
+                \xe0\xa1\x25\x07\x48\xab\xc9\x89\x74\xff\x08\x8f\x80\xbf\xc0\x96\
+                \x65\xf3\xee\xec\x4b\x68\xbd\x9d\x88\xc3\x31\xb3\x40\xf1\xe8\xcf\
+                \xf6\x38\xbb\x9c\xe4\xd1\x7f\xd4\xe5\x58\x9b\x7c\xfa\xd4\xf3\x0e\
+                \x9b\x75\x91\xe4\xba\x52\x2e\x19\x7e\xd1\xf5\xcd\x5a\x19\xfc\xba\
+                \x06\xf6\xfb\x52\xa8\x4b\x99\x04\xdd\xf8\xf9\xb4\x8b\x50\xa3\x4e\
+                \x62\x89\xf0\x87\x24\xfa\x83\x42\xc1\x87\xfa\xd5\x2d\x29\x2a\x5a\
+                \x71\x7a\x64\x6a\xd7\x27\x60\x63\x0d\xdb\xce\x49\xf5\x8d\x1f\x90\
+                \x89\x32\x17\xf8\x73\x43\xb8\xd2\x5a\x93\x86\x61\xd6\xe1\x75\x0a\
+                \xea\x79\x66\x76\x88\x4f\x71\xeb\x04\x25\xd6\x0a\x5a\x7a\x93\xe5\
+                \xb9\x4b\x17\x40\x0f\xb1\xb6\xb9\xf5\xde\x4f\xdc\xe0\xb3\xac\x3b\
+                \x11\x70\x60\x84\x4a\x43\x6e\x99\x20\xc0\x29\x71\x0a\xc0\x65\x02\
+                \x03\x01\x00\x01\xa3\x82\x02\xcb\x30\x82\x02\xc7\x30\x7d\x06\x08\
+                \x2b\x06\x01\x05\x05\x07\x01\x01\x04\x71\x30\x6f\x30\x4c\x06\x08\
+                \x2b\x06\x01\x05\x05\x07\x30\x02\x86\x40\x68\x74\x74\x70\x3a\x2f\
+                \x2f\x77\x77\x77\x2e\x61\x63\x63\x76\x2e\x65\x73\x2f\x66\x69\x6c\
+                \x65\x61\x64\x6d\x69\x6e\x2f\x41\x72\x63\x68\x69\x76\x6f\x73\x2f\
+                \x63\x65\x72\x74\x69\x66\x69\x63\x61\x64\x6f\x73\x2f\x72\x61\x69\
+                \x7a\x61\x63\x63\x76\x31\x2e\x63\x72\x74\x30\x1f\x06\x08\x2b\x06\
+                \x01\x05\x05\x07\x30\x01\x86\x13\x68\x74\x74\x70\x3a\x2f\x2f\x6f\
+                \x63\x73\x70\x2e\x61\x63\x63\x76\x2e\x65\x73\x30\x1d\x06\x03\x55\
+                \x1d\x0e\x04\x16\x04\x14\xd2\x87\xb4\xe3\xdf\x37\x27\x93\x55\xf6\
+                \x56\xea\x81\xe5\x36\xcc\x8c\x1e\x3f\xbd\x30\x0f\x06\x03\x55\x1d\
+                \x13\x01\x01\xff\x04\x05\x30\x03\x01\x01\xff\x30\x1f\x06\x03\x55\
+                \x1d\x23\x04\x18\x30\x16\x80\x14\xd2\x87\xb4\xe3\xdf\x37\x27\x93\
+                \x55\xf6\x56\xea\x81\xe5\x36\xcc\x8c\x1e\x3f\xbd\x30\x82\x01\x73\
+                \x06\x03\x55\x1d\x20\x04\x82\x01\x6a\x30\x82\x01\x66\x30\x82\x01\
+                \x62\x06\x04\x55\x1d\x20\x00\x30\x82\x01\x58\x30\x82\x01\x22\x06\
+                \x08\x2b\x06\x01\x05\x05\x07\x02\x02\x30\x82\x01\x14\x1e\x82\x01\
+                \x10\x00\x41\x00\x75\x00\x74\x00\x6f\x00\x72\x00\x69\x00\x64\x00\
+                \x61\x00\x64\x00\x20\x00\x64\x00\x65\x00\x20\x00\x43\x00\x65\x00\
+                \x72\x00\x74\x00\x69\x00\x66\x00\x69\x00\x63\x00\x61\x00\x63\x00\
+                \x69\x00\xf3\x00\x6e\x00\x20\x00\x52\x00\x61\x00\xed\x00\x7a\x00\
+                \x20\x00\x64\x00\x65\x00\x20\x00\x6c\x00\x61\x00\x20\x00\x41\x00\
+                \x43\x00\x43\x00\x56\x00\x20\x00\x28\x00\x41\x00\x67\x00\x65\x00\
+                \x6e\x00\x63\x00\x69\x00\x61\x00\x20\x00\x64\x00\x65\x00\x20\x00\
+                \x54\x00\x65\x00\x63\x00\x6e\x00\x6f\x00\x6c\x00\x6f\x00\x67\x00\
+                \xed\x00\x61\x00\x20\x00\x79\x00\x20\x00\x43\x00\x65\x00\x72\x00\
+                \x74\x00\x69\x00\x66\x00\x69\x00\x63\x00\x61\x00\x63\x00\x69\x00\
+                \xf3\x00\x6e\x00\x20\x00\x45\x00\x6c\x00\x65\x00\x63\x00\x74\x00\
+                \x72\x00\xf3\x00\x6e\x00\x69\x00\x63\x00\x61\x00\x2c\x00\x20\x00\
+                \x43\x00\x49\x00\x46\x00\x20\x00\x51\x00\x34\x00\x36\x00\x30\x00\
+                \x31\x00\x31\x00\x35\x00\x36\x00\x45\x00\x29\x00\x2e\x00\x20\x00\
+                \x43\x00\x50\x00\x53\x00\x20\x00\x65\x00\x6e\x00\x20\x00\x68\x00\
+                \x74\x00\x74\x00\x70\x00\x3a\x00\x2f\x00\x2f\x00\x77\x00\x77\x00\
+                \x77\x00\x2e\x00\x61\x00\x63\x00\x63\x00\x76\x00\x2e\x00\x65\x00\
+                \x73\x30\x30\x06\x08\x2b\x06\x01\x05\x05\x07\x02\x01\x16\x24\x68\
+                \x74\x74\x70\x3a\x2f\x2f\x77\x77\x77\x2e\x61\x63\x63\x76\x2e\x65\
+                \x73\x2f\x6c\x65\x67\x69\x73\x6c\x61\x63\x69\x6f\x6e\x5f\x63\x2e\
+                \x68\x74\x6d\x30\x55\x06\x03\x55\x1d\x1f\x04\x4e\x30\x4c\x30\x4a\
+                \xa0\x48\xa0\x46\x86\x44\x68\x74\x74\x70\x3a\x2f\x2f\x77\x77\x77\
+                \x2e\x61\x63\x63\x76\x2e\x65\x73\x2f\x66\x69\x6c\x65\x61\x64\x6d\
+                \x69\x6e\x2f\x41\x72\x63\x68\x69\x76\x6f\x73\x2f\x63\x65\x72\x74\
+                \x69\x66\x69\x63\x61\x64\x6f\x73\x2f\x72\x61\x69\x7a\x61\x63\x63\
+                \x76\x31\x5f\x64\x65\x72\x2e\x63\x72\x6c\x30\x0e\x06\x03\x55\x1d\
+                \x0f\x01\x01\xff\x04\x04\x03\x02\x01\x06\x30\x17\x06\x03\x55\x1d\
+                \x11\x04\x10\x30\x0e\x81\x0c\x61\x63\x63\x76\x40\x61\x63\x63\x76\
+                \x2e\x65\x73\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x05\
+                \x05\x00\x03\x82\x02\x01\x00\x97\x31\x02\x9f\xe7\xfd\x43\x67\x48\
+                \x44\x14\xe4\x29\x87\xed\x4c\x28\x66\xd0\x8f\x35\xda\x4d\x61\xb7\
+                \x4a\x97\x4d\xb5\xdb\x90\xe0\x05\x2e\x0e\xc6\x79\xd0\xf2\x97\x69\
+                \x0f\xbd\x04\x47\xd9\xbe\xdb\xb5\x29\xda\x9b\xd9\xae\xa9\x99\xd5\
+                \xd3\x3c\x30\x93\xf5\x8d\xa1\xa8\xfc\x06\x8d\x44\xf4\xca\x16\x95\
+                \x7c\x33\xdc\x62\x8b\xa8\x37\xf8\x27\xd8\x09\x2d\x1b\xef\xc8\x14\
+                \x27\x20\xa9\x64\x44\xff\x2e\xd6\x75\xaa\x6c\x4d\x60\x40\x19\x49\
+                \x43\x54\x63\xda\xe2\xcc\xba\x66\xe5\x4f\x44\x7a\x5b\xd9\x6a\x81\
+                \x2b\x40\xd5\x7f\xf9\x01\x27\x58\x2c\xc8\xed\x48\x91\x7c\x3f\xa6\
+                \x00\xcf\xc4\x29\x73\x11\x36\xde\x86\x19\x3e\x9d\xee\x19\x8a\x1b\
+                \xd5\xb0\xed\x8e\x3d\x9c\x2a\xc0\x0d\xd8\x3d\x66\xe3\x3c\x0d\xbd\
+                \xd5\x94\x5c\xe2\xe2\xa7\x35\x1b\x04\x00\xf6\x3f\x5a\x8d\xea\x43\
+                \xbd\x5f\x89\x1d\xa9\xc1\xb0\xcc\x99\xe2\x4d\x00\x0a\xda\xc9\x27\
+                \x5b\xe7\x13\x90\x5c\xe4\xf5\x33\xa2\x55\x6d\xdc\xe0\x09\x4d\x2f\
+                \xb1\x26\x5b\x27\x75\x00\x09\xc4\x62\x77\x29\x08\x5f\x9e\x59\xac\
+                \xb6\x7e\xad\x9f\x54\x30\x22\x03\xc1\x1e\x71\x64\xfe\xf9\x38\x0a\
+                \x96\x18\xdd\x02\x14\xac\x23\xcb\x06\x1c\x1e\xa4\x7d\x8d\x0d\xde\
+                \x27\x41\xe8\xad\xda\x15\xb7\xb0\x23\xdd\x2b\xa8\xd3\xda\x25\x87\
+                \xed\xe8\x55\x44\x4d\x88\xf4\x36\x7e\x84\x9a\x78\xac\xf7\x0e\x56\
+                \x49\x0e\xd6\x33\x25\xd6\x84\x50\x42\x6c\x20\x12\x1d\x2a\xd5\xbe\
+                \xbc\xf2\x70\x81\xa4\x70\x60\xbe\x05\xb5\x9b\x9e\x04\x44\xbe\x61\
+                \x23\xac\xe9\xa5\x24\x8c\x11\x80\x94\x5a\xa2\xa2\xb9\x49\xd2\xc1\
+                \xdc\xd1\xa7\xed\x31\x11\x2c\x9e\x19\xa6\xee\xe1\x55\xe1\xc0\xea\
+                \xcf\x0d\x84\xe4\x17\xb7\xa2\x7c\xa5\xde\x55\x25\x06\xee\xcc\xc0\
+                \x87\x5c\x40\xda\xcc\x95\x3f\x55\xe0\x35\xc7\xb8\x84\xbe\xb4\x5d\
+                \xcd\x7a\x83\x01\x72\xee\x87\xe6\x5f\x1d\xae\xb5\x85\xc6\x26\xdf\
+                \xe6\xc1\x9a\xe9\x1e\x02\x47\x9f\x2a\xa8\x6d\xa9\x5b\xcf\xec\x45\
+                \x77\x7f\x98\x27\x9a\x32\x5d\x2a\xe3\x84\xee\xc5\x98\x66\x2f\x96\
+                \x20\x1d\xdd\xd8\xc3\x27\xd7\xb0\xf9\xfe\xd9\x7d\xcd\xd0\x9f\x8f\
+                \x0b\x14\x58\x51\x9f\x2f\x8b\xc3\x38\x2d\xde\xe8\x8f\xd6\x8d\x87\
+                \xa4\xf5\x56\x43\x16\x99\x2c\xf4\xa4\x56\xb4\x34\xb8\x61\x37\xc9\
+                \xc2\x58\x80\x1b\xa0\x97\xa1\xfc\x59\x8d\xe9\x11\xf6\xd1\x0f\x4b\
+                \x55\x34\x46\x2a\x8b\x86\x3b",
+        issuer: b"\x30\x42\x31\x12\x30\x10\x06\x03\x55\x04\x03\x0c\x09\x41\x43\x43\
+                  \x56\x52\x41\x49\x5a\x31\x31\x10\x30\x0e\x06\x03\x55\x04\x0b\x0c\
+                  \x07\x50\x4b\x49\x41\x43\x43\x56\x31\x0d\x30\x0b\x06\x03\x55\x04\
+                  \x0a\x0c\x04\x41\x43\x43\x56\x31\x0b\x30\x09\x06\x03\x55\x04\x06\
+                  \x13\x02\x45\x53",
+        serial: b"\x02\x08\x5e\xc3\xb7\xa6\x43\x7f\xa4\xe0",
+        subject: b"\x30\x42\x31\x12\x30\x10\x06\x03\x55\x04\x03\x0c\x09\x41\x43\x43\
+                   \x56\x52\x41\x49\x5a\x31\x31\x10\x30\x0e\x06\x03\x55\x04\x0b\x0c\
+                   \x07\x50\x4b\x49\x41\x43\x43\x56\x31\x0d\x30\x0b\x06\x03\x55\x04\
+                   \x0a\x0c\x04\x41\x43\x43\x56\x31\x0b\x30\x09\x06\x03\x55\x04\x06\
+                   \x13\x02\x45\x53",
+        server_distrust_after: None,
+        email_distrust_after: None,
+        mozilla_ca_policy: Some(true),
+    },
+    ::nss_certdata_parser::CertificateRef {
+        label: "Explicitly Distrust Example Compromised CA",
+        cert: b"\x30\x82\x01\x95\x30\x82\x01\x3c\xa0\x03\x02\x01\x02\x02\x06\x4a\
+                \x3c\x00\xde\x00\x00\x30\x0a\x06\x08\x2a\x86\x48\xce\x3d\x04\x03\
+                \x02\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\
+                \x31\x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\
+                \x6c\x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\
+                \x75\x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\
+                \x78\x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\
+                \x65\x64\x20\x43\x41\x30\x1e\x17\x0d\x31\x31\x30\x33\x31\x35\x30\
+                \x30\x30\x30\x30\x30\x5a\x17\x0d\x32\x31\x30\x33\x31\x32\x30\x30\
+                \x30\x30\x30\x30\x5a\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\
+                \x13\x02\x58\x58\x31\x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\
+                \x78\x61\x6d\x70\x6c\x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\
+                \x46\x69\x78\x74\x75\x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\
+                \x03\x0c\x16\x45\x78\x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\
+                \x6f\x6d\x69\x73\x65\x64\x20\x43\x41\x30\x59\x30\x13\x06\x07\x2a\
+                \x86\x48\xce\x3d\x02\x01\x06\x08\x2a\x86\x48\xce\x3d\x03\x01\x07\
+                \x03\x42\x00\x04\xb8\xfa\x1a\x4a\xcb\xd9\x00\xb7\x88\xff\x1f\x85\
+                \x24\xcc\xff\xf1\xdd\x2a\x3d\x6c\x91\x7e\x40\x09\xaf\x60\x4f\xbd\
+                \x40\x6d\xb7\x02\x9a\x5c\xc3\x2d\x14\xfc\x83\x72\x66\x84\x45\x27\
+                \x48\x1f\x7f\x06\xcb\x4f\xb3\x47\x33\xb2\x4c\xa9\x2e\x86\x1f\x72\
+                \xcc\x7c\xae\x37\x30\x0a\x06\x08\x2a\x86\x48\xce\x3d\x04\x03\x02\
+                \x03\x47\x00\x30\x44\x02\x20\x71\x2c\x3f\x89\xb0\xc4\xe0\x9e\xba\
+                \x8a\x6e\xf3\x4b\xcd\xd5\x4a\x7b\x4b\x22\xdb\x70\xc5\x0f\x74\xfc\
+                \x3d\x26\x9d\x56\x59\x8d\x7d\x02\x20\x7e\xb8\xa9\x90\x07\x72\x00\
+                \x3c\x17\xf1\x43\x7d\x33\x72\xe1\x3f\x04\x0c\x4e\xf0\x37\xeb\x05\
+                \x3e\x84\xfb\xbc\xd4\x5b\xc1\x26\x13",
+        issuer: b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+                  \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+                  \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+                  \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+                  \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+                  \x64\x20\x43\x41",
+        serial: b"\x02\x06\x4a\x3c\x00\xde\x00\x00",
+        subject: b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+                   \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+                   \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+                   \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+                   \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+                   \x64\x20\x43\x41",
+        server_distrust_after: None,
+        email_distrust_after: None,
+        mozilla_ca_policy: Some(false),
+    },
+    ::nss_certdata_parser::CertificateRef {
+        label: "E-Tugra Certification Authority",
+        cert: b"\x30\x82\x06\x4b\x30\x82\x04\x33\xa0\x03\x02\x01\x02\x02\x08\x6a\
+                \x68\x3e\x9c\x51\x9b\xcb\x53\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\
+                \x0d\x01\x01\x0b\x05\x00\x30\x81\xb2\x31\x0b\x30\x09\x06\x03\x55\
+                \x04\x06\x13\x02\x54\x52\x31\x0f\x30\x0d\x06\x03\x55\x04\x07\x0c\
+                \x06\x41\x6e\x6b\x61\x72\x61\x31\x40\x30\x3e\x06\x03\x55\x04\x0a\
+                \x0c\x37\x45\x2d\x54\x75\xc4\x9f\x72\x61\x20\x45\x42\x47\x20\x42\
+                \x69\x6c\x69\xc5\x9f\x69\x6d\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\x6a\
+                \x69\x6c\x65\x72\x69\x20\x76\x65\x20\x48\x69\x7a\x6d\x65\x74\x6c\
+                \x65\x72\x69\x20\x41\x2e\xc5\x9e\x2e\x31\x26\x30\x24\x06\x03\x55\
+                \x04\x0b\x0c\x1d\x45\x2d\x54\x75\x67\x72\x61\x20\x53\x65\x72\x74\
+                \x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\x65\x72\x6b\x65\x7a\
+                \x69\x31\x28\x30\x26\x06\x03\x55\x04\x03\x0c\x1f\x45\x2d\x54\x75\
+                \x67\x72\x61\x20\x43\x65\x72\x74\x69\x66\x69\x63\x61\x74\x69\x6f\
+                \x6e\x20\x41\x75\x74\x68\x6f\x72\x69\x74\x79\x30\x1e\x17\x0d\x31\
+                \x33\x30\x33\x30\x35\x31\x32\x30\x39\x34\x38\x5a\x17\x0d\x32\x33\
+                \x30\x33\x30\x33\x31\x32\x30\x39\x34\x38\x5a\x30\x81\xb2\x31\x0b\
+                \x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\x31\x0f\x30\x0d\x06\
+                \x03\x55\x04\x07\x0c\x06\x41\x6e\x6b\x61\x72\x61\x31\x40\x30\x3e\
+                \x06\x03\x55\x04\x0a\x0c\x37\x45\x2d\x54\x75\xc4\x9f\x72\x61\x20\
+                \x45\x42\x47\x20\x42\x69\x6c\x69\xc5\x9f\x69\x6d\x20\x54\x65\x6b\
+                \x6e\x6f\x6c\x6f\x6a\x69\x6c\x65\x72\x69\x20\x76\x65\x20\x48\x69\
+                \x7a\x6d\x65\x74\x6c\x65\x72\x69\x20\x41\x2e\xc5\x9e\x2e\x31\x26\
+                \x30\x24\x06\x03\x55\x04\x0b\x0c\x1d\x45\x2d\x54\x75\x67\x72\x61\
+                \x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\
+                \x65\x72\x6b\x65\x7a\x69\x31\x28\x30\x26\x06\x03\x55\x04\x03\x0c\
+                \x1f\x45\x2d\x54\x75\x67\x72\x61\x20\x43\x65\x72\x74\x69\x66\x69\
+                \x63\x61\x74\x69\x6f\x6e\x20\x41\x75\x74\x68\x6f\x72\x69\x74\x79\
+                \x30\x82\x02\x22\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\
+                \x01\x05\x00\x03\x82\x02\x0f\x00\x30\x82\x02\x0a\x02\x82\x02\x01\
+                \x00\xe2\xf5\x3f\x93\x05\x51\x1e\x85\x62\x54\x5e\x7a\x0b\xf5\x18\
+                \x07\x83\xae\x7e\xaf\x7c\xf7\xd4\x8a\x6b\xa5\x63\x43\x39\xb9\x4b\
+                \xf7\xc3\xc6\x64\x89\x3d\x94\x2e\x54\x80\x52\x39\x39\x07\x4b\x4b\
+                \xdd\x85\x07\x76\x87\xcc\xbf\x2f\x95\x4c\xcc\x7d\xa7\x3d\xbc\x47\
+                \x0f\x98\x70\xf8\x8c\x85\x1e\x74\x8e\x92\x6d\x1b\x40\xd1\x99\x0d\
+                \xbb\x75\x6e\xc8\xa9\x6b\x9a\xc0\x84\x31\xaf\xca\x43\xcb\xeb\x2b\
+                \x34\xe8\x8f\x97\x6b\x01\x9b\xd5\x0e\x4a\x08\xaa\x5b\x92\x74\x85\
+                \x43\xd3\x80\xae\xa1\x88\x5b\xae\xb3\xea\x5e\xcb\x16\x9a\x77\x44\
+                \xc8\xa1\xf6\x54\x68\xce\xde\x8f\x97\x2b\xba\x5b\x40\x02\x0c\x64\
+                \x17\xc0\xb5\x93\xcd\xe1\xf1\x13\x66\xce\x0c\x79\xef\xd1\x91\x28\
+                \xab\x5f\xa0\x12\x52\x30\x73\x19\x8e\x8f\xe1\x8c\x07\xa2\xc3\xbb\
+                \x4a\xf0\xea\x1f\x15\xa8\xee\x25\xcc\xa4\x46\xf8\x1b\x22\xef\xb3\
+                \x0e\x43\xba\x2c\x24\xb8\xc5\x2c\x5c\xd4\x1c\xf8\x5d\x64\xbd\xc3\
+                \x93\x5e\x28\xa7\x3f\x27\xf1\x8e\x1e\xd3\x2a\x50\x05\xa3\x55\xd9\
+                \xcb\xe7\x39\x53\xc0\x98\x9e\x8c\x54\x62\x8b\x26\xb0\xf7\x7d\x8d\
+                \x7c\xe4\xc6\x9e\x66\x42\x55\x82\x47\xe7\xb2\x58\x8d\x66\xf7\x07\
+                \x7c\x2e\x36\xe6\x50\x1c\x3f\xdb\x43\x24\xc5\xbf\x86\x47\x79\xb3\
+                \x79\x1c\xf7\x5a\xf4\x13\xec\x6c\xf8\x3f\xe2\x59\x1f\x95\xee\x42\
+                \x3e\xb9\xad\xa8\x32\x85\x49\x97\x46\xfe\x4b\x31\x8f\x5a\xcb\xad\
+                \x74\x47\x1f\xe9\x91\xb7\xdf\x28\x04\x22\xa0\xd4\x0f\x5d\xe2\x79\
+                \x4f\xea\x6c\x85\x86\xbd\xa8\xa6\xce\xe4\xfa\xc3\xe1\xb3\xae\xde\
+                \x3c\x51\xee\xcb\x13\x7c\x01\x7f\x84\x0e\x5d\x51\x94\x9e\x13\x0c\
+                \xb6\x2e\xa5\x4c\xf9\x39\x70\x36\x6f\x96\xca\x2e\x0c\x44\x55\xc5\
+                \xca\xfa\x5d\x02\xa3\xdf\xd6\x64\x8c\x5a\xb3\x01\x0a\xa9\xb5\x0a\
+                \x47\x17\xff\xef\x91\x40\x2a\x8e\xa1\x46\x3a\x31\x98\xe5\x11\xfc\
+                \xcc\xbb\x49\x56\x8a\xfc\xb9\xd0\x61\x9a\x6f\x65\x6c\xe6\xc3\xcb\
+                \x3e\x75\x49\xfe\x8f\xa7\xe2\x89\xc5\x67\xd7\x9d\x46\x13\x4e\x31\
+                \x76\x3b\x24\xb3\x9e\x11\x65\x86\xab\x7f\xef\x1d\xd4\xf8\xbc\xe7\
+                \xac\x5a\x5c\xb7\x5a\x47\x5c\x55\xce\x55\xb4\x22\x71\x5b\x5b\x0b\
+                \xf0\xcf\xdc\xa0\x61\x64\xea\xa9\xd7\x68\x0a\x63\xa7\xe0\x0d\x3f\
+                \xa0\xaf\xd3\xaa\xd2\x7e\xef\x51\xa0\xe6\x51\x2b\x55\x92\x15\x17\
+                \x53\xcb\xb7\x66\x0e\x66\x4c\xf8\xf9\x75\x4c\x90\xe7\x12\x70\xc7\
+                \x45\x02\x03\x01\x00\x01\xa3\x63\x30\x61\x30\x1d\x06\x03\x55\x1d\
+                \x0e\x04\x16\x04\x14\x2e\xe3\xdb\xb2\x49\xd0\x9c\x54\x79\x5c\xfa\
+                \x27\x2a\xfe\xcc\x4e\xd2\xe8\x4e\x54\x30\x0f\x06\x03\x55\x1d\x13\
+                \x01\x01\xff\x04\x05\x30\x03\x01\x01\xff\x30\x1f\x06\x03\x55\x1d\
+                \x23\x04\x18\x30\x16\x80\x14\x2e\xe3\xdb\xb2\x49\xd0\x9c\x54\x79\
+                \x5c\xfa\x27\x2a\xfe\xcc\x4e\xd2\xe8\x4e\x54\x30\x0e\x06\x03\x55\
+                \x1d\x0f\x01\x01\xff\x04\x04\x03\x02\x01\x06\x30\x0d\x06\x09\x2a\
+                \x86\x48\x86\xf7\x0d\x01\x01\x0b\x05\x00\x03\x82\x02\x01\x00\x05\
+                \x37\x3a\xf4\x4d\xb7\x45\xe2\x45\x75\x24\x8f\xb6\x77\x52\xe8\x1c\
+                \xd8\x10\x93\x65\xf3\xf2\x59\x06\xa4\x3e\x1e\x29\xec\x5d\xd1\xd0\
+                \xab\x7c\xe0\x0a\x90\x48\x78\xed\x4e\x98\x03\x99\xfe\x28\x60\x91\
+                \x1d\x30\x1d\xb8\x63\x7c\xa8\xe6\x35\xb5\xfa\xd3\x61\x76\xe6\xd6\
+                \x07\x4b\xca\x69\x9a\xb2\x84\x7a\x77\x93\x45\x17\x15\x9f\x24\xd0\
+                \x98\x13\x12\xff\xbb\xa0\x2e\xfd\x4e\x4c\x87\xf8\xce\x5c\xaa\x98\
+                \x1b\x05\xe0\x00\x46\x4a\x82\x80\xa5\x33\x8b\x28\xdc\xed\x38\xd3\
+                \xdf\xe5\x3e\xe9\xfe\xfb\x59\xdd\x61\x84\x4f\xd2\x54\x96\x13\x61\
+                \x13\x3e\x8f\x80\x69\xbe\x93\x47\xb5\x35\x43\xd2\x5a\xbb\x3d\x5c\
+                \xef\xb3\x42\x47\xcd\x3b\x55\x13\x06\xb0\x09\xdb\xfd\x63\xf6\x3a\
+                \x88\x0a\x99\x6f\x7e\xe1\xce\x1b\x53\x6a\x44\x66\x23\x51\x08\x7b\
+                \xbc\x5b\x52\xa2\xfd\x06\x37\x38\x40\x61\x8f\x4a\x96\xb8\x90\x37\
+                \xf8\x66\xc7\x78\x90\x00\x15\x2e\x8b\xad\x51\x35\x53\x07\xa8\x6b\
+                \x68\xae\xf9\x4e\x3c\x07\x26\xcd\x08\x05\x70\xcc\x39\x3f\x76\xbd\
+                \xa5\xd3\x67\x26\x01\x86\xa6\x53\xd2\x60\x3b\x7c\x43\x7f\x55\x8a\
+                \xbc\x95\x1a\xc1\x28\x39\x4c\x1f\x43\xd2\x91\xf4\x72\x59\x8a\xb9\
+                \x56\xfc\x3f\xb4\x9d\xda\x70\x9c\x76\x5a\x8c\x43\x50\xee\x8e\x30\
+                \x72\x4d\xdf\xff\x49\xf7\xc6\xa9\x67\xd9\x6d\xac\x02\x11\xe2\x3a\
+                \x16\x25\xa7\x58\x08\xcb\x6f\x53\x41\x9c\x48\x38\x47\x68\x33\xd1\
+                \xd7\xc7\x8f\xd4\x74\x21\xd4\xc3\x05\x90\x7a\xff\xce\x96\x88\xb1\
+                \x15\x29\x5d\x23\xab\xd0\x60\xa1\x12\x4f\xde\xf4\x17\xcd\x32\xe5\
+                \xc9\xbf\xc8\x43\xad\xfd\x2e\x8e\xf1\xaf\xe2\xf4\x98\xfa\x12\x1f\
+                \x20\xd8\xc0\xa7\x0c\x85\xc5\x90\xf4\x3b\x2d\x96\x26\xb1\x2c\xbe\
+                \x4c\xab\xeb\xb1\xd2\x8a\xc9\xdb\x78\x13\x0f\x1e\x09\x9d\x6d\x8f\
+                \x00\x9f\x02\xda\xc1\xfa\x1f\x7a\x7a\x09\xc4\x4a\xe6\x88\x2a\x97\
+                \x9f\x89\x8b\xfd\x37\x5f\x5f\x3a\xce\x38\x59\x86\x4b\xaf\x71\x0b\
+                \xb4\xd8\xf2\x70\x4f\x9f\x32\x13\xe3\xb0\xa7\x57\xe5\xda\xda\x43\
+                \xcb\x84\x34\xf2\x28\xc4\xea\x6d\xf4\x2a\xef\xc1\x6b\x76\xda\xfb\
+                \x7e\xbb\x85\x3c\xd2\x53\xc2\x4d\xbe\x71\xe1\x45\xd1\xfd\x23\x67\
+                \x0d\x13\x75\xfb\xcf\x65\x67\x22\x9d\xae\xb0\x09\xd1\x09\xff\x1d\
+                \x34\xbf\xfe\x23\x97\x37\xd2\x39\xfa\x3d\x0d\x06\x0b\xb4\xdb\x3b\
+                \xa3\xab\x6f\x5c\x1d\xb6\x7e\xe8\xb3\x82\x34\xed\x06\x5c\x24",
+        issuer: b"\x30\x81\xb2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\
+                  \x31\x0f\x30\x0d\x06\x03\x55\x04\x07\x0c\x06\x41\x6e\x6b\x61\x72\
+                  \x61\x31\x40\x30\x3e\x06\x03\x55\x04\x0a\x0c\x37\x45\x2d\x54\x75\
+                  \xc4\x9f\x72\x61\x20\x45\x42\x47\x20\x42\x69\x6c\x69\xc5\x9f\x69\
+                  \x6d\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\x6a\x69\x6c\x65\x72\x69\x20\
+                  \x76\x65\x20\x48\x69\x7a\x6d\x65\x74\x6c\x65\x72\x69\x20\x41\x2e\
+                  \xc5\x9e\x2e\x31\x26\x30\x24\x06\x03\x55\x04\x0b\x0c\x1d\x45\x2d\
+                  \x54\x75\x67\x72\x61\x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\
+                  \x79\x6f\x6e\x20\x4d\x65\x72\x6b\x65\x7a\x69\x31\x28\x30\x26\x06\
+                  \x03\x55\x04\x03\x0c\x1f\x45\x2d\x54\x75\x67\x72\x61\x20\x43\x65\
+                  \x72\x74\x69\x66\x69\x63\x61\x74\x69\x6f\x6e\x20\x41\x75\x74\x68\
+                  \x6f\x72\x69\x74\x79",
+        serial: b"\x02\x08\x6a\x68\x3e\x9c\x51\x9b\xcb\x53",
+        subject: b"\x30\x81\xb2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\
+                   \x31\x0f\x30\x0d\x06\x03\x55\x04\x07\x0c\x06\x41\x6e\x6b\x61\x72\
+                   \x61\x31\x40\x30\x3e\x06\x03\x55\x04\x0a\x0c\x37\x45\x2d\x54\x75\
+                   \xc4\x9f\x72\x61\x20\x45\x42\x47\x20\x42\x69\x6c\x69\xc5\x9f\x69\
+                   \x6d\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\x6a\x69\x6c\x65\x72\x69\x20\
+                   \x76\x65\x20\x48\x69\x7a\x6d\x65\x74\x6c\x65\x72\x69\x20\x41\x2e\
+                   \xc5\x9e\x2e\x31\x26\x30\x24\x06\x03\x55\x04\x0b\x0c\x1d\x45\x2d\
+                   \x54\x75\x67\x72\x61\x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\
+                   \x79\x6f\x6e\x20\x4d\x65\x72\x6b\x65\x7a\x69\x31\x28\x30\x26\x06\
+                   \x03\x55\x04\x03\x0c\x1f\x45\x2d\x54\x75\x67\x72\x61\x20\x43\x65\
+                   \x72\x74\x69\x66\x69\x63\x61\x74\x69\x6f\x6e\x20\x41\x75\x74\x68\
+                   \x6f\x72\x69\x74\x79",
+        server_distrust_after: Some(::nss_certdata_parser::Time::from_unix(1675209600)),
+        email_distrust_after: Some(::nss_certdata_parser::Time::from_unix(1675209600)),
+        mozilla_ca_policy: Some(true),
+    },
+    ::nss_certdata_parser::CertificateRef {
+        label: "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1",
+        cert: b"\x30\x82\x04\x63\x30\x82\x03\x4b\xa0\x03\x02\x01\x02\x02\x01\x01\
+                \x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b\x05\x00\x30\
+                \x81\xd2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\x31\
+                \x18\x30\x16\x06\x03\x55\x04\x07\x13\x0f\x47\x65\x62\x7a\x65\x20\
+                \x2d\x20\x4b\x6f\x63\x61\x65\x6c\x69\x31\x42\x30\x40\x06\x03\x55\
+                \x04\x0a\x13\x39\x54\x75\x72\x6b\x69\x79\x65\x20\x42\x69\x6c\x69\
+                \x6d\x73\x65\x6c\x20\x76\x65\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\x6a\
+                \x69\x6b\x20\x41\x72\x61\x73\x74\x69\x72\x6d\x61\x20\x4b\x75\x72\
+                \x75\x6d\x75\x20\x2d\x20\x54\x55\x42\x49\x54\x41\x4b\x31\x2d\x30\
+                \x2b\x06\x03\x55\x04\x0b\x13\x24\x4b\x61\x6d\x75\x20\x53\x65\x72\
+                \x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\x65\x72\x6b\x65\
+                \x7a\x69\x20\x2d\x20\x4b\x61\x6d\x75\x20\x53\x4d\x31\x36\x30\x34\
+                \x06\x03\x55\x04\x03\x13\x2d\x54\x55\x42\x49\x54\x41\x4b\x20\x4b\
+                \x61\x6d\x75\x20\x53\x4d\x20\x53\x53\x4c\x20\x4b\x6f\x6b\x20\x53\
+                \x65\x72\x74\x69\x66\x69\x6b\x61\x73\x69\x20\x2d\x20\x53\x75\x72\
+                \x75\x6d\x20\x31\x30\x1e\x17\x0d\x31\x33\x31\x31\x32\x35\x30\x38\
+                \x32\x35\x35\x35\x5a\x17\x0d\x34\x33\x31\x30\x32\x35\x30\x38\x32\
+                \x35\x35\x35\x5a\x30\x81\xd2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\
+                \x13\x02\x54\x52\x31\x18\x30\x16\x06\x03\x55\x04\x07\x13\x0f\x47\
+                \x65\x62\x7a\x65\x20\x2d\x20\x4b\x6f\x63\x61\x65\x6c\x69\x31\x42\
+                \x30\x40\x06\x03\x55\x04\x0a\x13\x39\x54\x75\x72\x6b\x69\x79\x65\
+                \x20\x42\x69\x6c\x69\x6d\x73\x65\x6c\x20\x76\x65\x20\x54\x65\x6b\
+                \x6e\x6f\x6c\x6f\x6a\x69\x6b\x20\x41\x72\x61\x73\x74\x69\x72\x6d\
+                \x61\x20\x4b\x75\x72\x75\x6d\x75\x20\x2d\x20\x54\x55\x42\x49\x54\
+                \x41\x4b\x31\x2d\x30\x2b\x06\x03\x55\x04\x0b\x13\x24\x4b\x61\x6d\
+                \x75\x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\
+                \x4d\x65\x72\x6b\x65\x7a\x69\x20\x2d\x20\x4b\x61\x6d\x75\x20\x53\
+                \x4d\x31\x36\x30\x34\x06\x03\x55\x04\x03\x13\x2d\x54\x55\x42\x49\
+                \x54\x41\x4b\x20\x4b\x61\x6d\x75\x20\x53\x4d\x20\x53\x53\x4c\x20\
+                \x4b\x6f\x6b\x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x69\x20\
+                \x2d\x20\x53\x75\x72\x75\x6d\x20\x31\x30\x82\x01\x22\x30\x0d\x06\
+                \x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x01\x05\x00\x03\x82\x01\x0f\
+                \x00\x30\x82\x01\x0a\x02\x82\x01\x01\x00\xaf\x75\x30\x33\xaa\xbb\
+                \x6b\xd3\x99\x2c\x12\x37\x84\xd9\x8d\x7b\x97\x80\xd3\x6e\xe7\xff\
+                \x9b\x50\x95\x3e\x90\x95\x56\x42\xd7\x19\x7c\x26\x84\x8d\x92\xfa\
+                \x01\x1d\x3a\x0f\xe2\x64\x38\xb7\x8c\xbc\xe8\x88\xf9\x8b\x24\xab\
+                \x2e\xa3\xf5\x37\xe4\x40\x8e\x18\x25\x79\x83\x75\x1f\x3b\xff\x6c\
+                \xa8\xc5\xc6\x56\xf8\xb4\xed\x8a\x44\xa3\xab\x6c\x4c\xfc\x1d\xd0\
+                \xdc\xef\x68\xbd\xcf\xe4\xaa\xce\xf0\x55\xf7\xa2\x34\xd4\x83\x6b\
+                \x37\x7c\x1c\xc2\xfe\xb5\x03\xec\x57\xce\xbc\xb4\xb5\xc5\xed\x00\
+                \x0f\x53\x37\x2a\x4d\xf4\x4f\x0c\x83\xfb\x86\xcf\xcb\xfe\x8c\x4e\
+                \xbd\x87\xf9\xa7\x8b\x21\x57\x9c\x7a\xdf\x03\x67\x89\x2c\x9d\x97\
+                \x61\xa7\x10\xb8\x55\x90\x7f\x0e\x2d\x27\x38\x74\xdf\xe7\xfd\xda\
+                \x4e\x12\xe3\x4d\x15\x22\x02\xc8\xe0\xe0\xfc\x0f\xad\x8a\xd7\xc9\
+                \x54\x50\xcc\x3b\x0f\xca\x16\x80\x84\xd0\x51\x56\xc3\x8e\x56\x7f\
+                \x89\x22\x33\x2f\xe6\x85\x0a\xbd\xa5\xa8\x1b\x36\xde\xd3\xdc\x2c\
+                \x6d\x3b\xc7\x13\xbd\x59\x23\x2c\xe6\xe5\xa4\xf7\xd8\x0b\xed\xea\
+                \x90\x40\x44\xa8\x95\xbb\x93\xd5\xd0\x80\x34\xb6\x46\x78\x0e\x1f\
+                \x00\x93\x46\xe1\xee\xe9\xf9\xec\x4f\x17\x02\x03\x01\x00\x01\xa3\
+                \x42\x30\x40\x30\x1d\x06\x03\x55\x1d\x0e\x04\x16\x04\x14\x65\x3f\
+                \xc7\x8a\x86\xc6\x3c\xdd\x3c\x54\x5c\x35\xf8\x3a\xed\x52\x0c\x47\
+                \x57\xc8\x30\x0e\x06\x03\x55\x1d\x0f\x01\x01\xff\x04\x04\x03\x02\
+                \x01\x06\x30\x0f\x06\x03\x55\x1d\x13\x01\x01\xff\x04\x05\x30\x03\
+                \x01\x01\xff\x30\x0d\x06\x09\x2a\x86\x48\x86\xf7\x0d\x01\x01\x0b\
+                \x05\x00\x03\x82\x01\x01\x00\x2a\x3f\xe1\xf1\x32\x8e\xae\xe1\x98\
+                \x5c\x4b\x5e\xcf\x6b\x1e\x6a\x09\xd2\x22\xa9\x12\xc7\x5e\x57\x7d\
+                \x73\x56\x64\x80\x84\x7a\x93\xe4\x09\xb9\x10\xcd\x9f\x2a\x27\xe1\
+                \x00\x77\xbe\x48\xc8\x35\xa8\x81\x9f\xe4\xb8\x2c\xc9\x7f\x0e\xb0\
+                \xd2\x4b\x37\x5d\xea\xb9\xd5\x0b\x5e\x34\xbd\xf4\x73\x29\xc3\xed\
+                \x26\x15\x9c\x7e\x08\x53\x8a\x58\x8d\xd0\x4b\x28\xdf\xc1\xb3\xdf\
+                \x20\xf3\xf9\xe3\xe3\x3a\xdf\xcc\x9c\x94\xd8\x4e\x4f\xc3\x6b\x17\
+                \xb7\xf7\x72\xe8\xad\x66\x33\xb5\x25\x53\xab\xe0\xf8\x4c\xa9\x9d\
+                \xfd\xf2\x0d\xba\xae\xb9\xd9\xaa\xc6\x6b\xf9\x93\xbb\xae\xab\xb8\
+                \x97\x3c\x03\x1a\xba\x43\xc6\x96\xb9\x45\x72\x38\xb3\xa7\xa1\x96\
+                \x3d\x91\x7b\x7e\xc0\x21\x53\x4c\x87\xed\xf2\x0b\x54\x95\x51\x93\
+                \xd5\x22\xa5\x0d\x8a\xf1\x93\x0e\x3e\x54\x0e\xb0\xd8\xc9\x4e\xdc\
+                \xf2\x31\x32\x56\xea\x64\xf9\xea\xb5\x9d\x16\x66\x42\x72\xf3\x7f\
+                \xd3\xb1\x31\x43\xfc\xa4\x8e\x17\xf1\x6d\x23\xab\x94\x66\xf8\xad\
+                \xfb\x0f\x08\x6e\x26\x2d\x7f\x17\x07\x09\xb2\x8c\xfb\x50\xc0\x9f\
+                \x96\x8d\xcf\xb6\xfd\x00\x9d\x5a\x14\x9a\xbf\x02\x44\xf5\xc1\xc2\
+                \x9f\x22\x5e\xa2\x0f\xa1\xe3",
+        issuer: b"\x30\x81\xd2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\
+                  \x31\x18\x30\x16\x06\x03\x55\x04\x07\x13\x0f\x47\x65\x62\x7a\x65\
+                  \x20\x2d\x20\x4b\x6f\x63\x61\x65\x6c\x69\x31\x42\x30\x40\x06\x03\
+                  \x55\x04\x0a\x13\x39\x54\x75\x72\x6b\x69\x79\x65\x20\x42\x69\x6c\
+                  \x69\x6d\x73\x65\x6c\x20\x76\x65\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\
+                  \x6a\x69\x6b\x20\x41\x72\x61\x73\x74\x69\x72\x6d\x61\x20\x4b\x75\
+                  \x72\x75\x6d\x75\x20\x2d\x20\x54\x55\x42\x49\x54\x41\x4b\x31\x2d\
+                  \x30\x2b\x06\x03\x55\x04\x0b\x13\x24\x4b\x61\x6d\x75\x20\x53\x65\
+                  \x72\x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\x65\x72\x6b\
+                  \x65\x7a\x69\x20\x2d\x20\x4b\x61\x6d\x75\x20\x53\x4d\x31\x36\x30\
+                  \x34\x06\x03\x55\x04\x03\x13\x2d\x54\x55\x42\x49\x54\x41\x4b\x20\
+                  \x4b\x61\x6d\x75\x20\x53\x4d\x20\x53\x53\x4c\x20\x4b\x6f\x6b\x20\
+                  \x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x69\x20\x2d\x20\x53\x75\
+                  \x72\x75\x6d\x20\x31",
+        serial: b"\x02\x01\x01",
+        subject: b"\x30\x81\xd2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\
+                   \x31\x18\x30\x16\x06\x03\x55\x04\x07\x13\x0f\x47\x65\x62\x7a\x65\
+                   \x20\x2d\x20\x4b\x6f\x63\x61\x65\x6c\x69\x31\x42\x30\x40\x06\x03\
+                   \x55\x04\x0a\x13\x39\x54\x75\x72\x6b\x69\x79\x65\x20\x42\x69\x6c\
+                   \x69\x6d\x73\x65\x6c\x20\x76\x65\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\
+                   \x6a\x69\x6b\x20\x41\x72\x61\x73\x74\x69\x72\x6d\x61\x20\x4b\x75\
+                   \x72\x75\x6d\x75\x20\x2d\x20\x54\x55\x42\x49\x54\x41\x4b\x31\x2d\
+                   \x30\x2b\x06\x03\x55\x04\x0b\x13\x24\x4b\x61\x6d\x75\x20\x53\x65\
+                   \x72\x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\x65\x72\x6b\
+                   \x65\x7a\x69\x20\x2d\x20\x4b\x61\x6d\x75\x20\x53\x4d\x31\x36\x30\
+                   \x34\x06\x03\x55\x04\x03\x13\x2d\x54\x55\x42\x49\x54\x41\x4b\x20\
+                   \x4b\x61\x6d\x75\x20\x53\x4d\x20\x53\x53\x4c\x20\x4b\x6f\x6b\x20\
+                   \x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x69\x20\x2d\x20\x53\x75\
+                   \x72\x75\x6d\x20\x31",
+        server_distrust_after: None,
+        email_distrust_after: None,
+        mozilla_ca_policy: Some(true),
+    },
+], &[
+    ::nss_certdata_parser::TrustRef {
+        label: "ACCVRAIZ1",
+        issuer: b"\x30\x42\x31\x12\x30\x10\x06\x03\x55\x04\x03\x0c\x09\x41\x43\x43\
+                  \x56\x52\x41\x49\x5a\x31\x31\x10\x30\x0e\x06\x03\x55\x04\x0b\x0c\
+                  \x07\x50\x4b\x49\x41\x43\x43\x56\x31\x0d\x30\x0b\x06\x03\x55\x04\
+                  \x0a\x0c\x04\x41\x43\x43\x56\x31\x0b\x30\x09\x06\x03\x55\x04\x06\
+                  \x13\x02\x45\x53",
+        serial: b"\x02\x08\x5e\xc3\xb7\xa6\x43\x7f\xa4\xe0",
+        tls_server_trust: ::nss_certdata_parser::TrustLevel::TrustedDelegator,
+        email_trust: ::nss_certdata_parser::TrustLevel::TrustedDelegator,
+        code_signing_trust: ::nss_certdata_parser::TrustLevel::MustVerify,
+        md5: Some(b"\xd0\xa0\x5a\xee\x05\xb6\x09\x94\x21\xa1\x7d\xf1\xb2\x29\x82\x02"),
+        sha1: Some(b"\x93\x05\x7a\x88\x15\xc6\x4f\xce\x88\x2f\xfa\x91\x16\x52\x28\x78\
+                     \xbc\x53\x64\x17"),
+    },
+    ::nss_certdata_parser::TrustRef {
+        label: "Explicitly Distrust Example Compromised CA",
+        issuer: b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+                  \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+                  \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+                  \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+                  \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+                  \x64\x20\x43\x41",
+        serial: b"\x02\x06\x4a\x3c\x00\xde\x00\x00",
+        tls_server_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        email_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        code_signing_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        md5: Some(b"\xb3\x34\xa7\x33\x1d\xb8\xf3\x03\xb4\xcb\x7e\xb8\x1e\x0c\xb1\x2c"),
+        sha1: Some(b"\xa6\x12\x6b\x61\x96\x8c\xf0\x0a\x3b\xd1\x25\xef\xb0\x31\xe9\xca\
+                     \xab\x74\xa4\x7d"),
+    },
+    ::nss_certdata_parser::TrustRef {
+        label: "Explicitly Distrust Example Mis-issued Intermediate",
+        issuer: b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+                  \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+                  \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+                  \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+                  \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+                  \x64\x20\x43\x41",
+        serial: b"\x02\x06\x4a\x3c\x00\xde\x00\x01",
+        tls_server_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        email_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        code_signing_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        md5: Some(b"\xfc\x25\xb7\xa2\xb8\x12\x7e\x47\x6a\xaf\x04\xed\xf7\x3b\xad\x5f"),
+        sha1: Some(b"\xf0\xcd\x4b\x1f\x7a\x25\x9a\xfa\x62\xc3\x66\x58\x0a\xd3\x0e\xcd\
+                     \x3a\xc4\x48\xfd"),
+    },
+    ::nss_certdata_parser::TrustRef {
+        label: "Explicitly Distrust Example Fraudulent Server",
+        issuer: b"\x30\x52\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x58\x58\x31\
+                  \x22\x30\x20\x06\x03\x55\x04\x0a\x0c\x19\x45\x78\x61\x6d\x70\x6c\
+                  \x65\x20\x44\x69\x73\x74\x72\x75\x73\x74\x20\x46\x69\x78\x74\x75\
+                  \x72\x65\x73\x31\x1f\x30\x1d\x06\x03\x55\x04\x03\x0c\x16\x45\x78\
+                  \x61\x6d\x70\x6c\x65\x20\x43\x6f\x6d\x70\x72\x6f\x6d\x69\x73\x65\
+                  \x64\x20\x43\x41",
+        serial: b"\x02\x06\x4a\x3c\x00\xde\x00\x02",
+        tls_server_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        email_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        code_signing_trust: ::nss_certdata_parser::TrustLevel::Distrust,
+        md5: None,
+        sha1: None,
+    },
+    ::nss_certdata_parser::TrustRef {
+        label: "E-Tugra Certification Authority",
+        issuer: b"\x30\x81\xb2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\
+                  \x31\x0f\x30\x0d\x06\x03\x55\x04\x07\x0c\x06\x41\x6e\x6b\x61\x72\
+                  \x61\x31\x40\x30\x3e\x06\x03\x55\x04\x0a\x0c\x37\x45\x2d\x54\x75\
+                  \xc4\x9f\x72\x61\x20\x45\x42\x47\x20\x42\x69\x6c\x69\xc5\x9f\x69\
+                  \x6d\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\x6a\x69\x6c\x65\x72\x69\x20\
+                  \x76\x65\x20\x48\x69\x7a\x6d\x65\x74\x6c\x65\x72\x69\x20\x41\x2e\
+                  \xc5\x9e\x2e\x31\x26\x30\x24\x06\x03\x55\x04\x0b\x0c\x1d\x45\x2d\
+                  \x54\x75\x67\x72\x61\x20\x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\
+                  \x79\x6f\x6e\x20\x4d\x65\x72\x6b\x65\x7a\x69\x31\x28\x30\x26\x06\
+                  \x03\x55\x04\x03\x0c\x1f\x45\x2d\x54\x75\x67\x72\x61\x20\x43\x65\
+                  \x72\x74\x69\x66\x69\x63\x61\x74\x69\x6f\x6e\x20\x41\x75\x74\x68\
+                  \x6f\x72\x69\x74\x79",
+        serial: b"\x02\x08\x6a\x68\x3e\x9c\x51\x9b\xcb\x53",
+        tls_server_trust: ::nss_certdata_parser::TrustLevel::TrustedDelegator,
+        email_trust: ::nss_certdata_parser::TrustLevel::MustVerify,
+        code_signing_trust: ::nss_certdata_parser::TrustLevel::MustVerify,
+        md5: Some(b"\xb8\xa1\x03\x63\xb0\xbd\x21\x71\x70\x8a\x6f\x13\x3a\xbb\x79\x49"),
+        sha1: Some(b"\x51\xc6\xe7\x08\x49\x06\x6e\xf3\x92\xd4\x5c\xa0\x0d\x6d\xa3\x62\
+                     \x8f\xc3\x52\x39"),
+    },
+    ::nss_certdata_parser::TrustRef {
+        label: "TUBITAK Kamu SM SSL Kok Sertifikasi - Surum 1",
+        issuer: b"\x30\x81\xd2\x31\x0b\x30\x09\x06\x03\x55\x04\x06\x13\x02\x54\x52\
+                  \x31\x18\x30\x16\x06\x03\x55\x04\x07\x13\x0f\x47\x65\x62\x7a\x65\
+                  \x20\x2d\x20\x4b\x6f\x63\x61\x65\x6c\x69\x31\x42\x30\x40\x06\x03\
+                  \x55\x04\x0a\x13\x39\x54\x75\x72\x6b\x69\x79\x65\x20\x42\x69\x6c\
+                  \x69\x6d\x73\x65\x6c\x20\x76\x65\x20\x54\x65\x6b\x6e\x6f\x6c\x6f\
+                  \x6a\x69\x6b\x20\x41\x72\x61\x73\x74\x69\x72\x6d\x61\x20\x4b\x75\
+                  \x72\x75\x6d\x75\x20\x2d\x20\x54\x55\x42\x49\x54\x41\x4b\x31\x2d\
+                  \x30\x2b\x06\x03\x55\x04\x0b\x13\x24\x4b\x61\x6d\x75\x20\x53\x65\
+                  \x72\x74\x69\x66\x69\x6b\x61\x73\x79\x6f\x6e\x20\x4d\x65\x72\x6b\
+                  \x65\x7a\x69\x20\x2d\x20\x4b\x61\x6d\x75\x20\x53\x4d\x31\x36\x30\
+                  \x34\x06\x03\x55\x04\x03\x13\x2d\x54\x55\x42\x49\x54\x41\x4b\x20\
+                  \x4b\x61\x6d\x75\x20\x53\x4d\x20\x53\x53\x4c\x20\x4b\x6f\x6b\x20\
+                  \x53\x65\x72\x74\x69\x66\x69\x6b\x61\x73\x69\x20\x2d\x20\x53\x75\
+                  \x72\x75\x6d\x20\x31",
+        serial: b"\x02\x01\x01",
+        tls_server_trust: ::nss_certdata_parser::TrustLevel::TrustedDelegator,
+        email_trust: ::nss_certdata_parser::TrustLevel::MustVerify,
+        code_signing_trust: ::nss_certdata_parser::TrustLevel::MustVerify,
+        md5: Some(b"\xdc\x00\x81\xdc\x69\x2f\x3e\x2f\xb0\x3b\xf6\x3d\x5a\x91\x8e\x49"),
+        sha1: Some(b"\x31\x43\x64\x9b\xec\xce\x27\xec\xed\x3a\x3f\x0b\x8f\x0d\xe4\xe8\
+                     \x91\xdd\xee\xca"),
+    },
+]);
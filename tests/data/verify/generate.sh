@@ -0,0 +1,69 @@
+#!/bin/sh
+# Regenerates the test certificates in this directory (with OpenSSL 3.4
+# or later, for -not_before/-not_after).  The keys are thrown away, so
+# running this changes every file.
+#
+#   root            RSA 2048, self-signed
+#   int-p384        P-384, signed by root (RSA PKCS#1 v1.5, SHA-256),
+#                   pathLenConstraint 1
+#   int-p256        P-256, signed by int-p384 (ECDSA, SHA-384),
+#                   pathLenConstraint 0
+#   leaf            P-256, signed by int-p256 (ECDSA, SHA-256)
+#   int-ed25519     Ed25519, signed by root (RSA-PSS, SHA-256)
+#   leaf-ed25519    P-256, signed by int-ed25519 (Ed25519)
+#   leaf-of-leaf    P-256, signed by leaf, which isn't a CA
+#   sub-int         P-256 CA, signed by int-p256 despite its path length
+#   deep-leaf       P-256, signed by sub-int
+set -e
+cd "$(dirname "$0")"
+tmp=$(mktemp -d)
+trap 'rm -rf "$tmp"' EXIT
+
+dates="-not_before 20240101000000Z -not_after 20490101000000Z"
+ca_ext() {
+    printf 'basicConstraints=critical,CA:TRUE%s\nkeyUsage=critical,keyCertSign,cRLSign\n' "$1"
+    printf 'subjectKeyIdentifier=hash\nauthorityKeyIdentifier=keyid\n'
+}
+leaf_ext() {
+    printf 'basicConstraints=critical,CA:FALSE\nkeyUsage=critical,digitalSignature\n'
+    printf 'extendedKeyUsage=serverAuth\nsubjectAltName=DNS:%s\n' "$1"
+    printf 'subjectKeyIdentifier=hash\nauthorityKeyIdentifier=keyid\n'
+}
+
+# issue NAME SUBJECT KEYALG ISSUER SERIAL EXTFILE [SIGOPTS...]
+issue() {
+    name=$1 subj=$2 alg=$3 issuer=$4 serial=$5 ext=$6
+    shift 6
+    case $alg in
+        p256) keyopts="-algorithm EC -pkeyopt ec_paramgen_curve:P-256" ;;
+        p384) keyopts="-algorithm EC -pkeyopt ec_paramgen_curve:P-384" ;;
+        ed25519) keyopts="-algorithm ED25519" ;;
+    esac
+    openssl genpkey $keyopts -out "$tmp/$name.key"
+    openssl req -new -key "$tmp/$name.key" -subj "$subj" -out "$tmp/$name.csr" 2>/dev/null
+    openssl x509 -req -in "$tmp/$name.csr" -CA "$issuer.der" -CAform DER \
+        -CAkey "$tmp/$issuer.key" -set_serial "$serial" $dates -extfile "$ext" "$@" \
+        -outform DER -out "$name.der" 2>/dev/null
+}
+
+ca_ext ",pathlen:1" > "$tmp/pathlen1.ext"
+ca_ext ",pathlen:0" > "$tmp/pathlen0.ext"
+ca_ext "" > "$tmp/ca.ext"
+leaf_ext leaf.example > "$tmp/leaf.ext"
+leaf_ext ed25519.example > "$tmp/leaf-ed25519.ext"
+
+openssl genpkey -algorithm RSA -pkeyopt rsa_keygen_bits:2048 -out "$tmp/root.key" 2>/dev/null
+openssl req -new -key "$tmp/root.key" -subj "/C=XX/O=Test/CN=Test Root" -out "$tmp/root.csr"
+openssl x509 -req -in "$tmp/root.csr" -key "$tmp/root.key" -set_serial 1 $dates \
+    -extfile "$tmp/ca.ext" -sha256 -outform DER -out root.der 2>/dev/null
+
+issue int-p384 "/C=XX/O=Test/CN=Test P-384 Intermediate" p384 root 2 "$tmp/pathlen1.ext" -sha256
+issue int-p256 "/C=XX/O=Test/CN=Test P-256 Intermediate" p256 int-p384 3 "$tmp/pathlen0.ext" \
+    -sha384
+issue leaf "/CN=leaf.example" p256 int-p256 4 "$tmp/leaf.ext" -sha256
+issue int-ed25519 "/C=XX/O=Test/CN=Test Ed25519 Intermediate" ed25519 root 5 "$tmp/ca.ext" \
+    -sha256 -sigopt rsa_padding_mode:pss -sigopt rsa_pss_saltlen:digest
+issue leaf-ed25519 "/CN=ed25519.example" p256 int-ed25519 6 "$tmp/leaf-ed25519.ext"
+issue leaf-of-leaf "/CN=leaf-of-leaf.example" p256 leaf 7 "$tmp/leaf.ext" -sha256
+issue sub-int "/C=XX/O=Test/CN=Test Sub-Intermediate" p256 int-p256 8 "$tmp/ca.ext" -sha256
+issue deep-leaf "/CN=deep.example" p256 sub-int 9 "$tmp/leaf.ext" -sha256